@@ -5,17 +5,50 @@ use std::{
 
 use uuid::Uuid;
 
-use crate::storage::{AddressData, ContactData, CookAndRunData, HostingData, PlanData};
+use crate::{
+    storage::{AddressData, ContactData, CookAndRunData, HostingData, PlanData},
+    validation,
+};
 
 #[derive(Debug, Clone)]
 pub struct Calculator {
     contact_list: HashMap<Uuid, ContactData>,
     course_list: Vec<Uuid>,
-    course_with_more_hosts: Option<Uuid>,
+    course_name_map: HashMap<Uuid, String>,
+    course_with_more_hosts_list: Vec<Uuid>,
+    guests_per_hosting: Option<usize>,
     start_point: Option<AddressData>,
     end_point: Option<AddressData>,
     top_plan: Arc<Mutex<Option<Plan>>>,
     should_stop: Arc<Mutex<bool>>,
+    // Seconds without a better plan after which the improvement stops
+    stop_after_no_improvement: Option<u32>,
+    history: Arc<Mutex<Vec<IterationStat>>>,
+    improvement: Arc<Mutex<Option<Improvement>>>,
+}
+
+// State of the improvement between two iterations, so the caller can hand control
+// back to the browser after each of them
+#[derive(Debug)]
+struct Improvement {
+    plan: Plan,
+    meeting_tracker: MeetingTracker,
+    repeated_meeting_count: u32,
+    // Swaps must not make teams meet more often than in the first plan
+    max_repeated_meeting_count: u32,
+    iteration: usize,
+    start_time: chrono::DateTime<chrono::Utc>,
+    last_improvement: chrono::DateTime<chrono::Utc>,
+}
+
+// Plan after swapping two guests of the same course
+struct Neighbour {
+    plan: Plan,
+    guest_a: Uuid,
+    member_list_a: Vec<Uuid>,
+    guest_b: Uuid,
+    member_list_b: Vec<Uuid>,
+    repeated_meeting_delta: i64,
 }
 
 #[derive(Default, Debug, Clone, PartialEq)]
@@ -25,6 +58,30 @@ struct Plan {
     greatest_distance: f64,
 }
 
+#[derive(Debug, Clone, PartialEq)]
+pub struct CancellationData {
+    pub plan: PlanData,
+    pub affected_team_list: Vec<Uuid /*Contact ID */>,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct PlanEditData {
+    pub plan: PlanData,
+    // Rule violations, a plan with errors must not be saved
+    pub error_list: Vec<String>,
+    pub warning_list: Vec<String>,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct IterationStat {
+    pub iteration: usize,
+    pub elapsed_seconds: f64,
+    pub best_score: f64,
+    // Scores of the plans looked at in this iteration
+    pub population_best: f64,
+    pub population_mean: f64,
+}
+
 #[derive(Debug)]
 struct MeetingTracker {
     meetings: HashMap<Uuid, HashMap<Uuid, u32>>,
@@ -37,19 +94,75 @@ impl MeetingTracker {
         }
     }
 
-    fn record_meeting(&mut self, person1: Uuid, person2: Uuid) {
+    fn from_hosting_list<'a>(hosting_list: impl Iterator<Item = &'a HostingData>) -> Self {
+        let mut meeting_tracker = Self::new();
+        for hosting in hosting_list {
+            for (index, &guest) in hosting.guest_list.iter().enumerate() {
+                meeting_tracker.record_meeting(hosting.host, guest);
+                for &other_guest in &hosting.guest_list[index + 1..] {
+                    meeting_tracker.record_meeting(guest, other_guest);
+                }
+            }
+        }
+        meeting_tracker
+    }
+
+    // Returns how often the two have met now
+    fn record_meeting(&mut self, person1: Uuid, person2: Uuid) -> u32 {
         *self
             .meetings
             .entry(person1)
             .or_default()
             .entry(person2)
             .or_default() += 1;
-        *self
+        let count = self
             .meetings
             .entry(person2)
             .or_default()
             .entry(person1)
-            .or_default() += 1;
+            .or_default();
+        *count += 1;
+        *count
+    }
+
+    // Returns how often the two have met now
+    fn remove_meeting(&mut self, person1: Uuid, person2: Uuid) -> u32 {
+        let mut count = 0;
+        for (from, to) in [(person1, person2), (person2, person1)] {
+            if let Some(meeting_count) = self.meetings.get_mut(&from).and_then(|m| m.get_mut(&to)) {
+                *meeting_count = meeting_count.saturating_sub(1);
+                count = *meeting_count;
+            }
+        }
+        count
+    }
+
+    // Moves guest a from the members of its table to the table of guest b and the other
+    // way round. Returns the change of the repeated meeting count, swapping the member
+    // lists undoes the swap.
+    fn swap_guests(
+        &mut self,
+        guest_a: Uuid,
+        member_list_a: &[Uuid],
+        guest_b: Uuid,
+        member_list_b: &[Uuid],
+    ) -> i64 {
+        let mut delta = 0;
+        for (guest, member_list) in [(guest_a, member_list_a), (guest_b, member_list_b)] {
+            for &member in member_list {
+                if self.remove_meeting(guest, member) >= 1 {
+                    delta -= 1;
+                }
+            }
+        }
+        for (guest, member_list) in [(guest_a, member_list_b), (guest_b, member_list_a)] {
+            for &member in member_list {
+                if self.record_meeting(guest, member) >= 2 {
+                    delta += 1;
+                }
+            }
+        }
+        delta
     }
 
     fn get_meeting_count(&self, person1: Uuid, person2: Uuid) -> u32 {
@@ -70,6 +183,18 @@ impl MeetingTracker {
             .map(|m| m.values().sum())
             .unwrap_or(0)
     }
+
+    // Every meeting of two teams beyond their first one
+    fn repeated_meeting_count(&self) -> u32 {
+        let count: u32 = self
+            .meetings
+            .values()
+            .flat_map(|m| m.values())
+            .map(|count| count.saturating_sub(1))
+            .sum();
+        // Each meeting is recorded for both teams
+        count / 2
+    }
 }
 
 impl Plan {
@@ -223,19 +348,26 @@ impl Calculator {
         println!("Creating Calculator");
         let mut course_list = cook_and_run_data.course_list.clone();
         course_list.sort_by(|a, b| a.time.cmp(&b.time));
+        let course_name_map = course_list.iter().map(|c| (c.id, c.name.clone())).collect();
         let course_list = course_list.iter().map(|c| c.id).collect();
         let calc = Calculator {
             contact_list: cook_and_run_data
                 .contact_list
                 .iter()
+                .filter(|c| c.is_approved())
                 .map(|c| (c.id, c.clone()))
                 .collect(),
             course_list,
-            course_with_more_hosts: cook_and_run_data.course_with_more_hosts.clone(),
+            course_name_map,
+            course_with_more_hosts_list: cook_and_run_data.course_with_more_hosts_list.clone(),
+            guests_per_hosting: cook_and_run_data.guests_per_hosting.map(|g| g as usize),
             start_point: cook_and_run_data.start_point.clone().map(|s| s.address),
             end_point: cook_and_run_data.end_point.clone().map(|e| e.address),
             top_plan: Arc::new(Mutex::new(None)),
             should_stop: Arc::new(Mutex::new(false)),
+            stop_after_no_improvement: None,
+            history: Arc::new(Mutex::new(Vec::new())),
+            improvement: Arc::new(Mutex::new(None)),
         };
         let result = calc.check();
         if let Some(err) = result.err() {
@@ -246,12 +378,18 @@ impl Calculator {
     }
 
     pub fn calculate(&self) {
+        self.start_calculation();
+        while self.improve_step() {}
+    }
+
+    // Finds the first plan, improve_step() then improves it one iteration at a time
+    pub fn start_calculation(&self) {
         println!("Starting deterministic calculation...");
         let contact_list = self.contact_list.clone();
         let course_list = self.course_list.clone();
         let start_point = self.start_point.clone();
         let end_point = self.end_point.clone();
-        let course_with_more_hosts = self.course_with_more_hosts;
+        let hosting_number_map = self.get_number_of_hostings_per_course();
 
         let top_plan = Arc::clone(&self.top_plan);
         let should_stop = Arc::clone(&self.should_stop);
@@ -266,7 +404,7 @@ impl Calculator {
             &course_list,
             &start_point,
             &end_point,
-            &course_with_more_hosts,
+            &hosting_number_map,
         );
 
         match result {
@@ -290,7 +428,7 @@ impl Calculator {
                     &course_list,
                     &start_point,
                     &end_point,
-                    &course_with_more_hosts,
+                    &hosting_number_map,
                 ) {
                     println!("Found fallback PLAN with {} hostings", fallback_map.len());
                     let plan = Plan::new(
@@ -308,6 +446,198 @@ impl Calculator {
             }
         }
         // });
+        self.start_improvement();
+    }
+
+    pub fn set_stop_after_no_improvement(&mut self, seconds: Option<u32>) {
+        self.stop_after_no_improvement = seconds;
+    }
+
+    pub fn get_history(&self) -> Vec<IterationStat> {
+        self.history.lock().unwrap().clone()
+    }
+
+    // Swaps guests between the tables of a course as long as the greatest distance shrinks
+    // and no two teams meet more often than before
+    fn start_improvement(&self) {
+        let start_time = chrono::Utc::now();
+        self.history.lock().unwrap().clear();
+        let mut improvement = self.improvement.lock().unwrap();
+        let Some(plan) = self.top_plan.lock().unwrap().clone() else {
+            *improvement = None;
+            return;
+        };
+        let meeting_tracker = MeetingTracker::from_hosting_list(plan.hosting_list.values());
+        let repeated_meeting_count = meeting_tracker.repeated_meeting_count();
+        self.history.lock().unwrap().push(IterationStat {
+            iteration: 0,
+            elapsed_seconds: 0.0,
+            best_score: plan.greatest_distance,
+            population_best: plan.greatest_distance,
+            population_mean: plan.greatest_distance,
+        });
+        *improvement = Some(Improvement {
+            plan,
+            meeting_tracker,
+            repeated_meeting_count,
+            max_repeated_meeting_count: repeated_meeting_count,
+            iteration: 0,
+            start_time,
+            last_improvement: start_time,
+        });
+    }
+
+    // Runs one iteration of the improvement, returns false once it is done
+    pub fn improve_step(&self) -> bool {
+        let mut improvement_guard = self.improvement.lock().unwrap();
+        let Some(improvement) = improvement_guard.as_mut() else {
+            return false;
+        };
+        if *self.should_stop.lock().unwrap() {
+            *improvement_guard = None;
+            return false;
+        }
+        if let Some(stop_after) = self.stop_after_no_improvement {
+            if (chrono::Utc::now() - improvement.last_improvement).num_seconds()
+                >= stop_after as i64
+            {
+                println!("No better plan for {} seconds, stopping", stop_after);
+                *improvement_guard = None;
+                return false;
+            }
+        }
+
+        let neighbour_list = self.get_neighbour_list(improvement);
+        if neighbour_list.is_empty() {
+            *improvement_guard = None;
+            return false;
+        }
+        let population_mean = neighbour_list
+            .iter()
+            .map(|n| n.plan.greatest_distance)
+            .sum::<f64>()
+            / neighbour_list.len() as f64;
+        let best_neighbour = neighbour_list
+            .into_iter()
+            .min_by(|a, b| {
+                a.plan
+                    .greatest_distance
+                    .total_cmp(&b.plan.greatest_distance)
+            })
+            .expect("Expect neighbour");
+        let population_best = best_neighbour.plan.greatest_distance;
+
+        let improved = best_neighbour.plan.greatest_distance < improvement.plan.greatest_distance;
+        if improved {
+            improvement.meeting_tracker.swap_guests(
+                best_neighbour.guest_a,
+                &best_neighbour.member_list_a,
+                best_neighbour.guest_b,
+                &best_neighbour.member_list_b,
+            );
+            improvement.repeated_meeting_count = (improvement.repeated_meeting_count as i64
+                + best_neighbour.repeated_meeting_delta)
+                as u32;
+            improvement.plan = best_neighbour.plan;
+            improvement.last_improvement = chrono::Utc::now();
+            self.top_plan
+                .lock()
+                .unwrap()
+                .replace(improvement.plan.clone());
+        }
+        improvement.iteration += 1;
+        self.history.lock().unwrap().push(IterationStat {
+            iteration: improvement.iteration,
+            elapsed_seconds: (chrono::Utc::now() - improvement.start_time).num_milliseconds()
+                as f64
+                / 1000.0,
+            best_score: improvement.plan.greatest_distance,
+            population_best,
+            population_mean,
+        });
+        // No swap helps anymore
+        if !improved {
+            *improvement_guard = None;
+        }
+        improved
+    }
+
+    fn get_neighbour_list(&self, improvement: &mut Improvement) -> Vec<Neighbour> {
+        let Improvement {
+            plan,
+            meeting_tracker,
+            repeated_meeting_count,
+            max_repeated_meeting_count,
+            ..
+        } = improvement;
+        let hosting_list: Vec<&HostingData> = plan.hosting_list.values().collect();
+        let mut neighbour_list = Vec::new();
+        for (index, hosting_a) in hosting_list.iter().enumerate() {
+            for hosting_b in hosting_list[index + 1..]
+                .iter()
+                .filter(|h| h.name == hosting_a.name)
+            {
+                for &guest_a in hosting_a.guest_list.iter() {
+                    for &guest_b in hosting_b.guest_list.iter() {
+                        let member_list = |hosting: &HostingData, guest: Uuid| -> Vec<Uuid> {
+                            std::iter::once(hosting.host)
+                                .chain(hosting.guest_list.iter().cloned())
+                                .filter(|member| *member != guest)
+                                .collect()
+                        };
+                        let member_list_a = member_list(hosting_a, guest_a);
+                        let member_list_b = member_list(hosting_b, guest_b);
+                        let repeated_meeting_delta = meeting_tracker.swap_guests(
+                            guest_a,
+                            &member_list_a,
+                            guest_b,
+                            &member_list_b,
+                        );
+                        meeting_tracker.swap_guests(
+                            guest_a,
+                            &member_list_b,
+                            guest_b,
+                            &member_list_a,
+                        );
+                        if *repeated_meeting_count as i64 + repeated_meeting_delta
+                            > *max_repeated_meeting_count as i64
+                        {
+                            continue;
+                        }
+
+                        let mut hosting_map = plan.hosting_list.clone();
+                        for (hosting_id, from, to) in [
+                            (hosting_a.id, guest_a, guest_b),
+                            (hosting_b.id, guest_b, guest_a),
+                        ] {
+                            let hosting = hosting_map
+                                .get_mut(&hosting_id)
+                                .expect("Expect to find Hosting");
+                            for guest in hosting.guest_list.iter_mut() {
+                                if *guest == from {
+                                    *guest = to;
+                                }
+                            }
+                        }
+                        neighbour_list.push(Neighbour {
+                            plan: Plan::new(
+                                &self.start_point,
+                                &self.end_point,
+                                &self.course_list,
+                                hosting_map,
+                                &self.contact_list,
+                            ),
+                            guest_a,
+                            member_list_a,
+                            guest_b,
+                            member_list_b,
+                            repeated_meeting_delta,
+                        });
+                    }
+                }
+            }
+        }
+        neighbour_list
     }
 
     fn calculate_optimal_plan(
@@ -315,7 +645,7 @@ impl Calculator {
         course_list: &Vec<Uuid>,
         start_point: &Option<AddressData>,
         end_point: &Option<AddressData>,
-        course_with_more_hosts: &Option<Uuid>,
+        hosting_number_map: &HashMap<Uuid, usize>,
     ) -> Result<HashMap<Uuid, HostingData>, String> {
         // Phase 1: Optimal host assignment
         let host_assignments = Self::assign_hosts_optimally(
@@ -323,7 +653,7 @@ impl Calculator {
             course_list,
             start_point,
             end_point,
-            course_with_more_hosts,
+            hosting_number_map,
         );
 
         // Phase 2: Deterministic guest assignment
@@ -335,7 +665,7 @@ impl Calculator {
         course_list: &Vec<Uuid>,
         start_point: &Option<AddressData>,
         end_point: &Option<AddressData>,
-        course_with_more_hosts: &Option<Uuid>,
+        hosting_number_map: &HashMap<Uuid, usize>,
     ) -> Result<HashMap<Uuid, HostingData>, String> {
         // Same as optimal but with relaxed meeting constraints
         let host_assignments = Self::assign_hosts_optimally(
@@ -343,7 +673,7 @@ impl Calculator {
             course_list,
             start_point,
             end_point,
-            course_with_more_hosts,
+            hosting_number_map,
         );
 
         Self::assign_guests_with_relaxed_constraints(host_assignments, contact_list, course_list)
@@ -354,7 +684,7 @@ impl Calculator {
         course_list: &Vec<Uuid>,
         start_point: &Option<AddressData>,
         end_point: &Option<AddressData>,
-        course_with_more_hosts: &Option<Uuid>,
+        hosting_number_map: &HashMap<Uuid, usize>,
     ) -> HashMap<Uuid, HostingData> {
         println!(
             "Assigning {} hosting with distance calculation",
@@ -388,39 +718,24 @@ impl Calculator {
         let contact_list = [contact_start_distance, contact_goal_distance].concat();
         println!("List of hosts sorted by distance: {:?}", contact_list);
 
-        let hosts_per_course = contact_list.len() / course_list.len();
-        let overhang = contact_list.len() % course_list.len();
         let mut hosting_list = HashMap::new();
+        let mut contact_iter = contact_list.into_iter();
 
-        let mut current_course_index = 0;
-        let mut current_course_hosts = 0;
-
-        println!("Assigne {} hosts per course", hosts_per_course);
-        println!("Overhang: {}", overhang);
+        for &course_id in course_list {
+            let number_of_hostings = hosting_number_map.get(&course_id).copied().unwrap_or(0);
+            println!(
+                "Assigne {} hosts to course {}",
+                number_of_hostings, course_id
+            );
 
-        for (contact, _) in contact_list {
-            let course_id = course_list[current_course_index];
-            let hosting_data = HostingData {
-                id: Uuid::new_v4(),
-                host: contact.id,
-                name: course_id,
-                guest_list: Vec::new(),
-            };
-            hosting_list.insert(hosting_data.id, hosting_data);
-
-            current_course_hosts += 1;
-            if current_course_hosts >= hosts_per_course {
-                if course_with_more_hosts.is_some()
-                    && course_with_more_hosts.expect("Expect uuid!") == course_id
-                {
-                    if current_course_hosts == hosts_per_course + overhang {
-                        current_course_index += 1;
-                        current_course_hosts = 0;
-                    }
-                } else {
-                    current_course_index += 1;
-                    current_course_hosts = 0;
-                }
+            for (contact, _) in contact_iter.by_ref().take(number_of_hostings) {
+                let hosting_data = HostingData {
+                    id: Uuid::new_v4(),
+                    host: contact.id,
+                    name: course_id,
+                    guest_list: Vec::new(),
+                };
+                hosting_list.insert(hosting_data.id, hosting_data);
             }
         }
 
@@ -601,44 +916,513 @@ impl Calculator {
         let plan = self.top_plan.lock().unwrap().clone();
         plan.map(|p| p.to_plan_data())
     }
+
+    // Each hosting seats the host and its guests. Without a configured number of
+    // guests there are as many seats as courses, so every team hosts once.
+    fn get_number_of_seats(&self) -> usize {
+        self.guests_per_hosting
+            .map(|guests_per_hosting| guests_per_hosting + 1)
+            .unwrap_or(self.course_list.len())
+    }
+
+    // If the teams don't fill every seat, courses with more hosts get one extra hosting,
+    // the same as in the desktop calculator
+    fn get_number_of_hostings_per_course(&self) -> HashMap<Uuid, usize> {
+        let number_of_seats = self.get_number_of_seats();
+        let base_number_of_hostings = self.contact_list.len() / number_of_seats;
+        let has_overhang = !self.contact_list.len().is_multiple_of(number_of_seats);
+
+        self.course_list
+            .iter()
+            .map(|course_id| {
+                let extra_hostings =
+                    if has_overhang && self.course_with_more_hosts_list.contains(course_id) {
+                        1
+                    } else {
+                        0
+                    };
+                (*course_id, base_number_of_hostings + extra_hostings)
+            })
+            .collect()
+    }
+}
+
+impl Calculator {
+    // Removes a team from an existing plan. Hostings of the team are dissolved and the
+    // guests are spread over the other hostings of the same course. If the other hostings
+    // can't take them, the closest guest who doesn't host yet takes over the hosting.
+    // All teams at a table whose members changed are affected, all others stay untouched.
+    pub fn cancel_team(
+        &self,
+        plan_data: &PlanData,
+        team_id: Uuid,
+    ) -> Result<CancellationData, String> {
+        if !plan_data.walking_path.contains_key(&team_id) {
+            return Err("Team is not part of the plan!".to_string());
+        }
+
+        let member_set = |hosting: &HostingData| -> HashSet<Uuid> {
+            std::iter::once(hosting.host)
+                .chain(hosting.guest_list.iter().copied())
+                .collect()
+        };
+        let old_member_map: HashMap<Uuid, HashSet<Uuid>> = plan_data
+            .hosting_list
+            .iter()
+            .map(|h| (h.id, member_set(h)))
+            .collect();
+
+        let mut hosting_list: HashMap<Uuid, HostingData> = plan_data
+            .hosting_list
+            .iter()
+            .map(|h| (h.id, h.clone()))
+            .collect();
+
+        for hosting in hosting_list.values_mut() {
+            hosting.guest_list.retain(|&g| g != team_id);
+        }
+
+        let cancelled_hosting_list: Vec<HostingData> = hosting_list
+            .values()
+            .filter(|h| h.host == team_id)
+            .cloned()
+            .collect();
+
+        for cancelled_hosting in cancelled_hosting_list {
+            hosting_list.remove(&cancelled_hosting.id);
+            let mut meeting_tracker = MeetingTracker::from_hosting_list(hosting_list.values());
+
+            let course_hosting_id_list: Vec<Uuid> = hosting_list
+                .values()
+                .filter(|h| h.name == cancelled_hosting.name)
+                .map(|h| h.id)
+                .collect();
+
+            // Taking over a hosting must not make a team host twice
+            let new_host = self.find_closest_guest(&cancelled_hosting, |guest| {
+                hosting_list.values().all(|h| h.host != guest)
+            });
+
+            if cancelled_hosting.guest_list.len() <= course_hosting_id_list.len()
+                || new_host.is_none()
+            {
+                if course_hosting_id_list.is_empty() {
+                    return Err("Course has no other hosting to take the guests!".to_string());
+                }
+                let old_address =
+                    Plan::get_address(&self.contact_list, &cancelled_hosting.host).clone();
+                for &guest in &cancelled_hosting.guest_list {
+                    let hosting_id = self.find_best_hosting(
+                        guest,
+                        &old_address,
+                        &course_hosting_id_list,
+                        &hosting_list,
+                        &meeting_tracker,
+                    );
+                    let hosting = hosting_list
+                        .get_mut(&hosting_id)
+                        .expect("Expect to find Hosting");
+
+                    meeting_tracker.record_meeting(hosting.host, guest);
+                    for &other_guest in &hosting.guest_list {
+                        meeting_tracker.record_meeting(guest, other_guest);
+                    }
+                    hosting.guest_list.push(guest);
+                }
+            } else {
+                // The group stays together at the table of the promoted team
+                let new_host = new_host.expect("Expect new host");
+                let mut hosting_data = cancelled_hosting.clone();
+                hosting_data.host = new_host;
+                hosting_data.guest_list.retain(|&g| g != new_host);
+                hosting_list.insert(hosting_data.id, hosting_data);
+            }
+        }
+
+        let mut affected_team_set = HashSet::new();
+        for hosting in hosting_list.values() {
+            let new_member_set = member_set(hosting);
+            if old_member_map.get(&hosting.id) != Some(&new_member_set) {
+                affected_team_set.extend(new_member_set);
+            }
+        }
+        let mut affected_team_list: Vec<Uuid> = affected_team_set.into_iter().collect();
+        affected_team_list.sort();
+
+        let plan = Plan::new(
+            &self.start_point,
+            &self.end_point,
+            &self.course_list,
+            hosting_list,
+            &self.contact_list,
+        );
+
+        let mut plan = plan.to_plan_data();
+        plan.id = plan_data.id;
+
+        // The cancelled team is no longer expected in the plan
+        let mut calculator = self.clone();
+        calculator.contact_list.remove(&team_id);
+        let plan_edit_data = calculator.check_plan(&plan)?;
+        if !plan_edit_data.error_list.is_empty() {
+            return Err(format!(
+                "Team can't be cancelled without breaking the plan: {}",
+                plan_edit_data.error_list.join(", ")
+            ));
+        }
+        let plan = plan_edit_data.plan;
+
+        Ok(CancellationData {
+            plan,
+            affected_team_list,
+        })
+    }
+
+    fn find_best_hosting(
+        &self,
+        guest: Uuid,
+        old_address: &AddressData,
+        course_hosting_id_list: &[Uuid],
+        hosting_list: &HashMap<Uuid, HostingData>,
+        meeting_tracker: &MeetingTracker,
+    ) -> Uuid {
+        // Smallest group first, then fewest repeated meetings, then shortest detour
+        let hosting_key = |hosting: &HostingData| {
+            let meetings: u32 = std::iter::once(hosting.host)
+                .chain(hosting.guest_list.iter().copied())
+                .map(|team| meeting_tracker.get_meeting_count(guest, team))
+                .sum();
+            let distance =
+                old_address.distance(Plan::get_address(&self.contact_list, &hosting.host));
+            (hosting.guest_list.len(), meetings, distance)
+        };
+
+        course_hosting_id_list
+            .iter()
+            .map(|id| hosting_list.get(id).expect("Expect to find Hosting"))
+            .min_by(|a, b| {
+                hosting_key(a)
+                    .partial_cmp(&hosting_key(b))
+                    .unwrap_or(std::cmp::Ordering::Equal)
+            })
+            .map(|hosting| hosting.id)
+            .expect("Expect at least one Hosting in course")
+    }
+
+    fn find_closest_guest(
+        &self,
+        hosting: &HostingData,
+        is_candidate: impl Fn(Uuid) -> bool,
+    ) -> Option<Uuid> {
+        let host_address = Plan::get_address(&self.contact_list, &hosting.host);
+        hosting
+            .guest_list
+            .iter()
+            .copied()
+            .filter(|&guest| is_candidate(guest))
+            .min_by(|a, b| {
+                let distance_a = host_address.distance(Plan::get_address(&self.contact_list, a));
+                let distance_b = host_address.distance(Plan::get_address(&self.contact_list, b));
+                distance_a
+                    .partial_cmp(&distance_b)
+                    .unwrap_or(std::cmp::Ordering::Equal)
+            })
+    }
+}
+
+impl Calculator {
+    // Moves a guest team to another hosting of the same course
+    pub fn move_guest(
+        &self,
+        plan_data: &PlanData,
+        guest: Uuid,
+        to_hosting_id: Uuid,
+    ) -> Result<PlanEditData, String> {
+        let mut hosting_list = plan_data.hosting_list.clone();
+        let to_hosting = hosting_list
+            .iter()
+            .find(|h| h.id == to_hosting_id)
+            .ok_or("Hosting not found!".to_string())?;
+        let course_id = to_hosting.name;
+        if to_hosting.host == guest || to_hosting.guest_list.contains(&guest) {
+            return Err("Team is already at this hosting!".to_string());
+        }
+
+        let from_hosting = hosting_list
+            .iter_mut()
+            .find(|h| h.name == course_id && h.guest_list.contains(&guest))
+            .ok_or("Team is not a guest in this course!".to_string())?;
+        from_hosting.guest_list.retain(|&g| g != guest);
+        hosting_list
+            .iter_mut()
+            .find(|h| h.id == to_hosting_id)
+            .expect("Expect to find Hosting")
+            .guest_list
+            .push(guest);
+
+        self.check_plan(&PlanData {
+            hosting_list,
+            ..plan_data.clone()
+        })
+    }
+
+    // Swaps the hosts of two hostings. In each course the new host leaves the table it was
+    // a guest at and the old host takes its seat.
+    pub fn swap_hosts(
+        &self,
+        plan_data: &PlanData,
+        hosting_id_a: Uuid,
+        hosting_id_b: Uuid,
+    ) -> Result<PlanEditData, String> {
+        let mut hosting_list = plan_data.hosting_list.clone();
+        let hosting_a = hosting_list
+            .iter()
+            .find(|h| h.id == hosting_id_a)
+            .ok_or("Hosting not found!".to_string())?
+            .clone();
+        let hosting_b = hosting_list
+            .iter()
+            .find(|h| h.id == hosting_id_b)
+            .ok_or("Hosting not found!".to_string())?
+            .clone();
+        if hosting_a.id == hosting_b.id {
+            return Err("Hosting can't be swapped with itself!".to_string());
+        }
+
+        for (hosting, old_host, new_host) in [
+            (&hosting_a, hosting_a.host, hosting_b.host),
+            (&hosting_b, hosting_b.host, hosting_a.host),
+        ] {
+            for other in hosting_list.iter_mut().filter(|h| h.name == hosting.name) {
+                if other.id == hosting.id {
+                    other.host = new_host;
+                }
+                // The new host doesn't sit at its own table as a guest
+                if hosting_a.name != hosting_b.name {
+                    for guest in other.guest_list.iter_mut() {
+                        if *guest == new_host {
+                            *guest = old_host;
+                        }
+                    }
+                }
+            }
+        }
+
+        self.check_plan(&PlanData {
+            hosting_list,
+            ..plan_data.clone()
+        })
+    }
+
+    // Recomputes walking paths and the greatest distance and lists rule violations
+    pub fn check_plan(&self, plan_data: &PlanData) -> Result<PlanEditData, String> {
+        let team_name = |id: &Uuid| {
+            self.contact_list
+                .get(id)
+                .map_or(id.to_string(), |c| c.team_name.clone())
+        };
+        let course_name = |id: &Uuid| {
+            self.course_name_map
+                .get(id)
+                .cloned()
+                .unwrap_or(id.to_string())
+        };
+
+        for hosting in plan_data.hosting_list.iter() {
+            if !self.course_list.contains(&hosting.name) {
+                return Err("Plan contains an unknown course!".to_string());
+            }
+            if std::iter::once(&hosting.host)
+                .chain(hosting.guest_list.iter())
+                .any(|id| !self.contact_list.contains_key(id))
+            {
+                return Err("Plan contains an unknown team!".to_string());
+            }
+        }
+
+        let mut warning_list = Vec::new();
+
+        let mut contact_id_list: Vec<&Uuid> = self.contact_list.keys().collect();
+        contact_id_list.sort_by_key(|id| team_name(id));
+
+        for hosting in plan_data.hosting_list.iter() {
+            if hosting.guest_list.is_empty() {
+                warning_list.push(format!(
+                    "{} has no guests at {}",
+                    team_name(&hosting.host),
+                    course_name(&hosting.name)
+                ));
+            }
+        }
+
+        let meeting_tracker = MeetingTracker::from_hosting_list(plan_data.hosting_list.iter());
+        for (index, contact_a) in contact_id_list.iter().enumerate() {
+            for contact_b in contact_id_list[index + 1..].iter() {
+                let meeting_count = meeting_tracker.get_meeting_count(**contact_a, **contact_b);
+                if meeting_count > 1 {
+                    warning_list.push(format!(
+                        "{} meets {} {} times",
+                        team_name(contact_a),
+                        team_name(contact_b),
+                        meeting_count
+                    ));
+                }
+            }
+        }
+
+        let hosting_map = plan_data
+            .hosting_list
+            .iter()
+            .map(|h| (h.id, h.clone()))
+            .collect();
+        let new_plan = Plan::new(
+            &self.start_point,
+            &self.end_point,
+            &self.course_list,
+            hosting_map,
+            &self.contact_list,
+        );
+        let plan = PlanData {
+            walking_path: new_plan.walking_path,
+            greatest_distance: new_plan.greatest_distance,
+            ..plan_data.clone()
+        };
+
+        let course_list: Vec<(Uuid, String)> = self
+            .course_list
+            .iter()
+            .map(|id| (*id, course_name(id)))
+            .collect();
+        let team_list: Vec<(Uuid, String)> = contact_id_list
+            .iter()
+            .map(|id| (**id, team_name(id)))
+            .collect();
+        let error_list =
+            validation::validate_hosting_list(&course_list, &team_list, &plan).message_list();
+
+        Ok(PlanEditData {
+            plan,
+            error_list,
+            warning_list,
+        })
+    }
 }
 
 impl Calculator {
     fn check(&self) -> Result<(), String> {
+        self.check_number_of_courses()?;
         self.check_min_number_of_contacts()?;
+        self.check_guests_per_hosting()?;
         self.check_overhang()?;
+        self.check_number_of_hostings()?;
         Ok(())
     }
 
+    fn check_guests_per_hosting(&self) -> Result<(), String> {
+        if self.get_number_of_seats() < 2 {
+            Err("Every hosting needs at least one guest!".to_string())
+        } else {
+            Ok(())
+        }
+    }
+
+    fn check_number_of_courses(&self) -> Result<(), String> {
+        if self.course_list.len() < 2 {
+            Err("At least two courses are needed!".to_string())
+        } else {
+            Ok(())
+        }
+    }
+
     fn check_min_number_of_contacts(&self) -> Result<(), String> {
-        if self.contact_list.len() < self.course_list.len() {
-            Err("There can't be more courses than contact's!".to_string())
+        if self.contact_list.len() < self.get_number_of_seats() {
+            Err(format!(
+                "A hosting seats {} teams, but there are only {} teams!",
+                self.get_number_of_seats(),
+                self.contact_list.len()
+            ))
         } else {
             Ok(())
         }
     }
 
     fn check_overhang(&self) -> Result<(), String> {
-        if self.contact_list.len() % self.course_list.len() != 0
-            && self.course_with_more_hosts.is_none()
+        if !self
+            .contact_list
+            .len()
+            .is_multiple_of(self.get_number_of_seats())
+            && !self
+                .course_list
+                .iter()
+                .any(|course_id| self.course_with_more_hosts_list.contains(course_id))
         {
             Err("A course with more hosts has to be set!".to_string())
         } else {
             Ok(())
         }
     }
+
+    fn check_number_of_hostings(&self) -> Result<(), String> {
+        let number_of_hostings: usize = self.get_number_of_hostings_per_course().values().sum();
+        if number_of_hostings > self.contact_list.len() {
+            Err(format!(
+                "{} hostings are needed, but there are only {} teams!",
+                number_of_hostings,
+                self.contact_list.len()
+            ))
+        } else {
+            Ok(())
+        }
+    }
 }
 
 #[cfg(test)]
 mod tests {
 
+    use std::collections::HashSet;
+
     use uuid::Uuid;
 
     use crate::{
         calculator::{Calculator, MeetingTracker},
-        storage::{ContactData, CookAndRunData, CourseData},
+        storage::{
+            AddressData, ContactData, CookAndRunData, CourseData, HostingData, MeetingPointData,
+        },
     };
 
+    fn get_cook_and_run_data(number_of_contacts: usize, number_of_courses: u32) -> CookAndRunData {
+        let mut cook_and_run_data = CookAndRunData::default();
+        for i in 0..number_of_courses {
+            cook_and_run_data.course_list.push(CourseData {
+                id: Uuid::new_v4(),
+                name: format!("Course {}", i),
+                time: chrono::NaiveTime::from_hms_opt(18 + i, 0, 0).unwrap(),
+            });
+        }
+        for i in 0..number_of_contacts {
+            cook_and_run_data.contact_list.push(ContactData {
+                id: Uuid::new_v4(),
+                team_name: format!("Team {}", i),
+                address: AddressData {
+                    address: format!("Street {}", i),
+                    latitude: 50.55 + i as f64 * 0.001,
+                    longitude: 9.67 + (i % 3) as f64 * 0.002,
+                },
+                ..Default::default()
+            });
+        }
+        cook_and_run_data.end_point = Some(MeetingPointData {
+            name: "Bar".to_string(),
+            time: chrono::NaiveTime::from_hms_opt(22, 0, 0).unwrap(),
+            address: AddressData {
+                address: "End".to_string(),
+                latitude: 50.55,
+                longitude: 9.67,
+            },
+        });
+        cook_and_run_data
+    }
+
     #[test]
     fn test_sorting_in_constructor() {
         let course_1 = CourseData {
@@ -706,4 +1490,314 @@ mod tests {
         assert!(tracker.has_met(person1, person2));
         assert_eq!(tracker.get_meeting_count(person1, person2), 1);
     }
+
+    #[test]
+    fn test_cancel_team() {
+        let cook_and_run_data = get_cook_and_run_data(9, 3);
+        let calculator = Calculator::new(&cook_and_run_data).unwrap();
+        calculator.calculate();
+        let plan = calculator.get_top_plan().expect("Expect plan");
+
+        for contact in cook_and_run_data.contact_list.iter() {
+            let result = calculator.cancel_team(&plan, contact.id);
+            assert!(result.is_ok(), "Cancellation failed: {:?}", result.err());
+            let result = result.unwrap();
+
+            assert_eq!(result.plan.id, plan.id, "Plan id should be kept");
+            assert!(!result.affected_team_list.contains(&contact.id));
+            assert!(!result.plan.walking_path.contains_key(&contact.id));
+            assert_eq!(result.plan.walking_path.len(), 8);
+
+            for hosting in result.plan.hosting_list.iter() {
+                assert_ne!(hosting.host, contact.id, "Cancelled team is still hosting");
+                assert!(
+                    !hosting.guest_list.contains(&contact.id),
+                    "Cancelled team is still a guest"
+                );
+            }
+
+            // Everybody at a table whose members changed has to be told
+            for hosting in result.plan.hosting_list.iter() {
+                let old_hosting = plan.hosting_list.iter().find(|h| h.id == hosting.id);
+                let member_set = |h: &HostingData| -> HashSet<Uuid> {
+                    std::iter::once(h.host)
+                        .chain(h.guest_list.iter().copied())
+                        .collect()
+                };
+                if old_hosting.map(member_set) != Some(member_set(hosting)) {
+                    for team in member_set(hosting) {
+                        assert!(result.affected_team_list.contains(&team));
+                    }
+                }
+            }
+
+            for (team, path) in result.plan.walking_path.iter() {
+                assert_eq!(path.len(), 3, "Every team should still visit every course");
+                if !result.affected_team_list.contains(team) {
+                    assert_eq!(
+                        Some(path),
+                        plan.walking_path.get(team),
+                        "Walking path of an unaffected team changed"
+                    );
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_only_approved_teams_are_planned() {
+        let mut cook_and_run_data = get_cook_and_run_data(11, 3);
+        cook_and_run_data.contact_list[9].needs_check = true;
+        cook_and_run_data.contact_list[10].rejected = true;
+
+        let calculator = Calculator::new(&cook_and_run_data).unwrap();
+        calculator.calculate();
+        let plan = calculator.get_top_plan().expect("Expect plan");
+
+        assert_eq!(plan.walking_path.len(), 9);
+        for contact in cook_and_run_data.contact_list[9..].iter() {
+            assert!(!plan.walking_path.contains_key(&contact.id));
+            assert!(plan
+                .hosting_list
+                .iter()
+                .all(|h| h.host != contact.id && !h.guest_list.contains(&contact.id)));
+        }
+    }
+
+    #[test]
+    fn test_cancel_unknown_team() {
+        let cook_and_run_data = get_cook_and_run_data(9, 3);
+        let calculator = Calculator::new(&cook_and_run_data).unwrap();
+        calculator.calculate();
+        let plan = calculator.get_top_plan().expect("Expect plan");
+
+        assert!(calculator.cancel_team(&plan, Uuid::new_v4()).is_err());
+    }
+
+    #[test]
+    fn test_guests_per_hosting() {
+        let mut cook_and_run_data = get_cook_and_run_data(12, 3);
+        cook_and_run_data.guests_per_hosting = Some(3);
+        let calculator = Calculator::new(&cook_and_run_data).unwrap();
+        calculator.calculate();
+        let plan = calculator.get_top_plan().expect("Expect plan");
+
+        assert_eq!(plan.hosting_list.len(), 9);
+        for hosting in plan.hosting_list.iter() {
+            assert_eq!(hosting.guest_list.len(), 3, "Every hosting seats 4 teams");
+        }
+
+        cook_and_run_data.guests_per_hosting = Some(1);
+        assert!(
+            Calculator::new(&cook_and_run_data).is_err(),
+            "More hostings than teams should fail"
+        );
+    }
+
+    #[test]
+    fn test_number_of_courses() {
+        for (number_of_contacts, number_of_courses) in [(8, 2), (16, 4)] {
+            let cook_and_run_data = get_cook_and_run_data(number_of_contacts, number_of_courses);
+            let calculator = Calculator::new(&cook_and_run_data).unwrap();
+            calculator.calculate();
+            let plan = calculator.get_top_plan().expect("Expect plan");
+
+            assert_eq!(plan.hosting_list.len(), number_of_contacts);
+            assert_eq!(plan.walking_path.len(), number_of_contacts);
+            for (_, path) in plan.walking_path.iter() {
+                assert_eq!(path.len(), number_of_courses as usize);
+            }
+        }
+
+        assert!(Calculator::new(&get_cook_and_run_data(9, 1)).is_err());
+        assert!(Calculator::new(&get_cook_and_run_data(3, 4)).is_err());
+    }
+
+    #[test]
+    fn test_move_guest() {
+        let cook_and_run_data = get_cook_and_run_data(9, 3);
+        let calculator = Calculator::new(&cook_and_run_data).unwrap();
+        calculator.calculate();
+        let plan = calculator.get_top_plan().expect("Expect plan");
+
+        let check = calculator.check_plan(&plan).unwrap();
+        assert!(check.error_list.is_empty(), "{:?}", check.error_list);
+
+        let from_hosting = plan.hosting_list[0].clone();
+        let guest = from_hosting.guest_list[0];
+        let to_hosting = plan
+            .hosting_list
+            .iter()
+            .find(|h| h.name == from_hosting.name && h.id != from_hosting.id)
+            .expect("Expect second hosting in course")
+            .clone();
+
+        let result = calculator.move_guest(&plan, guest, to_hosting.id).unwrap();
+        assert_eq!(result.plan.id, plan.id);
+        // Teams still visit every course once, only the guests aren't balanced anymore
+        assert_eq!(result.error_list.len(), 2, "{:?}", result.error_list);
+        let moved_to = result
+            .plan
+            .hosting_list
+            .iter()
+            .find(|h| h.id == to_hosting.id)
+            .unwrap();
+        assert!(moved_to.guest_list.contains(&guest));
+        assert!(result.plan.walking_path[&guest].contains(&to_hosting.id));
+        assert!(!result.plan.walking_path[&guest].contains(&from_hosting.id));
+
+        assert!(calculator
+            .move_guest(&plan, to_hosting.host, from_hosting.id)
+            .is_err());
+
+        // Moving another guest the other way balances the course again
+        let other_guest = to_hosting.guest_list[0];
+        let result = calculator
+            .move_guest(&result.plan, other_guest, from_hosting.id)
+            .unwrap();
+        assert!(result.error_list.is_empty(), "{:?}", result.error_list);
+    }
+
+    #[test]
+    fn test_swap_hosts() {
+        let cook_and_run_data = get_cook_and_run_data(9, 3);
+        let calculator = Calculator::new(&cook_and_run_data).unwrap();
+        calculator.calculate();
+        let plan = calculator.get_top_plan().expect("Expect plan");
+
+        let hosting_a = plan.hosting_list[0].clone();
+        let hosting_b = plan
+            .hosting_list
+            .iter()
+            .find(|h| h.name != hosting_a.name)
+            .expect("Expect hosting in other course")
+            .clone();
+
+        let result = calculator
+            .swap_hosts(&plan, hosting_a.id, hosting_b.id)
+            .unwrap();
+        assert!(result.error_list.is_empty(), "{:?}", result.error_list);
+        let find = |id| {
+            result
+                .plan
+                .hosting_list
+                .iter()
+                .find(|h| h.id == id)
+                .unwrap()
+        };
+        assert_eq!(find(hosting_a.id).host, hosting_b.host);
+        assert_eq!(find(hosting_b.id).host, hosting_a.host);
+
+        // Two teams hosting the same hosting breaks the rules
+        let mut broken_plan = plan.clone();
+        broken_plan.hosting_list[0].host = hosting_b.host;
+        let check = calculator.check_plan(&broken_plan).unwrap();
+        assert!(!check.error_list.is_empty());
+    }
+
+    #[test]
+    fn test_improvement_history() {
+        let cook_and_run_data = get_cook_and_run_data(12, 3);
+        let calculator = Calculator::new(&cook_and_run_data).unwrap();
+        calculator.calculate();
+        let plan = calculator.get_top_plan().expect("Expect plan");
+        let history = calculator.get_history();
+
+        assert!(!history.is_empty());
+        assert_eq!(history[0].iteration, 0);
+        for window in history.windows(2) {
+            assert!(window[1].best_score <= window[0].best_score);
+            assert!(window[1].population_best <= window[1].population_mean);
+        }
+        assert_eq!(history.last().unwrap().best_score, plan.greatest_distance);
+
+        let check = calculator.check_plan(&plan).unwrap();
+        assert!(check.error_list.is_empty(), "{:?}", check.error_list);
+    }
+
+    #[test]
+    fn test_number_of_hostings_per_course() {
+        // Same cases as the desktop calculator, both have to plan the same number of hostings
+        let parse_list = |field: &str| -> Vec<usize> {
+            match field {
+                "-" => Vec::new(),
+                field => field
+                    .split(',')
+                    .map(|number| number.parse().unwrap())
+                    .collect(),
+            }
+        };
+        for line in include_str!("../../tests/data/hostings_per_course.txt")
+            .lines()
+            .filter(|line| !line.starts_with('#'))
+        {
+            let field_list: Vec<&str> = line.split_whitespace().collect();
+            let mut cook_and_run_data = get_cook_and_run_data(
+                field_list[0].parse().unwrap(),
+                field_list[1].parse().unwrap(),
+            );
+            cook_and_run_data.guests_per_hosting = field_list[2].parse().ok();
+            cook_and_run_data.course_with_more_hosts_list = parse_list(field_list[3])
+                .into_iter()
+                .map(|index| cook_and_run_data.course_list[index].id)
+                .collect();
+            let calculator = Calculator::new(&cook_and_run_data).unwrap();
+            let hosting_number_map = calculator.get_number_of_hostings_per_course();
+            let hostings: Vec<usize> = cook_and_run_data
+                .course_list
+                .iter()
+                .map(|course| hosting_number_map[&course.id])
+                .collect();
+            assert_eq!(hostings, parse_list(field_list[4]), "{}", line);
+        }
+    }
+
+    #[test]
+    fn test_swap_guests_matches_rebuilt_tracker() {
+        let cook_and_run_data = get_cook_and_run_data(12, 3);
+        let calculator = Calculator::new(&cook_and_run_data).unwrap();
+        calculator.calculate();
+        let plan = calculator.top_plan.lock().unwrap().clone().unwrap();
+        let mut meeting_tracker = MeetingTracker::from_hosting_list(plan.hosting_list.values());
+        let repeated_meeting_count = meeting_tracker.repeated_meeting_count() as i64;
+
+        let hosting_list: Vec<&HostingData> = plan.hosting_list.values().collect();
+        for (index, hosting_a) in hosting_list.iter().enumerate() {
+            for hosting_b in hosting_list[index + 1..]
+                .iter()
+                .filter(|h| h.name == hosting_a.name)
+            {
+                let guest_a = hosting_a.guest_list[0];
+                let guest_b = hosting_b.guest_list[0];
+                let member_list = |hosting: &HostingData, guest: Uuid| -> Vec<Uuid> {
+                    std::iter::once(hosting.host)
+                        .chain(hosting.guest_list.iter().cloned())
+                        .filter(|member| *member != guest)
+                        .collect()
+                };
+                let member_list_a = member_list(hosting_a, guest_a);
+                let member_list_b = member_list(hosting_b, guest_b);
+                let delta =
+                    meeting_tracker.swap_guests(guest_a, &member_list_a, guest_b, &member_list_b);
+
+                let mut hosting_map = plan.hosting_list.clone();
+                hosting_map.get_mut(&hosting_a.id).unwrap().guest_list[0] = guest_b;
+                hosting_map.get_mut(&hosting_b.id).unwrap().guest_list[0] = guest_a;
+                let rebuilt = MeetingTracker::from_hosting_list(hosting_map.values());
+                assert_eq!(
+                    repeated_meeting_count + delta,
+                    rebuilt.repeated_meeting_count() as i64
+                );
+
+                // Swapping the member lists undoes the swap
+                let undo =
+                    meeting_tracker.swap_guests(guest_a, &member_list_b, guest_b, &member_list_a);
+                assert_eq!(undo, -delta);
+                assert_eq!(
+                    meeting_tracker.repeated_meeting_count() as i64,
+                    repeated_meeting_count
+                );
+            }
+        }
+    }
 }
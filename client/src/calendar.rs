@@ -0,0 +1,300 @@
+use chrono::{Duration, NaiveDateTime, Utc};
+use uuid::Uuid;
+
+use crate::storage::{
+    mapper::{Hosting, Plan},
+    CookAndRunData, CourseData, MeetingPointData,
+};
+
+// Length of the last course if no end point is set
+const DEFAULT_COURSE_MINUTES: i64 = 120;
+const MEETING_POINT_MINUTES: i64 = 30;
+
+struct Event {
+    uid: String,
+    start: NaiveDateTime,
+    end: NaiveDateTime,
+    summary: String,
+    location: String,
+    description: String,
+}
+
+fn escape(text: &str) -> String {
+    text.replace('\\', "\\\\")
+        .replace(';', "\\;")
+        .replace(',', "\\,")
+        .replace("\r\n", "\\n")
+        .replace('\n', "\\n")
+}
+
+// Lines longer than 75 bytes are continued on the next line starting with a space
+fn fold(line: &str) -> String {
+    let mut folded = String::new();
+    let mut length = 0;
+    for c in line.chars() {
+        if length + c.len_utf8() > 75 {
+            folded.push_str("\r\n ");
+            length = 1;
+        }
+        folded.push(c);
+        length += c.len_utf8();
+    }
+    folded
+}
+
+fn format_time(time: &NaiveDateTime) -> String {
+    time.format("%Y%m%dT%H%M%S").to_string()
+}
+
+fn to_ics(name: &str, event_list: &[Event]) -> String {
+    let stamp = Utc::now().format("%Y%m%dT%H%M%SZ").to_string();
+    let mut line_list = vec![
+        "BEGIN:VCALENDAR".to_string(),
+        "VERSION:2.0".to_string(),
+        "PRODID:-//tcc//Cook and Run//EN".to_string(),
+        "CALSCALE:GREGORIAN".to_string(),
+        format!("X-WR-CALNAME:{}", escape(name)),
+    ];
+    for event in event_list {
+        line_list.push("BEGIN:VEVENT".to_string());
+        line_list.push(format!("UID:{}", event.uid));
+        line_list.push(format!("DTSTAMP:{}", stamp));
+        line_list.push(format!("DTSTART:{}", format_time(&event.start)));
+        line_list.push(format!("DTEND:{}", format_time(&event.end)));
+        line_list.push(format!("SUMMARY:{}", escape(&event.summary)));
+        line_list.push(format!("LOCATION:{}", escape(&event.location)));
+        line_list.push(format!("DESCRIPTION:{}", escape(&event.description)));
+        line_list.push("END:VEVENT".to_string());
+    }
+    line_list.push("END:VCALENDAR".to_string());
+
+    line_list
+        .iter()
+        .map(|l| fold(l))
+        .collect::<Vec<String>>()
+        .join("\r\n")
+        + "\r\n"
+}
+
+// A course lasts until the next course starts, the last one until the end point
+fn course_end(cook_and_run: &CookAndRunData, course: &CourseData) -> NaiveDateTime {
+    let next_time = cook_and_run
+        .course_list
+        .iter()
+        .map(|c| c.time)
+        .filter(|t| *t > course.time)
+        .min()
+        .or(cook_and_run
+            .end_point
+            .as_ref()
+            .map(|e| e.time)
+            .filter(|t| *t > course.time));
+    match next_time {
+        Some(time) => cook_and_run.occur.and_time(time),
+        None => {
+            cook_and_run.occur.and_time(course.time) + Duration::minutes(DEFAULT_COURSE_MINUTES)
+        }
+    }
+}
+
+fn hosting_event(cook_and_run: &CookAndRunData, hosting: &Hosting, uid: String) -> Event {
+    let guest_names = hosting
+        .guest_list
+        .iter()
+        .map(|g| g.team_name.clone())
+        .collect::<Vec<String>>()
+        .join(", ");
+    Event {
+        uid,
+        start: cook_and_run.occur.and_time(hosting.course.time),
+        end: course_end(cook_and_run, &hosting.course),
+        summary: format!("{} at {}", hosting.course.name, hosting.host.team_name),
+        location: hosting.host.address.address.clone(),
+        description: format!(
+            "Course: {}\nHost: {}\nGuests: {}",
+            hosting.course.name, hosting.host.team_name, guest_names
+        ),
+    }
+}
+
+fn meeting_point_event(
+    cook_and_run: &CookAndRunData,
+    meeting_point: &MeetingPointData,
+    uid: String,
+) -> Event {
+    let start = cook_and_run.occur.and_time(meeting_point.time);
+    Event {
+        uid,
+        start,
+        end: start + Duration::minutes(MEETING_POINT_MINUTES),
+        summary: meeting_point.name.clone(),
+        location: meeting_point.address.address.clone(),
+        description: cook_and_run.plan_text.clone().unwrap_or_default(),
+    }
+}
+
+fn meeting_point_event_list(cook_and_run: &CookAndRunData) -> (Option<Event>, Option<Event>) {
+    let start = cook_and_run
+        .start_point
+        .as_ref()
+        .map(|s| meeting_point_event(cook_and_run, s, format!("start-{}@tcc", cook_and_run.id)));
+    let end = cook_and_run
+        .end_point
+        .as_ref()
+        .map(|e| meeting_point_event(cook_and_run, e, format!("end-{}@tcc", cook_and_run.id)));
+    (start, end)
+}
+
+pub fn team_calendar(
+    cook_and_run: &CookAndRunData,
+    plan: &Plan,
+    contact_id: Uuid,
+) -> Result<String, String> {
+    let (contact, walking_path) = plan
+        .walking_path
+        .iter()
+        .find(|(c, _)| c.id == contact_id)
+        .ok_or("Team is not part of the plan")?;
+
+    let (start, end) = meeting_point_event_list(cook_and_run);
+    let mut event_list: Vec<Event> = start.into_iter().collect();
+    let mut hosting_list = walking_path.clone();
+    hosting_list.sort_by_key(|h| h.course.time);
+    for hosting in hosting_list.iter() {
+        event_list.push(hosting_event(
+            cook_and_run,
+            hosting,
+            format!("{}-{}@tcc", hosting.id, contact.id),
+        ));
+    }
+    event_list.extend(end);
+
+    Ok(to_ics(
+        &format!("{} - {}", cook_and_run.name, contact.team_name),
+        &event_list,
+    ))
+}
+
+pub fn project_calendar(cook_and_run: &CookAndRunData, plan: &Plan) -> String {
+    let (start, end) = meeting_point_event_list(cook_and_run);
+    let mut event_list: Vec<Event> = start.into_iter().collect();
+    let mut hosting_list = plan.hosting_list.clone();
+    hosting_list.sort_by(|a, b| {
+        a.course
+            .time
+            .cmp(&b.course.time)
+            .then(a.host.team_name.cmp(&b.host.team_name))
+    });
+    for hosting in hosting_list.iter() {
+        event_list.push(hosting_event(
+            cook_and_run,
+            hosting,
+            format!("{}@tcc", hosting.id),
+        ));
+    }
+    event_list.extend(end);
+
+    to_ics(&cook_and_run.name, &event_list)
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::{NaiveDate, NaiveTime};
+    use uuid::Uuid;
+
+    use crate::{
+        calendar::{fold, project_calendar, team_calendar},
+        storage::{
+            mapper::Plan, AddressData, ContactData, CookAndRunData, CourseData, HostingData,
+            MeetingPointData, PlanData,
+        },
+    };
+
+    fn get_team(name: &str, address: &str) -> ContactData {
+        ContactData {
+            id: Uuid::new_v4(),
+            team_name: name.to_string(),
+            address: AddressData {
+                address: address.to_string(),
+                ..Default::default()
+            },
+            ..Default::default()
+        }
+    }
+
+    fn get_course(name: &str, hour: u32) -> CourseData {
+        CourseData {
+            id: Uuid::new_v4(),
+            name: name.to_string(),
+            time: NaiveTime::from_hms_opt(hour, 0, 0).unwrap(),
+        }
+    }
+
+    #[test]
+    fn test_team_calendar() {
+        let mut cook_and_run = CookAndRunData::new(Uuid::new_v4(), "Summer Run".to_string());
+        cook_and_run.occur = NaiveDate::from_ymd_opt(2025, 10, 18).unwrap();
+        cook_and_run.end_point = Some(MeetingPointData {
+            name: "Party".to_string(),
+            time: NaiveTime::from_hms_opt(23, 0, 0).unwrap(),
+            address: AddressData::default(),
+        });
+        let host = get_team("Chili Chasers", "Main Street 1, Town");
+        let guest = get_team("Pasta Pirates", "Side Road 2");
+        let starter = get_course("Starter", 18);
+        let dessert = get_course("Dessert", 21);
+        cook_and_run.contact_list = vec![host.clone(), guest.clone()];
+        cook_and_run.course_list = vec![dessert.clone(), starter.clone()];
+
+        let starter_hosting = HostingData {
+            id: Uuid::new_v4(),
+            name: starter.id,
+            host: host.id,
+            guest_list: vec![guest.id],
+        };
+        let dessert_hosting = HostingData {
+            id: Uuid::new_v4(),
+            name: dessert.id,
+            host: guest.id,
+            guest_list: vec![host.id],
+        };
+        let plan_data = PlanData {
+            id: Uuid::new_v4(),
+            hosting_list: vec![starter_hosting.clone(), dessert_hosting.clone()],
+            walking_path: [(guest.id, vec![dessert_hosting.id, starter_hosting.id])]
+                .into_iter()
+                .collect(),
+            greatest_distance: 0.0,
+        };
+        let plan = Plan::from_plan_data(
+            &plan_data,
+            &cook_and_run.course_list,
+            &cook_and_run.contact_list,
+        );
+
+        let ics = team_calendar(&cook_and_run, &plan, guest.id).unwrap();
+        assert!(ics.starts_with("BEGIN:VCALENDAR\r\n"));
+        assert_eq!(ics.matches("BEGIN:VEVENT").count(), 3);
+        // Courses are sorted and last until the next one starts
+        let starter_index = ics.find("SUMMARY:Starter at Chili Chasers").unwrap();
+        let dessert_index = ics.find("SUMMARY:Dessert at Pasta Pirates").unwrap();
+        assert!(starter_index < dessert_index);
+        assert!(ics.contains("DTSTART:20251018T180000\r\nDTEND:20251018T210000"));
+        assert!(ics.contains("DTSTART:20251018T210000\r\nDTEND:20251018T230000"));
+        assert!(ics.contains("LOCATION:Main Street 1\\, Town"));
+        assert!(ics.contains("Guests: Pasta Pirates"));
+
+        assert!(team_calendar(&cook_and_run, &plan, Uuid::new_v4()).is_err());
+
+        let ics = project_calendar(&cook_and_run, &plan);
+        assert_eq!(ics.matches("BEGIN:VEVENT").count(), 3);
+    }
+
+    #[test]
+    fn test_fold() {
+        let line = "DESCRIPTION:".to_string() + &"ä".repeat(60);
+        let folded = fold(&line);
+        assert!(folded.split("\r\n").all(|l| l.len() <= 75));
+        assert_eq!(folded.replace("\r\n ", ""), line);
+    }
+}
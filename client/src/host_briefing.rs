@@ -0,0 +1,240 @@
+use chrono::NaiveTime;
+
+use crate::{
+    pdf::{Font, PdfDocument},
+    storage::{mapper::Hosting, CookAndRunData, HostingData},
+};
+
+const TITLE_SIZE: f32 = 20.0;
+const HEADLINE_SIZE: f32 = 14.0;
+const TEXT_SIZE: f32 = 11.0;
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct BriefingGuest {
+    pub team_name: String,
+    pub members: u32,
+    pub phone_number: String,
+    pub diets: Vec<String>,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct HostBriefing {
+    pub course_name: String,
+    pub arrival_time: NaiveTime,
+    pub host_name: String,
+    pub host_members: u32,
+    pub guest_list: Vec<BriefingGuest>,
+    // Diets with the number of guest teams that named them, most frequent first
+    pub diet_list: Vec<(String, u32)>,
+}
+
+impl HostBriefing {
+    pub fn new(hosting: &Hosting) -> Self {
+        let guest_list: Vec<BriefingGuest> = hosting
+            .guest_list
+            .iter()
+            .map(|g| BriefingGuest {
+                team_name: g.team_name.clone(),
+                members: g.members,
+                phone_number: g.phone_number.clone(),
+                diets: g.diets.clone(),
+            })
+            .collect();
+
+        let mut diet_list: Vec<(String, u32)> = Vec::new();
+        for diet in guest_list.iter().flat_map(|g| {
+            let mut diets: Vec<String> = g
+                .diets
+                .iter()
+                .map(|d| d.trim().to_lowercase())
+                .filter(|d| !d.is_empty())
+                .collect();
+            diets.sort();
+            diets.dedup();
+            diets
+        }) {
+            match diet_list.iter_mut().find(|(d, _)| *d == diet) {
+                Some((_, count)) => *count += 1,
+                None => diet_list.push((diet, 1)),
+            }
+        }
+        diet_list.sort_by(|a, b| b.1.cmp(&a.1).then(a.0.cmp(&b.0)));
+
+        HostBriefing {
+            course_name: hosting.course.name.clone(),
+            arrival_time: hosting.course.time,
+            host_name: hosting.host.team_name.clone(),
+            host_members: hosting.host.members,
+            guest_list,
+            diet_list,
+        }
+    }
+
+    pub fn from_hosting_data(
+        hosting_data: &HostingData,
+        cook_and_run: &CookAndRunData,
+    ) -> Result<Self, String> {
+        let course = cook_and_run
+            .course_list
+            .iter()
+            .find(|c| c.id == hosting_data.name)
+            .ok_or("Course of hosting not found")?;
+        let host = cook_and_run
+            .contact_list
+            .iter()
+            .find(|c| c.id == hosting_data.host)
+            .ok_or("Host of hosting not found")?;
+        let guest_list = hosting_data
+            .guest_list
+            .iter()
+            .map(|id| {
+                cook_and_run
+                    .contact_list
+                    .iter()
+                    .find(|c| c.id == *id)
+                    .cloned()
+                    .ok_or("Guest of hosting not found".to_string())
+            })
+            .collect::<Result<Vec<_>, String>>()?;
+
+        Ok(HostBriefing::new(&Hosting {
+            id: hosting_data.id,
+            course: course.clone(),
+            host: host.clone(),
+            guest_list,
+        }))
+    }
+
+    pub fn guest_headcount(&self) -> u32 {
+        self.guest_list.iter().map(|g| g.members).sum()
+    }
+
+    // Guests and the host team together
+    pub fn headcount(&self) -> u32 {
+        self.guest_headcount() + self.host_members
+    }
+
+    pub fn to_pdf(&self) -> String {
+        let mut document = PdfDocument::new();
+        document.text(
+            &format!("{} - {}", self.course_name, self.host_name),
+            TITLE_SIZE,
+            Font::Bold,
+        );
+        document.text(
+            &format!("Guests arrive at {}", self.arrival_time.format("%H:%M")),
+            TEXT_SIZE,
+            Font::Regular,
+        );
+        document.text(
+            &format!(
+                "Headcount: {} ({} guests, {} of your team)",
+                self.headcount(),
+                self.guest_headcount(),
+                self.host_members
+            ),
+            TEXT_SIZE,
+            Font::Regular,
+        );
+        document.space(TEXT_SIZE);
+
+        document.text("Dietary requirements", HEADLINE_SIZE, Font::Bold);
+        if self.diet_list.is_empty() {
+            document.text("None", TEXT_SIZE, Font::Regular);
+        }
+        for (diet, count) in self.diet_list.iter() {
+            document.text(
+                &format!(
+                    "{} ({} team{})",
+                    diet,
+                    count,
+                    if *count == 1 { "" } else { "s" }
+                ),
+                TEXT_SIZE,
+                Font::Regular,
+            );
+        }
+        document.space(TEXT_SIZE);
+
+        document.text("Guests", HEADLINE_SIZE, Font::Bold);
+        for guest in self.guest_list.iter() {
+            document.text(
+                &format!("({}) {}", guest.members, guest.team_name),
+                TEXT_SIZE,
+                Font::Bold,
+            );
+            document.text(
+                &format!("Phone: {}", guest.phone_number),
+                TEXT_SIZE,
+                Font::Regular,
+            );
+            if !guest.diets.is_empty() {
+                document.text(
+                    &format!("Diets: {}", guest.diets.join(", ")),
+                    TEXT_SIZE,
+                    Font::Regular,
+                );
+            }
+        }
+        document.to_pdf()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::NaiveTime;
+    use uuid::Uuid;
+
+    use crate::{
+        host_briefing::HostBriefing,
+        storage::{ContactData, CookAndRunData, CourseData, HostingData},
+    };
+
+    fn get_team(name: &str, members: u32, diets: Vec<&str>) -> ContactData {
+        ContactData {
+            id: Uuid::new_v4(),
+            team_name: name.to_string(),
+            members,
+            phone_number: "+49 123".to_string(),
+            diets: diets.iter().map(|d| d.to_string()).collect(),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_briefing() {
+        let mut cook_and_run = CookAndRunData::new(Uuid::new_v4(), "Summer Run".to_string());
+        let host = get_team("Host", 2, vec!["vegan"]);
+        let guest_1 = get_team("Guest 1", 2, vec!["Vegan", "nut-free"]);
+        let guest_2 = get_team("Guest 2", 3, vec!["vegan "]);
+        let course = CourseData {
+            id: Uuid::new_v4(),
+            name: "Main".to_string(),
+            time: NaiveTime::from_hms_opt(20, 0, 0).unwrap(),
+        };
+        cook_and_run.contact_list = vec![host.clone(), guest_1.clone(), guest_2.clone()];
+        cook_and_run.course_list = vec![course.clone()];
+
+        let hosting_data = HostingData {
+            id: Uuid::new_v4(),
+            name: course.id,
+            host: host.id,
+            guest_list: vec![guest_1.id, guest_2.id],
+        };
+        let briefing = HostBriefing::from_hosting_data(&hosting_data, &cook_and_run).unwrap();
+
+        assert_eq!(briefing.arrival_time, course.time);
+        assert_eq!(briefing.guest_headcount(), 5);
+        assert_eq!(briefing.headcount(), 7);
+        // Diets of the host don't count, spelling differences are merged
+        assert_eq!(
+            briefing.diet_list,
+            vec![("vegan".to_string(), 2), ("nut-free".to_string(), 1)]
+        );
+        assert!(briefing.to_pdf().contains("/Count 1"));
+
+        let mut unknown_guest = hosting_data.clone();
+        unknown_guest.guest_list.push(Uuid::new_v4());
+        assert!(HostBriefing::from_hosting_data(&unknown_guest, &cook_and_run).is_err());
+    }
+}
@@ -1,2 +1,11 @@
 pub mod calculator;
+pub mod calendar;
+pub mod host_briefing;
+pub mod map_projection;
+pub mod pairing;
+pub mod pdf;
+pub mod plan_analytics;
+pub mod plan_comparison;
+pub mod progress_sheet;
 pub mod storage;
+pub mod validation;
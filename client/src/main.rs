@@ -1,8 +1,17 @@
 mod address_connector;
 mod auth0;
 mod calculator;
+mod calendar;
+mod host_briefing;
+mod map_projection;
+mod pdf;
+mod plan_analytics;
+mod plan_comparison;
+mod progress_sheet;
+mod server_connector;
 mod side;
 mod storage;
+mod validation;
 
 use std::sync::Arc;
 use std::sync::Mutex;
@@ -14,10 +23,12 @@ use side::Dashboard;
 use side::ProjectCalculationPage;
 use side::ProjectCoursesPage;
 use side::ProjectOverviewPage;
+use side::ProjectReviewPage;
 use side::ProjectStartEndPage;
 use side::ProjectTeamsPage;
 use side::RunSchedule;
 use side::ShareTeam;
+use side::SharedRunSchedule;
 use storage::LocalStorage;
 use uuid::Uuid;
 use web_sys::console;
@@ -38,6 +49,8 @@ fn main() {
 enum Route {
     #[route("/")]
     Home {},
+    #[route("/schedule/:token")]
+    SharedRunSchedule { token: String },
     #[nest("/cook-and-run")]
         #[route("/")]
         Dashboard {},
@@ -48,6 +61,8 @@ enum Route {
         ProjectOverviewPage { cook_and_run_id: Uuid },
         #[route("/:cook_and_run_id/teams")]
         ProjectTeamsPage { cook_and_run_id: Uuid },
+        #[route("/:cook_and_run_id/review")]
+        ProjectReviewPage { cook_and_run_id: Uuid },
         #[route("/:cook_and_run_id/team-share/:share_id")]
         ShareTeam { cook_and_run_id: Uuid ,share_id: Uuid},
         #[route("/:cook_and_run_id/start-end")]
@@ -0,0 +1,253 @@
+use crate::storage::{AddressData, ContactData, CookAndRunData, PlanData};
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct TeamRoute {
+    pub contact: ContactData,
+    pub color: String,
+    // Start point, the hosts in course order and the end point
+    pub stop_list: Vec<(String, AddressData)>,
+}
+
+// Projects coordinates onto a plane of the given size, good enough for the area of a city
+#[derive(Debug, Clone, PartialEq)]
+pub struct Projection {
+    min_x: f64,
+    max_y: f64,
+    scale: f64,
+    offset_x: f64,
+    offset_y: f64,
+    lat_factor: f64,
+}
+
+impl Projection {
+    pub fn new(
+        address_list: &[&AddressData],
+        width: f64,
+        height: f64,
+        padding: f64,
+    ) -> Option<Self> {
+        if address_list.is_empty() {
+            return None;
+        }
+        let mean_lat =
+            address_list.iter().map(|a| a.latitude).sum::<f64>() / address_list.len() as f64;
+        // Longitude degrees get shorter towards the poles
+        let lat_factor = mean_lat.to_radians().cos();
+
+        let x_list: Vec<f64> = address_list
+            .iter()
+            .map(|a| a.longitude * lat_factor)
+            .collect();
+        let y_list: Vec<f64> = address_list.iter().map(|a| a.latitude).collect();
+        let min_x = x_list.iter().cloned().fold(f64::INFINITY, f64::min);
+        let max_x = x_list.iter().cloned().fold(f64::NEG_INFINITY, f64::max);
+        let min_y = y_list.iter().cloned().fold(f64::INFINITY, f64::min);
+        let max_y = y_list.iter().cloned().fold(f64::NEG_INFINITY, f64::max);
+
+        let inner_width = width - 2.0 * padding;
+        let inner_height = height - 2.0 * padding;
+        let span_x = max_x - min_x;
+        let span_y = max_y - min_y;
+        let scale = if span_x <= f64::EPSILON && span_y <= f64::EPSILON {
+            1.0
+        } else {
+            (inner_width / span_x).min(inner_height / span_y)
+        };
+
+        // Centers the points on the plane
+        let offset_x = padding + (inner_width - span_x * scale) / 2.0;
+        let offset_y = padding + (inner_height - span_y * scale) / 2.0;
+
+        Some(Projection {
+            min_x,
+            max_y,
+            scale,
+            offset_x,
+            offset_y,
+            lat_factor,
+        })
+    }
+
+    pub fn project(&self, address: &AddressData) -> (f64, f64) {
+        let x = (address.longitude * self.lat_factor - self.min_x) * self.scale + self.offset_x;
+        let y = (self.max_y - address.latitude) * self.scale + self.offset_y;
+        (x, y)
+    }
+}
+
+// Evenly spread colors so neighbouring teams can be told apart
+pub fn team_color(index: usize, count: usize) -> String {
+    let hue = (index as f64 * 360.0 / count.max(1) as f64).round();
+    format!("hsl({}, 70%, 45%)", hue)
+}
+
+pub fn team_route_list(cook_and_run: &CookAndRunData, plan: &PlanData) -> Vec<TeamRoute> {
+    let contact_list: Vec<&ContactData> = cook_and_run
+        .contact_list
+        .iter()
+        .filter(|c| plan.walking_path.contains_key(&c.id))
+        .collect();
+
+    contact_list
+        .iter()
+        .enumerate()
+        .map(|(index, contact)| {
+            let mut stop_list = Vec::new();
+            if let Some(start_point) = &cook_and_run.start_point {
+                stop_list.push((start_point.name.clone(), start_point.address.clone()));
+            }
+
+            let mut hosting_list: Vec<(chrono::NaiveTime, String, AddressData)> = plan
+                .walking_path
+                .get(&contact.id)
+                .into_iter()
+                .flatten()
+                .filter_map(|hosting_id| plan.hosting_list.iter().find(|h| h.id == *hosting_id))
+                .filter_map(|hosting| {
+                    let course = cook_and_run
+                        .course_list
+                        .iter()
+                        .find(|c| c.id == hosting.name)?;
+                    let host = cook_and_run
+                        .contact_list
+                        .iter()
+                        .find(|c| c.id == hosting.host)?;
+                    Some((
+                        course.time,
+                        format!("{}: {}", course.name, host.team_name),
+                        host.address.clone(),
+                    ))
+                })
+                .collect();
+            hosting_list.sort_by_key(|(time, _, _)| *time);
+            stop_list.extend(hosting_list.into_iter().map(|(_, name, a)| (name, a)));
+
+            if let Some(end_point) = &cook_and_run.end_point {
+                stop_list.push((end_point.name.clone(), end_point.address.clone()));
+            }
+
+            TeamRoute {
+                contact: (*contact).clone(),
+                color: team_color(index, contact_list.len()),
+                stop_list,
+            }
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::NaiveTime;
+    use uuid::Uuid;
+
+    use crate::{
+        map_projection::{team_route_list, Projection},
+        storage::{
+            AddressData, ContactData, CookAndRunData, CourseData, HostingData, MeetingPointData,
+            PlanData,
+        },
+    };
+
+    fn get_address(latitude: f64, longitude: f64) -> AddressData {
+        AddressData {
+            address: format!("{} {}", latitude, longitude),
+            latitude,
+            longitude,
+        }
+    }
+
+    #[test]
+    fn test_projection_fits_plane() {
+        let address_list = [
+            get_address(48.10, 11.50),
+            get_address(48.20, 11.70),
+            get_address(48.15, 11.60),
+        ];
+        let reference_list: Vec<&AddressData> = address_list.iter().collect();
+        let projection = Projection::new(&reference_list, 400.0, 300.0, 20.0).unwrap();
+
+        for address in address_list.iter() {
+            let (x, y) = projection.project(address);
+            assert!((20.0..=380.0).contains(&x), "x {} out of plane", x);
+            assert!((20.0..=280.0).contains(&y), "y {} out of plane", y);
+        }
+        // North is up
+        let (_, south_y) = projection.project(&address_list[0]);
+        let (_, north_y) = projection.project(&address_list[1]);
+        assert!(north_y < south_y);
+
+        // A single point ends up in the middle
+        let single = [&address_list[0]];
+        let projection = Projection::new(&single, 400.0, 300.0, 20.0).unwrap();
+        assert_eq!(projection.project(&address_list[0]), (200.0, 150.0));
+
+        assert!(Projection::new(&[], 400.0, 300.0, 20.0).is_none());
+    }
+
+    #[test]
+    fn test_team_route_list() {
+        let mut cook_and_run = CookAndRunData::new(Uuid::new_v4(), "Summer Run".to_string());
+        let host = ContactData {
+            id: Uuid::new_v4(),
+            team_name: "Host".to_string(),
+            address: get_address(48.1, 11.5),
+            ..Default::default()
+        };
+        let guest = ContactData {
+            id: Uuid::new_v4(),
+            team_name: "Guest".to_string(),
+            address: get_address(48.2, 11.6),
+            ..Default::default()
+        };
+        let starter = CourseData {
+            id: Uuid::new_v4(),
+            name: "Starter".to_string(),
+            time: NaiveTime::from_hms_opt(18, 0, 0).unwrap(),
+        };
+        let main = CourseData {
+            id: Uuid::new_v4(),
+            name: "Main".to_string(),
+            time: NaiveTime::from_hms_opt(20, 0, 0).unwrap(),
+        };
+        cook_and_run.contact_list = vec![host.clone(), guest.clone()];
+        cook_and_run.course_list = vec![starter.clone(), main.clone()];
+        cook_and_run.end_point = Some(MeetingPointData {
+            name: "Party".to_string(),
+            time: NaiveTime::from_hms_opt(23, 0, 0).unwrap(),
+            address: get_address(48.15, 11.55),
+        });
+
+        let starter_hosting = HostingData {
+            id: Uuid::new_v4(),
+            name: starter.id,
+            host: host.id,
+            guest_list: vec![guest.id],
+        };
+        let main_hosting = HostingData {
+            id: Uuid::new_v4(),
+            name: main.id,
+            host: guest.id,
+            guest_list: vec![host.id],
+        };
+        let plan = PlanData {
+            id: Uuid::new_v4(),
+            hosting_list: vec![starter_hosting.clone(), main_hosting.clone()],
+            walking_path: [(guest.id, vec![main_hosting.id, starter_hosting.id])]
+                .into_iter()
+                .collect(),
+            greatest_distance: 0.0,
+        };
+
+        let route_list = team_route_list(&cook_and_run, &plan);
+        assert_eq!(route_list.len(), 1);
+        let stop_name_list: Vec<&str> = route_list[0]
+            .stop_list
+            .iter()
+            .map(|(name, _)| name.as_str())
+            .collect();
+        assert_eq!(
+            stop_name_list,
+            vec!["Starter: Host", "Main: Guest", "Party"]
+        );
+    }
+}
@@ -0,0 +1,226 @@
+use std::collections::HashSet;
+
+use uuid::Uuid;
+
+use crate::storage::{ContactData, ParticipantData};
+
+// Pairs single participants into teams. Preferred partners are paired first, afterwards
+// every participant without a kitchen gets the closest free participant with a kitchen
+// and the remaining participants with a kitchen are paired by distance.
+pub fn pair_participants(participant_list: &[ParticipantData]) -> Result<Vec<ContactData>, String> {
+    if !participant_list.len().is_multiple_of(2) {
+        return Err(format!(
+            "{} participants can't be paired, one participant is left over!",
+            participant_list.len()
+        ));
+    }
+
+    let number_of_kitchens = participant_list.iter().filter(|p| p.has_kitchen).count();
+    if number_of_kitchens < participant_list.len() / 2 {
+        return Err(format!(
+            "{} teams need a kitchen, but only {} participants have one!",
+            participant_list.len() / 2,
+            number_of_kitchens
+        ));
+    }
+
+    let mut paired_set: HashSet<Uuid> = HashSet::new();
+    let mut pair_list: Vec<(&ParticipantData, &ParticipantData)> = Vec::new();
+
+    // Phase 1: Preferred partners
+    for participant in participant_list {
+        if paired_set.contains(&participant.id) {
+            continue;
+        }
+        let partner = participant.preferred_partner.and_then(|partner_id| {
+            participant_list
+                .iter()
+                .find(|p| p.id == partner_id && p.id != participant.id)
+        });
+        if let Some(partner) = partner {
+            if !paired_set.contains(&partner.id) && (participant.has_kitchen || partner.has_kitchen)
+            {
+                paired_set.insert(participant.id);
+                paired_set.insert(partner.id);
+                pair_list.push((participant, partner));
+            }
+        }
+    }
+
+    let free_list: Vec<&ParticipantData> = participant_list
+        .iter()
+        .filter(|p| !paired_set.contains(&p.id))
+        .collect();
+    let kitchen_list: Vec<&ParticipantData> = free_list
+        .iter()
+        .copied()
+        .filter(|p| p.has_kitchen)
+        .collect();
+    let no_kitchen_list: Vec<&ParticipantData> = free_list
+        .iter()
+        .copied()
+        .filter(|p| !p.has_kitchen)
+        .collect();
+    if kitchen_list.len() < no_kitchen_list.len() {
+        return Err(
+            "Preferred partners leave too few participants with a kitchen for the others!"
+                .to_string(),
+        );
+    }
+
+    // Phase 2: Participants without kitchen join the closest participant with kitchen
+    pair_list.extend(pair_closest(
+        &no_kitchen_list,
+        &kitchen_list,
+        &mut paired_set,
+    ));
+
+    // Phase 3: Remaining participants with kitchen
+    let kitchen_list: Vec<&ParticipantData> = kitchen_list
+        .into_iter()
+        .filter(|p| !paired_set.contains(&p.id))
+        .collect();
+    pair_list.extend(pair_closest(&kitchen_list, &kitchen_list, &mut paired_set));
+
+    Ok(pair_list
+        .into_iter()
+        .map(|(first, second)| to_contact_data(first, second))
+        .collect())
+}
+
+fn pair_closest<'a>(
+    first_list: &[&'a ParticipantData],
+    second_list: &[&'a ParticipantData],
+    paired_set: &mut HashSet<Uuid>,
+) -> Vec<(&'a ParticipantData, &'a ParticipantData)> {
+    let mut candidate_list = Vec::new();
+    for &first in first_list {
+        for &second in second_list {
+            if first.id != second.id {
+                candidate_list.push((first.address.distance(&second.address), first, second));
+            }
+        }
+    }
+    candidate_list.sort_by(|a, b| a.0.partial_cmp(&b.0).unwrap_or(std::cmp::Ordering::Equal));
+
+    let mut pair_list = Vec::new();
+    for (_, first, second) in candidate_list {
+        if paired_set.contains(&first.id) || paired_set.contains(&second.id) {
+            continue;
+        }
+        paired_set.insert(first.id);
+        paired_set.insert(second.id);
+        pair_list.push((first, second));
+    }
+    pair_list
+}
+
+fn to_contact_data(first: &ParticipantData, second: &ParticipantData) -> ContactData {
+    // The team cooks in the kitchen of the first participant who has one
+    let (host, partner) = if first.has_kitchen {
+        (first, second)
+    } else {
+        (second, first)
+    };
+
+    let mut diets = host.diets.clone();
+    for diet in partner.diets.iter() {
+        if !diets.contains(diet) {
+            diets.push(diet.clone());
+        }
+    }
+
+    ContactData {
+        id: Uuid::new_v4(),
+        team_name: format!("{} & {}", host.name, partner.name),
+        address: host.address.clone(),
+        mail: host.mail.clone(),
+        phone_number: host.phone_number.clone(),
+        members: 2,
+        diets,
+        needs_check: false,
+        rejected: false,
+        notes: vec![],
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use uuid::Uuid;
+
+    use crate::{
+        pairing::pair_participants,
+        storage::{AddressData, ParticipantData},
+    };
+
+    fn get_participant(name: &str, latitude: f64, has_kitchen: bool) -> ParticipantData {
+        ParticipantData {
+            id: Uuid::new_v4(),
+            name: name.to_string(),
+            address: AddressData {
+                address: format!("{} street", name),
+                latitude,
+                longitude: 9.67,
+            },
+            has_kitchen,
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_pair_participants() {
+        let participant_list = vec![
+            get_participant("Anna", 50.50, true),
+            get_participant("Ben", 50.60, true),
+            get_participant("Clara", 50.61, false),
+            get_participant("David", 50.51, false),
+        ];
+
+        let team_list = pair_participants(&participant_list).unwrap();
+        let mut team_name_list: Vec<&str> =
+            team_list.iter().map(|t| t.team_name.as_str()).collect();
+        team_name_list.sort();
+        assert_eq!(team_name_list, vec!["Anna & David", "Ben & Clara"]);
+
+        for team in team_list.iter() {
+            assert_eq!(team.members, 2);
+            assert!(
+                team.address.address == "Anna street" || team.address.address == "Ben street",
+                "Team should cook in a kitchen"
+            );
+        }
+    }
+
+    #[test]
+    fn test_pair_preferred_partner() {
+        let mut participant_list = vec![
+            get_participant("Anna", 50.50, true),
+            get_participant("Ben", 50.60, true),
+            get_participant("Clara", 50.61, false),
+            get_participant("David", 50.51, false),
+        ];
+        participant_list[0].preferred_partner = Some(participant_list[2].id);
+
+        let team_list = pair_participants(&participant_list).unwrap();
+        assert!(team_list.iter().any(|t| t.team_name == "Anna & Clara"));
+        assert!(team_list.iter().any(|t| t.team_name == "Ben & David"));
+    }
+
+    #[test]
+    fn test_pair_infeasible() {
+        let participant_list = vec![
+            get_participant("Anna", 50.50, true),
+            get_participant("Ben", 50.60, false),
+            get_participant("Clara", 50.61, false),
+        ];
+        assert!(pair_participants(&participant_list).is_err());
+
+        let participant_list = vec![
+            get_participant("Anna", 50.50, true),
+            get_participant("Ben", 50.60, false),
+            get_participant("Clara", 50.61, false),
+            get_participant("David", 50.51, false),
+        ];
+        assert!(pair_participants(&participant_list).is_err());
+    }
+}
@@ -0,0 +1,236 @@
+// Minimal PDF writer for text documents using the standard Helvetica fonts. Text is written as
+// hex strings so the whole document stays ASCII.
+
+const PAGE_WIDTH: f32 = 595.0;
+const PAGE_HEIGHT: f32 = 842.0;
+const MARGIN: f32 = 56.0;
+// Average width of a Helvetica character relative to the font size
+const CHAR_WIDTH: f32 = 0.5;
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Font {
+    Regular,
+    Bold,
+}
+
+impl Font {
+    fn name(&self) -> &'static str {
+        match self {
+            Font::Regular => "F1",
+            Font::Bold => "F2",
+        }
+    }
+}
+
+pub struct PdfDocument {
+    page_list: Vec<String>,
+    content: String,
+    y: f32,
+}
+
+impl PdfDocument {
+    pub fn new() -> Self {
+        PdfDocument {
+            page_list: Vec::new(),
+            content: String::new(),
+            y: PAGE_HEIGHT - MARGIN,
+        }
+    }
+
+    pub fn page_count(&self) -> usize {
+        self.page_list.len() + if self.content.is_empty() { 0 } else { 1 }
+    }
+
+    pub fn new_page(&mut self) {
+        if !self.content.is_empty() {
+            self.page_list.push(std::mem::take(&mut self.content));
+        }
+        self.y = PAGE_HEIGHT - MARGIN;
+    }
+
+    // Writes the text wrapped to the page width, long texts continue on a new page
+    pub fn text(&mut self, text: &str, size: f32, font: Font) {
+        let max_chars = ((PAGE_WIDTH - 2.0 * MARGIN) / (size * CHAR_WIDTH)) as usize;
+        for paragraph in text.lines() {
+            for line in wrap(paragraph, max_chars) {
+                if self.y - size < MARGIN {
+                    self.new_page();
+                }
+                self.y -= size * 1.2;
+                self.content.push_str(&format!(
+                    "BT /{} {} Tf {} {} Td <{}> Tj ET\n",
+                    font.name(),
+                    size,
+                    MARGIN,
+                    self.y,
+                    encode(&line)
+                ));
+            }
+        }
+    }
+
+    pub fn space(&mut self, height: f32) {
+        self.y -= height;
+    }
+
+    pub fn to_pdf(mut self) -> String {
+        self.new_page();
+        if self.page_list.is_empty() {
+            self.page_list.push(String::new());
+        }
+
+        let mut object_list = vec![
+            "<< /Type /Catalog /Pages 2 0 R >>".to_string(),
+            String::new(), // Page tree, written once the page ids are known
+            "<< /Type /Font /Subtype /Type1 /BaseFont /Helvetica /Encoding /WinAnsiEncoding >>"
+                .to_string(),
+            "<< /Type /Font /Subtype /Type1 /BaseFont /Helvetica-Bold /Encoding /WinAnsiEncoding >>"
+                .to_string(),
+        ];
+        let mut kid_list = Vec::new();
+        for content in self.page_list.iter() {
+            let page_id = object_list.len() + 1;
+            kid_list.push(format!("{} 0 R", page_id));
+            object_list.push(format!(
+                "<< /Type /Page /Parent 2 0 R /MediaBox [0 0 {} {}] \
+                 /Resources << /Font << /F1 3 0 R /F2 4 0 R >> >> /Contents {} 0 R >>",
+                PAGE_WIDTH,
+                PAGE_HEIGHT,
+                page_id + 1
+            ));
+            object_list.push(format!(
+                "<< /Length {} >>\nstream\n{}endstream",
+                content.len(),
+                content
+            ));
+        }
+        object_list[1] = format!(
+            "<< /Type /Pages /Kids [{}] /Count {} >>",
+            kid_list.join(" "),
+            kid_list.len()
+        );
+
+        let mut pdf = "%PDF-1.4\n".to_string();
+        let mut offset_list = Vec::new();
+        for (index, object) in object_list.iter().enumerate() {
+            offset_list.push(pdf.len());
+            pdf.push_str(&format!("{} 0 obj\n{}\nendobj\n", index + 1, object));
+        }
+        let xref_offset = pdf.len();
+        pdf.push_str(&format!("xref\n0 {}\n", object_list.len() + 1));
+        pdf.push_str("0000000000 65535 f \n");
+        for offset in offset_list {
+            pdf.push_str(&format!("{:010} 00000 n \n", offset));
+        }
+        pdf.push_str(&format!(
+            "trailer\n<< /Size {} /Root 1 0 R >>\nstartxref\n{}\n%%EOF\n",
+            object_list.len() + 1,
+            xref_offset
+        ));
+        pdf
+    }
+}
+
+impl Default for PdfDocument {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+fn wrap(text: &str, max_chars: usize) -> Vec<String> {
+    let mut line_list = Vec::new();
+    let mut line = String::new();
+    for word in text.split_whitespace() {
+        if !line.is_empty() && line.chars().count() + 1 + word.chars().count() > max_chars {
+            line_list.push(std::mem::take(&mut line));
+        }
+        if !line.is_empty() {
+            line.push(' ');
+        }
+        line.push_str(word);
+    }
+    if !line.is_empty() || line_list.is_empty() {
+        line_list.push(line);
+    }
+    line_list
+}
+
+// Hex encoded WinAnsi text, characters the standard fonts can't show become '?'
+fn encode(text: &str) -> String {
+    text.chars()
+        .map(|c| {
+            let code = match c {
+                '€' => 0x80,
+                '–' => 0x96,
+                '—' => 0x97,
+                '„' => 0x84,
+                '“' => 0x93,
+                '”' => 0x94,
+                '‘' => 0x91,
+                '’' => 0x92,
+                c if (c as u32) >= 0x20 && (c as u32) < 0x7F => c as u32,
+                c if (c as u32) >= 0xA0 && (c as u32) <= 0xFF => c as u32,
+                _ => '?' as u32,
+            };
+            format!("{:02X}", code)
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::pdf::{encode, wrap, Font, PdfDocument};
+
+    #[test]
+    fn test_pdf_structure() {
+        let mut document = PdfDocument::new();
+        document.text("Team Ä", 18.0, Font::Bold);
+        document.new_page();
+        document.text("Second page", 11.0, Font::Regular);
+        assert_eq!(document.page_count(), 2);
+
+        let pdf = document.to_pdf();
+        assert!(pdf.is_ascii());
+        assert!(pdf.starts_with("%PDF-1.4\n"));
+        assert!(pdf.ends_with("%%EOF\n"));
+        assert!(pdf.contains("/Count 2"));
+
+        // Every object is found at the offset named in the cross reference table
+        let xref_offset: usize = pdf
+            .lines()
+            .skip_while(|l| *l != "startxref")
+            .nth(1)
+            .unwrap()
+            .parse()
+            .unwrap();
+        assert!(pdf[xref_offset..].starts_with("xref"));
+        for (index, line) in pdf[xref_offset..]
+            .lines()
+            .skip(3)
+            .take_while(|l| l.ends_with(" n "))
+            .enumerate()
+        {
+            let offset: usize = line[..10].parse().unwrap();
+            assert!(pdf[offset..].starts_with(&format!("{} 0 obj", index + 1)));
+        }
+    }
+
+    #[test]
+    fn test_long_text_continues_on_new_page() {
+        let mut document = PdfDocument::new();
+        for _ in 0..100 {
+            document.text("Line", 11.0, Font::Regular);
+        }
+        assert_eq!(document.page_count(), 2);
+    }
+
+    #[test]
+    fn test_wrap_and_encode() {
+        assert_eq!(
+            wrap("one two three four", 9),
+            vec!["one two", "three", "four"]
+        );
+        assert_eq!(wrap("", 9), vec![""]);
+        assert_eq!(encode("Aü€✓"), "41FC803F");
+    }
+}
@@ -0,0 +1,277 @@
+use std::fmt;
+
+use uuid::Uuid;
+
+use crate::{
+    map_projection::team_route_list,
+    storage::{CookAndRunData, PlanData},
+};
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Leg {
+    pub from: String,
+    pub to: String,
+    pub distance: f64,
+}
+
+// Settings of the project which fix parts of the route of a team
+#[derive(Debug, Clone, PartialEq)]
+pub enum Constraint {
+    // The team has to be at home for the course it hosts
+    Hosting(String),
+    // Teams which don't fill a whole hosting are spread as extra hostings over this course
+    MoreHosts(String),
+    StartPoint(String),
+    EndPoint(String),
+}
+
+impl fmt::Display for Constraint {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Constraint::Hosting(course) => write!(f, "Hosts {} at home", course),
+            Constraint::MoreHosts(course) => {
+                write!(f, "{} is the course with more hosts", course)
+            }
+            Constraint::StartPoint(name) => write!(f, "Starts at {}", name),
+            Constraint::EndPoint(name) => write!(f, "Ends at {}", name),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct TeamAnalytics {
+    pub contact_id: Uuid,
+    pub team_name: String,
+    pub total_distance: f64,
+    pub leg_list: Vec<Leg>,
+    // Teams met at the tables, in course order
+    pub met_team_list: Vec<String>,
+    // Positive if the team walks further than the average team
+    pub difference_to_average: f64,
+    pub constraint_list: Vec<Constraint>,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct PlanAnalytics {
+    pub average_distance: f64,
+    pub team_list: Vec<TeamAnalytics>,
+}
+
+pub fn analyse_plan(cook_and_run: &CookAndRunData, plan: &PlanData) -> PlanAnalytics {
+    let course_name = |id: &Uuid| {
+        cook_and_run
+            .course_list
+            .iter()
+            .find(|c| c.id == *id)
+            .map(|c| c.name.clone())
+            .unwrap_or_default()
+    };
+    let team_name = |id: &Uuid| {
+        cook_and_run
+            .contact_list
+            .iter()
+            .find(|c| c.id == *id)
+            .map(|c| c.team_name.clone())
+            .unwrap_or_default()
+    };
+
+    let mut team_list: Vec<TeamAnalytics> = team_route_list(cook_and_run, plan)
+        .into_iter()
+        .map(|route| {
+            let leg_list: Vec<Leg> = route
+                .stop_list
+                .windows(2)
+                .map(|w| Leg {
+                    from: w[0].0.clone(),
+                    to: w[1].0.clone(),
+                    distance: w[0].1.distance(&w[1].1),
+                })
+                .collect();
+
+            let mut hosting_list: Vec<_> = plan
+                .walking_path
+                .get(&route.contact.id)
+                .into_iter()
+                .flatten()
+                .filter_map(|hosting_id| plan.hosting_list.iter().find(|h| h.id == *hosting_id))
+                .collect();
+            hosting_list.sort_by_key(|h| {
+                cook_and_run
+                    .course_list
+                    .iter()
+                    .find(|c| c.id == h.name)
+                    .map(|c| c.time)
+            });
+
+            let met_team_list = hosting_list
+                .iter()
+                .flat_map(|h| std::iter::once(&h.host).chain(h.guest_list.iter()))
+                .filter(|id| **id != route.contact.id)
+                .map(team_name)
+                .collect();
+
+            let mut constraint_list = vec![];
+            if let Some(start_point) = &cook_and_run.start_point {
+                constraint_list.push(Constraint::StartPoint(start_point.name.clone()));
+            }
+            for hosting in hosting_list.iter().filter(|h| h.host == route.contact.id) {
+                constraint_list.push(Constraint::Hosting(course_name(&hosting.name)));
+                if cook_and_run
+                    .course_with_more_hosts_list
+                    .contains(&hosting.name)
+                {
+                    constraint_list.push(Constraint::MoreHosts(course_name(&hosting.name)));
+                }
+            }
+            if let Some(end_point) = &cook_and_run.end_point {
+                constraint_list.push(Constraint::EndPoint(end_point.name.clone()));
+            }
+
+            TeamAnalytics {
+                contact_id: route.contact.id,
+                team_name: route.contact.team_name.clone(),
+                total_distance: leg_list.iter().map(|l| l.distance).sum(),
+                leg_list,
+                met_team_list,
+                difference_to_average: 0.0,
+                constraint_list,
+            }
+        })
+        .collect();
+
+    let average_distance =
+        team_list.iter().map(|t| t.total_distance).sum::<f64>() / team_list.len().max(1) as f64;
+    for team in team_list.iter_mut() {
+        team.difference_to_average = team.total_distance - average_distance;
+    }
+    // Longest routes first, those are the ones people ask about
+    team_list.sort_by(|a, b| b.total_distance.total_cmp(&a.total_distance));
+
+    PlanAnalytics {
+        average_distance,
+        team_list,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashMap;
+
+    use chrono::NaiveTime;
+    use uuid::Uuid;
+
+    use crate::{
+        plan_analytics::{analyse_plan, Constraint},
+        storage::{
+            AddressData, ContactData, CookAndRunData, CourseData, HostingData, MeetingPointData,
+            PlanData,
+        },
+    };
+
+    fn get_team(name: &str, latitude: f64) -> ContactData {
+        ContactData {
+            id: Uuid::new_v4(),
+            team_name: name.to_string(),
+            address: AddressData {
+                address: name.to_string(),
+                latitude,
+                longitude: 11.5,
+            },
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_analyse_plan() {
+        let mut cook_and_run = CookAndRunData::new(Uuid::new_v4(), "Summer Run".to_string());
+        // Team 3 lives far away from the others
+        let team_list = vec![
+            get_team("Team 0", 48.00),
+            get_team("Team 1", 48.01),
+            get_team("Team 2", 48.02),
+            get_team("Team 3", 48.20),
+        ];
+        let starter = CourseData {
+            id: Uuid::new_v4(),
+            name: "Starter".to_string(),
+            time: NaiveTime::from_hms_opt(18, 0, 0).unwrap(),
+        };
+        let main = CourseData {
+            id: Uuid::new_v4(),
+            name: "Main".to_string(),
+            time: NaiveTime::from_hms_opt(20, 0, 0).unwrap(),
+        };
+        cook_and_run.contact_list = team_list.clone();
+        cook_and_run.course_list = vec![main.clone(), starter.clone()];
+        cook_and_run.course_with_more_hosts_list = vec![main.id];
+        cook_and_run.end_point = Some(MeetingPointData {
+            name: "Party".to_string(),
+            time: NaiveTime::from_hms_opt(23, 0, 0).unwrap(),
+            address: team_list[0].address.clone(),
+        });
+
+        let hosting = |course: &CourseData, host: usize, guest: usize| HostingData {
+            id: Uuid::new_v4(),
+            name: course.id,
+            host: team_list[host].id,
+            guest_list: vec![team_list[guest].id],
+        };
+        let hosting_list = vec![
+            hosting(&main, 1, 0),
+            hosting(&main, 3, 2),
+            hosting(&starter, 0, 1),
+            hosting(&starter, 2, 3),
+        ];
+        let mut walking_path: HashMap<Uuid, Vec<Uuid>> = HashMap::new();
+        for hosting in hosting_list.iter() {
+            for team in std::iter::once(&hosting.host).chain(hosting.guest_list.iter()) {
+                walking_path.entry(*team).or_default().push(hosting.id);
+            }
+        }
+        let plan = PlanData {
+            id: Uuid::new_v4(),
+            hosting_list,
+            walking_path,
+            greatest_distance: 0.0,
+        };
+
+        let analytics = analyse_plan(&cook_and_run, &plan);
+        assert_eq!(analytics.team_list.len(), 4);
+
+        let farthest = &analytics.team_list[0];
+        assert!(["Team 2", "Team 3"].contains(&farthest.team_name.as_str()));
+        assert!(farthest.difference_to_average > 0.0);
+        assert!(analytics.team_list[3].difference_to_average < 0.0);
+
+        let team_0 = analytics
+            .team_list
+            .iter()
+            .find(|t| t.team_name == "Team 0")
+            .unwrap();
+        // Starter at home, main at team 1 and back home to the party
+        assert_eq!(team_0.leg_list.len(), 2);
+        assert_eq!(team_0.leg_list[0].from, "Starter: Team 0");
+        assert_eq!(team_0.leg_list[0].to, "Main: Team 1");
+        assert_eq!(team_0.leg_list[1].to, "Party");
+        let leg_sum: f64 = team_0.leg_list.iter().map(|l| l.distance).sum();
+        assert_eq!(team_0.total_distance, leg_sum);
+        assert_eq!(team_0.met_team_list, vec!["Team 1", "Team 1"]);
+        assert_eq!(
+            team_0.constraint_list,
+            vec![
+                Constraint::Hosting("Starter".to_string()),
+                Constraint::EndPoint("Party".to_string())
+            ]
+        );
+
+        let team_3 = analytics
+            .team_list
+            .iter()
+            .find(|t| t.team_name == "Team 3")
+            .unwrap();
+        assert_eq!(team_3.met_team_list, vec!["Team 2", "Team 2"]);
+        assert!(team_3
+            .constraint_list
+            .contains(&Constraint::MoreHosts("Main".to_string())));
+    }
+}
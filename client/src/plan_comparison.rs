@@ -0,0 +1,226 @@
+use std::collections::HashMap;
+
+use uuid::Uuid;
+
+use crate::{
+    map_projection::{team_route_list, TeamRoute},
+    storage::{CookAndRunData, PlanData},
+};
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct PlanScore {
+    // Longest way of a single team, the calculator minimizes this one
+    pub greatest_distance: f64,
+    pub total_distance: f64,
+    pub average_distance: f64,
+    // Every meeting of two teams beyond their first one
+    pub repeated_meeting_count: u32,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct TeamDifference {
+    pub contact_id: Uuid,
+    pub team_name: String,
+    pub stop_list_a: Vec<String>,
+    pub stop_list_b: Vec<String>,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct PlanComparison {
+    pub score_a: PlanScore,
+    pub score_b: PlanScore,
+    // Only teams whose evening differs between both plans
+    pub team_difference_list: Vec<TeamDifference>,
+}
+
+fn route_distance(route: &TeamRoute) -> f64 {
+    route
+        .stop_list
+        .windows(2)
+        .map(|w| w[0].1.distance(&w[1].1))
+        .sum()
+}
+
+fn repeated_meeting_count(plan: &PlanData) -> u32 {
+    let mut meeting_map: HashMap<(Uuid, Uuid), u32> = HashMap::new();
+    for hosting in plan.hosting_list.iter() {
+        let mut team_list = vec![hosting.host];
+        team_list.extend(hosting.guest_list.iter().copied());
+        for (index, team) in team_list.iter().enumerate() {
+            for other_team in team_list[index + 1..].iter() {
+                let pair = if team < other_team {
+                    (*team, *other_team)
+                } else {
+                    (*other_team, *team)
+                };
+                *meeting_map.entry(pair).or_default() += 1;
+            }
+        }
+    }
+    meeting_map.values().map(|count| count - 1).sum()
+}
+
+pub fn plan_score(cook_and_run: &CookAndRunData, plan: &PlanData) -> PlanScore {
+    let distance_list: Vec<f64> = team_route_list(cook_and_run, plan)
+        .iter()
+        .map(route_distance)
+        .collect();
+    let total_distance: f64 = distance_list.iter().sum();
+    PlanScore {
+        greatest_distance: plan.greatest_distance,
+        total_distance,
+        average_distance: total_distance / distance_list.len().max(1) as f64,
+        repeated_meeting_count: repeated_meeting_count(plan),
+    }
+}
+
+pub fn compare_plans(
+    cook_and_run: &CookAndRunData,
+    plan_a: &PlanData,
+    plan_b: &PlanData,
+) -> PlanComparison {
+    let stop_name_map = |plan: &PlanData| -> HashMap<Uuid, Vec<String>> {
+        team_route_list(cook_and_run, plan)
+            .into_iter()
+            .map(|route| {
+                (
+                    route.contact.id,
+                    route.stop_list.into_iter().map(|(name, _)| name).collect(),
+                )
+            })
+            .collect()
+    };
+    let stop_name_map_a = stop_name_map(plan_a);
+    let stop_name_map_b = stop_name_map(plan_b);
+
+    let team_difference_list = cook_and_run
+        .contact_list
+        .iter()
+        .filter_map(|contact| {
+            let stop_list_a = stop_name_map_a
+                .get(&contact.id)
+                .cloned()
+                .unwrap_or_default();
+            let stop_list_b = stop_name_map_b
+                .get(&contact.id)
+                .cloned()
+                .unwrap_or_default();
+            if stop_list_a == stop_list_b {
+                return None;
+            }
+            Some(TeamDifference {
+                contact_id: contact.id,
+                team_name: contact.team_name.clone(),
+                stop_list_a,
+                stop_list_b,
+            })
+        })
+        .collect();
+
+    PlanComparison {
+        score_a: plan_score(cook_and_run, plan_a),
+        score_b: plan_score(cook_and_run, plan_b),
+        team_difference_list,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::NaiveTime;
+    use uuid::Uuid;
+
+    use crate::{
+        plan_comparison::compare_plans,
+        storage::{AddressData, ContactData, CookAndRunData, CourseData, HostingData, PlanData},
+    };
+
+    fn get_team(name: &str, latitude: f64) -> ContactData {
+        ContactData {
+            id: Uuid::new_v4(),
+            team_name: name.to_string(),
+            address: AddressData {
+                address: name.to_string(),
+                latitude,
+                longitude: 11.5,
+            },
+            ..Default::default()
+        }
+    }
+
+    fn get_plan(hosting_list: Vec<HostingData>) -> PlanData {
+        let mut walking_path = std::collections::HashMap::new();
+        for hosting in hosting_list.iter() {
+            for team in std::iter::once(&hosting.host).chain(hosting.guest_list.iter()) {
+                walking_path
+                    .entry(*team)
+                    .or_insert_with(Vec::new)
+                    .push(hosting.id);
+            }
+        }
+        PlanData {
+            id: Uuid::new_v4(),
+            hosting_list,
+            walking_path,
+            greatest_distance: 0.0,
+        }
+    }
+
+    #[test]
+    fn test_compare_plans() {
+        let mut cook_and_run = CookAndRunData::new(Uuid::new_v4(), "Summer Run".to_string());
+        let team_list: Vec<ContactData> = (0..4)
+            .map(|i| get_team(&format!("Team {}", i), 48.0 + i as f64 * 0.01))
+            .collect();
+        let starter = CourseData {
+            id: Uuid::new_v4(),
+            name: "Starter".to_string(),
+            time: NaiveTime::from_hms_opt(18, 0, 0).unwrap(),
+        };
+        let main = CourseData {
+            id: Uuid::new_v4(),
+            name: "Main".to_string(),
+            time: NaiveTime::from_hms_opt(20, 0, 0).unwrap(),
+        };
+        cook_and_run.contact_list = team_list.clone();
+        cook_and_run.course_list = vec![starter.clone(), main.clone()];
+
+        let hosting = |course: &CourseData, host: usize, guest: usize| HostingData {
+            id: Uuid::new_v4(),
+            name: course.id,
+            host: team_list[host].id,
+            guest_list: vec![team_list[guest].id],
+        };
+        // Team 0 and 1 meet twice in plan a
+        let plan_a = get_plan(vec![
+            hosting(&starter, 0, 1),
+            hosting(&starter, 2, 3),
+            hosting(&main, 1, 0),
+            hosting(&main, 3, 2),
+        ]);
+        let plan_b = get_plan(vec![
+            hosting(&starter, 0, 1),
+            hosting(&starter, 2, 3),
+            hosting(&main, 1, 2),
+            hosting(&main, 3, 0),
+        ]);
+
+        let comparison = compare_plans(&cook_and_run, &plan_a, &plan_b);
+        assert_eq!(comparison.score_a.repeated_meeting_count, 2);
+        assert_eq!(comparison.score_b.repeated_meeting_count, 0);
+        assert!(comparison.score_a.total_distance > 0.0);
+
+        let changed_team_list: Vec<&str> = comparison
+            .team_difference_list
+            .iter()
+            .map(|d| d.team_name.as_str())
+            .collect();
+        assert_eq!(changed_team_list, vec!["Team 0", "Team 2"]);
+        assert_eq!(
+            comparison.team_difference_list[0].stop_list_b,
+            vec!["Starter: Team 0", "Main: Team 3"]
+        );
+
+        let same = compare_plans(&cook_and_run, &plan_a, &plan_a);
+        assert!(same.team_difference_list.is_empty());
+    }
+}
@@ -0,0 +1,191 @@
+use crate::{
+    pdf::{Font, PdfDocument},
+    storage::{mapper::Plan, ContactData, CookAndRunData, MeetingPointData},
+};
+
+const TITLE_SIZE: f32 = 20.0;
+const HEADLINE_SIZE: f32 = 14.0;
+const TEXT_SIZE: f32 = 11.0;
+
+fn write_meeting_point(
+    document: &mut PdfDocument,
+    headline: &str,
+    meeting_point: &MeetingPointData,
+) {
+    document.text(
+        &format!(
+            "{} - {}: {}",
+            meeting_point.time.format("%H:%M"),
+            headline,
+            meeting_point.name
+        ),
+        HEADLINE_SIZE,
+        Font::Bold,
+    );
+    document.text(&meeting_point.address.address, TEXT_SIZE, Font::Regular);
+    document.space(TEXT_SIZE);
+}
+
+fn write_team_page(
+    document: &mut PdfDocument,
+    cook_and_run: &CookAndRunData,
+    plan: &Plan,
+    contact: &ContactData,
+) {
+    document.text(&contact.team_name, TITLE_SIZE, Font::Bold);
+    document.text(
+        &format!(
+            "{} - {}",
+            cook_and_run.name,
+            cook_and_run.occur.format("%d.%m.%Y")
+        ),
+        TEXT_SIZE,
+        Font::Regular,
+    );
+    document.space(TEXT_SIZE);
+
+    if let Some(start_point) = &cook_and_run.start_point {
+        write_meeting_point(document, "Start", start_point);
+    }
+
+    let mut hosting_list = plan.walking_path.get(contact).cloned().unwrap_or_default();
+    hosting_list.sort_by_key(|h| h.course.time);
+    for hosting in hosting_list.iter() {
+        let you_are_hosting = hosting.host.id == contact.id;
+        document.text(
+            &format!(
+                "{} - {}",
+                hosting.course.time.format("%H:%M"),
+                hosting.course.name
+            ),
+            HEADLINE_SIZE,
+            Font::Bold,
+        );
+        if you_are_hosting {
+            document.text("You are hosting", TEXT_SIZE, Font::Regular);
+        } else {
+            document.text(
+                &format!("Host: {}", hosting.host.team_name),
+                TEXT_SIZE,
+                Font::Regular,
+            );
+        }
+        document.text(
+            &format!("Address: {}", hosting.host.address.address),
+            TEXT_SIZE,
+            Font::Regular,
+        );
+        if you_are_hosting {
+            let mut diet_list: Vec<String> = hosting
+                .guest_list
+                .iter()
+                .flat_map(|g| g.diets.clone())
+                .collect();
+            diet_list.sort();
+            diet_list.dedup();
+            let diets = if diet_list.is_empty() {
+                "None".to_string()
+            } else {
+                diet_list.join(", ")
+            };
+            document.text(&format!("Guest diets: {}", diets), TEXT_SIZE, Font::Regular);
+        }
+        document.space(TEXT_SIZE);
+    }
+
+    if let Some(end_point) = &cook_and_run.end_point {
+        write_meeting_point(document, "End", end_point);
+    }
+
+    if let Some(plan_text) = &cook_and_run.plan_text {
+        document.space(TEXT_SIZE);
+        document.text(plan_text, TEXT_SIZE, Font::Regular);
+    }
+}
+
+// Progress sheets of all teams of the plan as one PDF, one page per team
+pub fn create_progress_sheets(cook_and_run: &CookAndRunData, plan: &Plan) -> String {
+    let mut document = PdfDocument::new();
+    for contact in cook_and_run
+        .contact_list
+        .iter()
+        .filter(|c| plan.walking_path.contains_key(*c))
+    {
+        document.new_page();
+        write_team_page(&mut document, cook_and_run, plan, contact);
+    }
+    document.to_pdf()
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::NaiveTime;
+    use uuid::Uuid;
+
+    use crate::{
+        progress_sheet::create_progress_sheets,
+        storage::{
+            mapper::Plan, AddressData, ContactData, CookAndRunData, CourseData, HostingData,
+            PlanData,
+        },
+    };
+
+    fn get_team(name: &str, diets: Vec<&str>) -> ContactData {
+        ContactData {
+            id: Uuid::new_v4(),
+            team_name: name.to_string(),
+            address: AddressData {
+                address: format!("{} Street 1", name),
+                ..Default::default()
+            },
+            diets: diets.iter().map(|d| d.to_string()).collect(),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_one_page_per_team() {
+        let mut cook_and_run = CookAndRunData::new(Uuid::new_v4(), "Summer Run".to_string());
+        let host = get_team("Host", vec![]);
+        let guest = get_team("Guest", vec!["vegan", "nut-free"]);
+        let not_in_plan = get_team("Late", vec![]);
+        let course = CourseData {
+            id: Uuid::new_v4(),
+            name: "Starter".to_string(),
+            time: NaiveTime::from_hms_opt(18, 0, 0).unwrap(),
+        };
+        cook_and_run.contact_list = vec![host.clone(), guest.clone(), not_in_plan];
+        cook_and_run.course_list = vec![course.clone()];
+        cook_and_run.plan_text = Some("See you at the party!".to_string());
+
+        let hosting = HostingData {
+            id: Uuid::new_v4(),
+            name: course.id,
+            host: host.id,
+            guest_list: vec![guest.id],
+        };
+        let plan_data = PlanData {
+            id: Uuid::new_v4(),
+            hosting_list: vec![hosting.clone()],
+            walking_path: [(host.id, vec![hosting.id]), (guest.id, vec![hosting.id])]
+                .into_iter()
+                .collect(),
+            greatest_distance: 0.0,
+        };
+        let plan = Plan::from_plan_data(
+            &plan_data,
+            &cook_and_run.course_list,
+            &cook_and_run.contact_list,
+        );
+
+        let pdf = create_progress_sheets(&cook_and_run, &plan);
+        assert!(pdf.starts_with("%PDF-1.4"));
+        assert!(pdf.contains("/Count 2"));
+        // Text is hex encoded, "nut-free, vegan" shows up on the page of the host
+        let hex: String = "Guest diets: nut-free, vegan"
+            .bytes()
+            .map(|b| format!("{:02X}", b))
+            .collect();
+        assert_eq!(pdf.matches(&hex).count(), 1);
+    }
+}
@@ -0,0 +1,198 @@
+use chrono::NaiveDateTime;
+use serde::Deserialize;
+use uuid::Uuid;
+
+const SERVER_URL: &str = "http://localhost:3000";
+
+#[derive(Debug, Deserialize, Clone, PartialEq)]
+pub struct ScheduleAddress {
+    pub address: String,
+    pub latitude: f64,
+    pub longitude: f64,
+}
+
+#[derive(Debug, Deserialize, Clone, PartialEq)]
+pub struct ScheduleTeam {
+    pub id: Uuid,
+    pub name: String,
+    pub address: ScheduleAddress,
+    pub mail: Option<String>,
+    pub phone: Option<String>,
+    pub members: Option<u32>,
+    pub diets: Option<String>,
+}
+
+#[derive(Debug, Deserialize, Clone, PartialEq)]
+pub struct ScheduleHost {
+    pub id: Uuid,
+    pub name: String,
+    pub phone: Option<String>,
+    pub address: ScheduleAddress,
+}
+
+#[derive(Debug, Deserialize, Clone, PartialEq)]
+pub struct ScheduleStep {
+    pub course_id: Uuid,
+    pub course_name: String,
+    pub course_time: String,
+    pub you_are_hosting: bool,
+    pub reveal_time: Option<NaiveDateTime>,
+    pub host: Option<ScheduleHost>,
+    pub guests: Vec<String>,
+}
+
+#[derive(Debug, Deserialize, Clone, PartialEq)]
+pub struct SchedulePlan {
+    pub introduction: Option<String>,
+    pub start_point: Option<ScheduleAddress>,
+    pub end_point: Option<ScheduleAddress>,
+    pub steps: Vec<ScheduleStep>,
+    pub next_reveal: Option<NaiveDateTime>,
+}
+
+#[derive(Debug, Deserialize, Clone, PartialEq)]
+pub struct TeamSchedule {
+    pub cook_and_run_id: Uuid,
+    pub cook_and_run_name: String,
+    pub occur: NaiveDateTime,
+    pub team: ScheduleTeam,
+    pub plan: SchedulePlan,
+}
+
+#[derive(Debug, Deserialize, Clone, PartialEq)]
+pub struct ScheduleToken {
+    pub team_id: Uuid,
+    pub token: String,
+}
+
+// Calendar feed of a schedule link that teams can subscribe to
+pub fn schedule_calendar_url(token: &str) -> String {
+    format!(
+        "{}/schedule/{}/calendar.ics",
+        SERVER_URL,
+        urlencoding::encode(token)
+    )
+}
+
+pub async fn get_schedule(token: &str) -> Result<TeamSchedule, String> {
+    let url = format!("{}/schedule/{}", SERVER_URL, urlencoding::encode(token));
+
+    let response = reqwest::get(&url)
+        .await
+        .map_err(|e| format!("Request failed: {}", e))?;
+    if !response.status().is_success() {
+        return Err(format!("Schedule not available: {}", response.status()));
+    }
+
+    response
+        .json::<TeamSchedule>()
+        .await
+        .map_err(|e| format!("Response parsing failed: {}", e))
+}
+
+pub async fn create_schedule_token(
+    cook_and_run_id: Uuid,
+    team_id: Uuid,
+) -> Result<ScheduleToken, String> {
+    let url = format!(
+        "{}/cook_and_run/{}/team/{}/schedule_token",
+        SERVER_URL, cook_and_run_id, team_id
+    );
+
+    let response = reqwest::Client::new()
+        .post(&url)
+        .send()
+        .await
+        .map_err(|e| format!("Request failed: {}", e))?;
+    if !response.status().is_success() {
+        return Err(format!("Schedule link not created: {}", response.status()));
+    }
+
+    response
+        .json::<ScheduleToken>()
+        .await
+        .map_err(|e| format!("Response parsing failed: {}", e))
+}
+
+pub async fn revoke_schedule_token(cook_and_run_id: Uuid, team_id: Uuid) -> Result<(), String> {
+    let url = format!(
+        "{}/cook_and_run/{}/team/{}/schedule_token",
+        SERVER_URL, cook_and_run_id, team_id
+    );
+
+    let response = reqwest::Client::new()
+        .delete(&url)
+        .send()
+        .await
+        .map_err(|e| format!("Request failed: {}", e))?;
+    if !response.status().is_success() {
+        return Err(format!("Schedule link not revoked: {}", response.status()));
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use super::*;
+
+    #[test]
+    fn test_schedule_response_parsing() {
+        let json_data = json!({
+            "cook_and_run_id": "123e4567-e89b-12d3-a456-426614174000",
+            "cook_and_run_name": "Summer Run",
+            "occur": "2025-10-18T18:00:00",
+            "team": {
+                "id": "789e0123-e45f-47a8-b901-234567890123",
+                "name": "The Cooking Ninjas",
+                "created": "2025-10-01T12:00:00",
+                "edited": "2025-10-01T12:00:00",
+                "address": {
+                    "address": "Maximilianstrasse 10, 80539 München",
+                    "latitude": 48.142385,
+                    "longitude": 11.579381
+                },
+                "mail": "ninjas@example.com",
+                "phone": null,
+                "members": 2,
+                "diets": "vegetarian",
+                "needs_check": false,
+                "status": "approved",
+                "review_reason": null,
+                "waitlist_position": null
+            },
+            "plan": {
+                "team_id": "789e0123-e45f-47a8-b901-234567890123",
+                "introduction": "Welcome!",
+                "start_point": null,
+                "end_point": null,
+                "steps": [
+                    {
+                        "course_id": "456e7890-e12b-44c5-a678-901234567890",
+                        "course_name": "Main Course",
+                        "course_time": "20:00",
+                        "you_are_hosting": false,
+                        "revealed": false,
+                        "reveal_time": "2025-10-18T19:30:00",
+                        "host": null,
+                        "guests": []
+                    }
+                ],
+                "next_reveal": "2025-10-18T19:30:00"
+            }
+        });
+
+        let parsed: Result<TeamSchedule, _> = serde_json::from_value(json_data);
+
+        assert!(
+            parsed.is_ok(),
+            "Failed to parse schedule response: {}",
+            parsed.unwrap_err()
+        );
+        let parsed = parsed.unwrap();
+        assert_eq!(parsed.plan.steps.len(), 1);
+        assert!(parsed.plan.steps[0].host.is_none());
+        assert_eq!(parsed.plan.next_reveal, parsed.plan.steps[0].reveal_time);
+    }
+}
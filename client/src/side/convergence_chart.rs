@@ -0,0 +1,73 @@
+use dioxus::prelude::*;
+
+use crate::calculator::IterationStat;
+
+const CHART_WIDTH: f64 = 600.0;
+const CHART_HEIGHT: f64 = 200.0;
+const CHART_PADDING: f64 = 20.0;
+
+fn points(history: &[IterationStat], score: impl Fn(&IterationStat) -> f64) -> String {
+    let min_score = history
+        .iter()
+        .map(|s| s.best_score)
+        .fold(f64::INFINITY, f64::min);
+    let max_score = history
+        .iter()
+        .map(|s| s.population_mean.max(s.best_score))
+        .fold(f64::NEG_INFINITY, f64::max);
+    let score_span = (max_score - min_score).max(f64::EPSILON);
+    let last_iteration = history.last().map_or(1, |s| s.iteration.max(1)) as f64;
+
+    history
+        .iter()
+        .map(|stat| {
+            let x = CHART_PADDING
+                + stat.iteration as f64 / last_iteration * (CHART_WIDTH - 2.0 * CHART_PADDING);
+            let y = CHART_PADDING
+                + (max_score - score(stat)) / score_span * (CHART_HEIGHT - 2.0 * CHART_PADDING);
+            format!("{:.1},{:.1}", x, CHART_HEIGHT - y)
+        })
+        .collect::<Vec<String>>()
+        .join(" ")
+}
+
+// Best score and mean score of the looked at plans per iteration, lower is better
+#[component]
+pub(crate) fn ConvergenceChart(history: Vec<IterationStat>) -> Element {
+    let Some(last) = history.last() else {
+        return rsx!();
+    };
+    let summary = format!(
+        "{} iterations in {:.1} s, greatest distance {:.2} km",
+        last.iteration, last.elapsed_seconds, last.best_score
+    );
+
+    rsx!(
+        div { class: "bg-white shadow-md rounded-xl p-4 my-4",
+            svg {
+                class: "w-full h-auto",
+                view_box: "0 0 {CHART_WIDTH} {CHART_HEIGHT}",
+                xmlns: "http://www.w3.org/2000/svg",
+                rect {
+                    width: "{CHART_WIDTH}",
+                    height: "{CHART_HEIGHT}",
+                    fill: "#fdfaf6",
+                }
+                polyline {
+                    points: points(&history, |s| s.population_mean),
+                    fill: "none",
+                    stroke: "#9CA3AF",
+                    stroke_width: "1",
+                    stroke_dasharray: "4 2",
+                }
+                polyline {
+                    points: points(&history, |s| s.best_score),
+                    fill: "none",
+                    stroke: "#C66741",
+                    stroke_width: "2",
+                }
+            }
+            p { class: "text-sm text-[#543D2B] mt-2", "{summary}" }
+        }
+    )
+}
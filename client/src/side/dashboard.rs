@@ -10,7 +10,7 @@ use web_sys::{console, wasm_bindgen::JsCast, HtmlInputElement};
 use crate::{
     side::{CloseButton, ConfirmButton, Input, InputError, SecondaryButton},
     storage::{LocalStorage, StorageR, StorageW},
-    Route,
+    validation, Route,
 };
 
 #[component]
@@ -207,6 +207,17 @@ fn CreateProjectDialog(create_project_signal: Signal<Element>) -> Element {
                                             error_signal
                                                 .set("Error creating project from file".to_string());
                                         } else {
+                                            // An imported plan may come from an older version or a hand edited file
+                                            if let Ok(cook_and_run) = storage.select_cook_and_run(project_id) {
+                                                if let Some(top_plan) = cook_and_run.top_plan.as_ref() {
+                                                    let report = validation::validate_plan(&cook_and_run, top_plan);
+                                                    for violation in report.violation_list.iter() {
+                                                        console::error_1(
+                                                            &format!("Imported plan violation: {}", violation).into(),
+                                                        );
+                                                    }
+                                                }
+                                            }
                                             create_project_signal
                                                 .set(rsx! {});
                                             use_navigator()
@@ -1,22 +1,48 @@
 use std::sync::{Arc, Mutex};
 
 use dioxus::prelude::*;
+use gloo_timers::future::TimeoutFuture;
 use uuid::Uuid;
 use web_sys::console;
 
 use crate::{
     calculator::Calculator,
-    side::{AddressSVG, Headline1, Headline2, SecondaryButton},
-    storage::{ContactData, LocalStorage, PlanData, StorageR, StorageW},
-    Route,
+    calendar, map_projection, progress_sheet,
+    side::{
+        download_file, AddressSVG, ConvergenceChart, Headline1, Headline2, InputError,
+        InputNumber, PlanMap, SecondaryButton,
+    },
+    storage::{
+        mapper::Plan, ContactData, CookAndRunData, LocalStorage, PlanData, PlanVersionKind,
+        StorageR,
+    },
+    validation, Route,
 };
 
-fn save_plan(cook_and_run_id: Uuid, plan: Option<PlanData>) -> Result<(), String> {
-    let storage = use_context::<Arc<Mutex<LocalStorage>>>();
-    let mut storage = storage.lock().expect("Expected storage lock");
+use super::{
+    plan_analytics::PlanAnalytics,
+    plan_editor::PlanEditor,
+    plan_versions::{save_plan_version, PlanVersions},
+};
 
-    let result = storage.update_top_plan_in_cook_and_run(cook_and_run_id, plan);
-    result
+fn download_calendar(cook_and_run: &CookAndRunData, plan_data: &PlanData) {
+    let plan = Plan::from_plan_data(
+        plan_data,
+        &cook_and_run.course_list,
+        &cook_and_run.contact_list,
+    );
+    let content = calendar::project_calendar(cook_and_run, &plan);
+    download_file(&format!("{}.ics", cook_and_run.name), &content);
+}
+
+fn download_progress_sheets(cook_and_run: &CookAndRunData, plan_data: &PlanData) {
+    let plan = Plan::from_plan_data(
+        plan_data,
+        &cook_and_run.course_list,
+        &cook_and_run.contact_list,
+    );
+    let content = progress_sheet::create_progress_sheets(cook_and_run, &plan);
+    download_file(&format!("{}.pdf", cook_and_run.name), &content);
 }
 
 #[component]
@@ -38,43 +64,147 @@ pub fn Calculate(id: Uuid) -> Element {
     }
     let cook_and_run = cook_and_run.expect("Expect cook and run");
     let mut top_plan_signal = use_signal(|| cook_and_run.top_plan.clone());
+    let mut violation_signal = use_signal(Vec::<String>::new);
+    let mut history_signal = use_signal(Vec::new);
+    let mut stop_after_signal = use_signal(|| "10".to_string());
+    let mut stop_after_error_signal = use_signal(|| "".to_string());
+    let mut running_signal = use_signal(|| false);
 
     let calculator = Calculator::new(&cook_and_run);
     if calculator.is_err() {
-        console::error_1(
-            &format!(
-                "Error while creating calculator: {}",
-                calculator.expect_err("Expect error"),
-            )
-            .into(),
-        );
-        return rsx!( "Error while creating calculator. Are all fields set?" );
+        let err = calculator.expect_err("Expect error");
+        console::error_1(&format!("Error while creating calculator: {}", err).into());
+        return rsx!( "Error while creating calculator: {err}" );
     }
-    let calculator = calculator.expect("Expect calculator");
+    let mut calculator = calculator.expect("Expect calculator");
+    let stop_calculator = calculator.clone();
+    let calendar_cook_and_run = cook_and_run.clone();
+    let sheet_cook_and_run = cook_and_run.clone();
+    let editor_cook_and_run = cook_and_run.clone();
+    let analytics_cook_and_run = cook_and_run.clone();
+    let validation_cook_and_run = cook_and_run.clone();
+    let route_list = top_plan_signal
+        .read()
+        .as_ref()
+        .map(|plan| map_projection::team_route_list(&cook_and_run, plan))
+        .unwrap_or_default();
 
     rsx! {
         section {
             Headline1 { headline: "Calculation".to_string() }
 
-            SecondaryButton {
-                text: "Calculat",
-                onclick: move |_| {
-                    calculator.calculate();
-                    calculator.stop();
-                    match calculator.get_top_plan() {
-                        Some(result) => {
-                            if let Err(e) = save_plan(id, Some(result.clone())) {
-                                console::error_1(&format!("Error saving plan: {}", e).into());
-                            } else {
-                                top_plan_signal.set(Some(result));
-                                console::log_1(&"Plan saved successfully".into());
-                            }
-                        }
-                        None => {
-                            console::error_1(&format!("Calculation result not set!").into());
+            div { class: "flex flex-col px-8",
+                span { class: "text-sm font-semibold mb-1 text-gray-700",
+                    "Stop after seconds without a better plan:"
+                }
+                InputNumber {
+                    place_holer: "Run until no swap helps anymore",
+                    value: stop_after_signal.read().clone(),
+                    is_error: !stop_after_error_signal.read().is_empty(),
+                    oninput: move |e: Event<FormData>| {
+                        let value = e.value();
+                        if value.trim().is_empty() || value.trim().parse::<u32>().is_ok() {
+                            stop_after_error_signal.set("".to_string());
+                        } else {
+                            stop_after_error_signal.set("Seconds have to be a whole number!".to_string());
                         }
+                        stop_after_signal.set(value);
+                    },
+                }
+                InputError { error: stop_after_error_signal.read().clone() }
+            }
+
+            if *running_signal.read() {
+                SecondaryButton {
+                    text: "Stop",
+                    onclick: move |_| stop_calculator.stop(),
+                }
+            } else {
+                SecondaryButton {
+                    text: "Calculat",
+                    onclick: move |_| {
+                        calculator.set_stop_after_no_improvement(stop_after_signal.read().trim().parse::<u32>().ok());
+                        let calculator = calculator.clone();
+                        let validation_cook_and_run = validation_cook_and_run.clone();
+                        running_signal.set(true);
+                        // Hands control back to the browser after every iteration, so the
+                        // chart grows while the plan is improved
+                        spawn(async move {
+                            calculator.start_calculation();
+                            history_signal.set(calculator.get_history());
+                            TimeoutFuture::new(0).await;
+                            while calculator.improve_step() {
+                                history_signal.set(calculator.get_history());
+                                TimeoutFuture::new(0).await;
+                            }
+                            history_signal.set(calculator.get_history());
+                            running_signal.set(false);
+                            match calculator.get_top_plan() {
+                                Some(result) => {
+                                    let report = validation::validate_plan(&validation_cook_and_run, &result);
+                                    for violation in report.violation_list.iter() {
+                                        console::error_1(&format!("Plan violation: {}", violation).into());
+                                    }
+                                    violation_signal.set(report.message_list());
+                                    if let Err(e) = save_plan_version(id, PlanVersionKind::BestFound, result.clone()) {
+                                        console::error_1(&format!("Error saving plan: {}", e).into());
+                                    } else {
+                                        top_plan_signal.set(Some(result));
+                                        console::log_1(&"Plan saved successfully".into());
+                                    }
+                                }
+                                None => {
+                                    console::error_1(&format!("Calculation result not set!").into());
+                                }
+                            }
+                        });
+                    },
+                }
+            }
+
+            ConvergenceChart { history: history_signal.read().clone() }
+
+            if !violation_signal.read().is_empty() {
+                div { class: "text-red-600 text-sm my-2",
+                    p { class: "font-bold", "The calculated plan breaks the rules:" }
+                    for violation in violation_signal.read().iter() {
+                        p { "{violation}" }
                     }
-                },
+                }
+            }
+
+            if let Some(top_plan) = top_plan_signal.read().clone() {
+                SecondaryButton {
+                    text: "Download calendar",
+                    onclick: {
+                        let top_plan = top_plan.clone();
+                        move |_| download_calendar(&calendar_cook_and_run, &top_plan)
+                    },
+                }
+                SecondaryButton {
+                    text: "Download progress sheets",
+                    onclick: move |_| download_progress_sheets(&sheet_cook_and_run, &top_plan),
+                }
+            }
+
+            PlanMap {
+                contact_list: cook_and_run
+                    .contact_list
+                    .iter()
+                    .filter(|contact| contact.is_approved())
+                    .cloned()
+                    .collect::<Vec<ContactData>>(),
+                start_point: cook_and_run.start_point.clone(),
+                end_point: cook_and_run.end_point.clone(),
+                route_list,
+            }
+
+            if top_plan_signal.read().is_some() {
+                PlanEditor { cook_and_run: editor_cook_and_run, top_plan_signal }
+            }
+            PlanVersions { cook_and_run_id: id, top_plan_signal }
+            if let Some(top_plan) = top_plan_signal.read().clone() {
+                PlanAnalytics { cook_and_run: analytics_cook_and_run, plan: top_plan }
             }
 
             div { class: "grid grid-cols-1 md:grid-cols-2 xl:grid-cols-3 gap-6 p-6 max-h-[calc(100vh-16rem)] overflow-y-auto pr-2",
@@ -84,6 +214,7 @@ pub fn Calculate(id: Uuid) -> Element {
                         cook_and_run
                             .contact_list
                             .iter()
+                            .filter(|contact| contact.is_approved())
                             .map(|contact| {
                                 let contact_id = contact.id.clone();
                                 rsx! {
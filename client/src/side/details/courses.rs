@@ -9,7 +9,7 @@ use uuid::Uuid;
 use web_sys::console;
 
 use crate::{
-    side::{Headline1, Input, InputError, InputTime, SavingIcon, WarnButton},
+    side::{Headline1, Input, InputError, InputNumber, InputTime, SavingIcon, WarnButton},
     storage::{CourseData, LocalStorage, StorageW},
 };
 
@@ -17,7 +17,9 @@ use crate::{
 pub struct CoursesParam {
     cook_and_run_id: Uuid,
     course_map: Signal<HashMap<Uuid, CourseParam>>,
-    selected_course: Signal<Option<Uuid>>,
+    course_with_more_hosts_list: Signal<Vec<Uuid>>,
+    guests_per_hosting: Signal<String>,
+    guests_per_hosting_error: Signal<String>,
 }
 
 #[derive(PartialEq, Clone)]
@@ -36,7 +38,8 @@ impl CoursesParam {
     pub(crate) fn new(
         cook_and_run_id: Uuid,
         course_data_list: Vec<CourseData>,
-        selected_course: Option<Uuid>,
+        course_with_more_hosts_list: Vec<Uuid>,
+        guests_per_hosting: Option<u32>,
     ) -> Self {
         let course_map: HashMap<Uuid, CourseParam> = course_data_list
             .iter()
@@ -46,7 +49,13 @@ impl CoursesParam {
         CoursesParam {
             cook_and_run_id,
             course_map: use_signal(|| course_map),
-            selected_course: use_signal(|| selected_course),
+            course_with_more_hosts_list: use_signal(|| course_with_more_hosts_list),
+            guests_per_hosting: use_signal(|| {
+                guests_per_hosting
+                    .map(|g| g.to_string())
+                    .unwrap_or_default()
+            }),
+            guests_per_hosting_error: use_signal(|| "".to_string()),
         }
     }
 
@@ -105,6 +114,9 @@ impl CoursesParam {
         }
 
         self.course_map.write().remove(course_id);
+        self.course_with_more_hosts_list
+            .write()
+            .retain(|c| c != course_id);
     }
 
     fn add_course_in_cook_and_rund_course(&mut self) {
@@ -128,11 +140,19 @@ impl CoursesParam {
         self.course_map.write().insert(course.id.clone(), course);
     }
 
-    fn set_course_with_more_hosts(&mut self, course_id: &Uuid) {
+    fn set_course_with_more_hosts(&mut self, course_id: &Uuid, more_hosts: bool) {
+        let mut course_with_more_hosts_list = self.course_with_more_hosts_list.read().clone();
+        course_with_more_hosts_list.retain(|c| c != course_id);
+        if more_hosts {
+            course_with_more_hosts_list.push(*course_id);
+        }
+
         let storage = use_context::<Arc<Mutex<LocalStorage>>>();
         let mut storage = storage.lock().expect("Expected storage lock");
-        let result = storage
-            .update_course_with_more_hosts_in_cook_and_run(self.cook_and_run_id, course_id.clone());
+        let result = storage.update_course_with_more_hosts_list_in_cook_and_run(
+            self.cook_and_run_id,
+            course_with_more_hosts_list.clone(),
+        );
         if result.is_err() {
             console::error_1(
                 &format!(
@@ -143,7 +163,39 @@ impl CoursesParam {
             );
             return;
         }
-        self.selected_course.set(Some(*course_id));
+        self.course_with_more_hosts_list
+            .set(course_with_more_hosts_list);
+    }
+
+    fn set_guests_per_hosting(&mut self, value: String) {
+        self.guests_per_hosting.set(value.clone());
+        let guests_per_hosting = if value.trim().is_empty() {
+            None
+        } else {
+            match value.trim().parse::<u32>() {
+                Ok(number) if number > 0 => Some(number),
+                _ => {
+                    self.guests_per_hosting_error
+                        .set("Number of guests has to be a positive number!".to_string());
+                    return;
+                }
+            }
+        };
+        self.guests_per_hosting_error.set("".to_string());
+
+        let storage = use_context::<Arc<Mutex<LocalStorage>>>();
+        let mut storage = storage.lock().expect("Expected storage lock");
+        let result = storage
+            .update_guests_per_hosting_in_cook_and_run(self.cook_and_run_id, guests_per_hosting);
+        if result.is_err() {
+            console::error_1(
+                &format!(
+                    "Error while setting guests per hosting: {}",
+                    result.expect_err("Expect error"),
+                )
+                .into(),
+            );
+        }
     }
 }
 
@@ -189,6 +241,19 @@ pub fn Courses(param: CoursesParam) -> Element {
         section {
             Headline1 { headline: "Courses".to_string() }
 
+            div { class: "flex flex-col px-8",
+                span { class: "text-sm font-semibold mb-1 text-gray-700", "Guests per hosting:" }
+                InputNumber {
+                    place_holer: "Number of courses - 1",
+                    value: param.guests_per_hosting.read().clone(),
+                    is_error: !param.guests_per_hosting_error.read().is_empty(),
+                    oninput: move |e: Event<FormData>| {
+                        param.set_guests_per_hosting(e.value());
+                    },
+                }
+                InputError { error: param.guests_per_hosting_error.read().clone() }
+            }
+
             // Scrollable grid
             div { class: "grid grid-cols-1 gap-4 p-8 max-h-[calc(100vh-16rem)] overflow-y-auto pr-6",
 
@@ -202,7 +267,7 @@ pub fn Courses(param: CoursesParam) -> Element {
                                     saving: param.get_course(&course_id).saving,
                                     error: param.get_course(&course_id).saving_error,
                                 }
-                            
+
                                 div { class: "grid grid-cols-10 gap-4",
                                     div { class: "flex flex-col col-span-7", key: course_id,
                                         span { class: "text-sm font-semibold mb-1 text-gray-700", "Name:" }
@@ -224,7 +289,7 @@ pub fn Courses(param: CoursesParam) -> Element {
                                         }
                                         InputError { error: param.get_course(&course_id).name_error }
                                     }
-                            
+
                                     div { class: "flex flex-col col-span-3",
                                         span { class: "text-sm font-semibold mb-1 text-gray-700", "Time:" }
                                         InputTime {
@@ -254,26 +319,26 @@ pub fn Courses(param: CoursesParam) -> Element {
                                         InputError { error: param.get_course(&course_id).time_error }
                                     }
                                 }
-                            
+
                                 div { class: "flex flex-wrap items-center gap-3",
-                            
-                            
+
+
                                     WarnButton {
                                         text: "Delete",
                                         onclick: move |_| {
                                             param.del_course(&course_id);
                                         },
                                     }
-                            
-                            
+
+
                                     div { class: "flex items-center gap-2",
-                            
+
                                         input {
-                                            r#type: "radio",
-                                            name: "multi_participant_course",
-                                            checked: param.selected_course.read().is_some_and(|c| c.eq(&course_id)),
-                                            onchange: move |_| {
-                                                param.set_course_with_more_hosts(&course_id);
+                                            r#type: "checkbox",
+                                            checked: param.course_with_more_hosts_list.read().contains(&course_id),
+                                            onclick: move |_| {
+                                                let more_hosts = !param.course_with_more_hosts_list.read().contains(&course_id);
+                                                param.set_course_with_more_hosts(&course_id, more_hosts);
                                             },
                                         }
                                         label { class: "text-sm text-gray-700", "Allow more hosts!" }
@@ -295,7 +360,7 @@ pub fn Courses(param: CoursesParam) -> Element {
                         }
                     }
                 }
-            
+
             }
         }
     }
@@ -2,6 +2,10 @@ mod address;
 mod calculate;
 mod courses;
 mod overview;
+mod plan_analytics;
+mod plan_editor;
+mod plan_versions;
+mod review;
 mod share_team;
 mod startend;
 mod teams;
@@ -17,6 +21,7 @@ use std::sync::{Arc, Mutex};
 use uuid::Uuid;
 use web_sys::console;
 
+pub(crate) use overview::download_file;
 pub use share_team::ShareTeam;
 
 fn get_cook_and_run_data(id: Uuid) -> Result<CookAndRunData, String> {
@@ -30,6 +35,7 @@ fn get_cook_and_run_data(id: Uuid) -> Result<CookAndRunData, String> {
 enum MenuPage {
     Overview,
     Teams,
+    Review,
     StartEnd,
     Courses,
     Calculation,
@@ -49,6 +55,12 @@ pub fn ProjectTeamsPage(cook_and_run_id: Uuid) -> Element {
     )
 }
 #[component]
+pub fn ProjectReviewPage(cook_and_run_id: Uuid) -> Element {
+    rsx!(
+        ProjectDetailPage { cook_and_run_id, menu: MenuPage::Review }
+    )
+}
+#[component]
 pub fn ProjectStartEndPage(cook_and_run_id: Uuid) -> Element {
     rsx!(
         ProjectDetailPage { cook_and_run_id, menu: MenuPage::StartEnd }
@@ -86,9 +98,15 @@ fn ProjectDetailPage(cook_and_run_id: Uuid, menu: MenuPage) -> Element {
     let cook_and_run = cook_and_run.expect("Expected cook and run data");
     let cook_and_run_overview = cook_and_run.clone();
 
+    let review_team_list = cook_and_run.contact_list.clone();
     let team_props = TeamsProps {
         project_id: cook_and_run_id,
         team_list: cook_and_run.contact_list,
+        start_point: cook_and_run.start_point.clone(),
+        end_point: cook_and_run.end_point.clone(),
+        max_teams: cook_and_run.max_teams,
+        waitlist: cook_and_run.waitlist,
+        participant_list: cook_and_run.participant_list,
     };
 
     let start_end_param = StartEndParam::new(
@@ -100,7 +118,8 @@ fn ProjectDetailPage(cook_and_run_id: Uuid, menu: MenuPage) -> Element {
     let courses_param = CoursesParam::new(
         cook_and_run_id,
         cook_and_run.course_list,
-        cook_and_run.course_with_more_hosts,
+        cook_and_run.course_with_more_hosts_list,
+        cook_and_run.guests_per_hosting,
     );
 
     let current_page = use_signal(|| menu.clone());
@@ -115,6 +134,9 @@ fn ProjectDetailPage(cook_and_run_id: Uuid, menu: MenuPage) -> Element {
                     match current_page() {
                         MenuPage::Overview => Overview(cook_and_run_overview),
                         MenuPage::Teams => Teams(&team_props),
+                        MenuPage::Review => rsx! {
+                            review::Review { project_id: cook_and_run_id, team_list: review_team_list }
+                        },
                         MenuPage::StartEnd => rsx! {
                             StartEnd { param: start_end_param }
                         },
@@ -147,6 +169,7 @@ fn get_side_bar(mut current_page: Signal<MenuPage>) -> Element {
                     current_page,
                 }
                 SidebarButton { label: "Teams", page: MenuPage::Teams, current_page }
+                SidebarButton { label: "Review", page: MenuPage::Review, current_page }
                 SidebarButton {
                     label: "Start and end point",
                     page: MenuPage::StartEnd,
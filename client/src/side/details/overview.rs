@@ -6,7 +6,7 @@ use uuid::Uuid;
 use web_sys::wasm_bindgen::{JsCast, JsValue};
 use web_sys::{console, js_sys, Blob, HtmlAnchorElement, Url};
 
-use crate::side::{Headline1, Headline2, InputDate, InputMultirow, Text};
+use crate::side::{Headline1, Headline2, InputDate, InputMultirow, InputNumber, Text};
 use crate::storage::{CookAndRunData, LocalStorage, StorageR};
 
 use crate::{
@@ -43,6 +43,13 @@ fn update_meta_of_cook_and_run(
     result
 }
 
+fn update_reveal_minutes(id: Uuid, reveal_minutes: Option<u32>) -> Result<(), String> {
+    let storage = use_context::<Arc<Mutex<LocalStorage>>>();
+    let mut storage = storage.lock().expect("Expected storage lock");
+    let result = storage.update_reveal_minutes_in_cook_and_run(id, reveal_minutes);
+    result
+}
+
 fn select_cook_and_run_json(id: Uuid) -> Result<String, String> {
     let storage = use_context::<Arc<Mutex<LocalStorage>>>();
     let storage = storage.lock().expect("Expected storage lock");
@@ -116,10 +123,33 @@ pub(crate) fn Overview(props: CookAndRunData) -> Element {
 
     let mut occur_signal = use_signal(|| props.occur);
 
+    let mut reveal_minutes_signal =
+        use_signal(|| props.reveal_minutes.map(|m| m.to_string()).unwrap_or_default());
+    let mut reveal_minutes_error_signal = use_signal(|| "".to_string());
+
     let on_save = move |_| {
         let current_name = name_signal.read().clone();
+        let reveal_minutes = reveal_minutes_signal.read().trim().to_string();
+        let reveal_minutes = if reveal_minutes.is_empty() {
+            None
+        } else {
+            match reveal_minutes.parse::<u32>() {
+                Ok(m) => Some(m),
+                Err(_) => {
+                    reveal_minutes_error_signal.set("Please enter a valid number".to_string());
+                    return;
+                }
+            }
+        };
+        reveal_minutes_error_signal.set("".to_string());
+
         if current_name.is_empty() {
             error_message.set("Project name can not be empty!".to_string());
+        } else if let Err(e) = update_reveal_minutes(project_id, reveal_minutes) {
+            console::error_1(&format!("Error saving reveal minutes: {}", e).into());
+            error_message.set(
+                "Technical error while saving project. Please try again later.".to_string(),
+            );
         } else {
             let result = update_meta_of_cook_and_run(
                 project_id,
@@ -186,6 +216,17 @@ pub(crate) fn Overview(props: CookAndRunData) -> Element {
                 },
             }
 
+            label { class: "block font-semibold text-[#3B3B3B]",
+                "Reveal addresses (minutes before each course)"
+            }
+            InputNumber {
+                place_holer: "Show all addresses right away",
+                value: reveal_minutes_signal.read().clone(),
+                is_error: !reveal_minutes_error_signal.read().is_empty(),
+                oninput: move |e: FormEvent| reveal_minutes_signal.set(e.value()),
+            }
+            InputError { error: reveal_minutes_error_signal.read() }
+
 
             div { class: "flex flex-wrap gap-4 items-center mt-4",
                 ConfirmButton {
@@ -0,0 +1,66 @@
+use dioxus::prelude::*;
+
+use crate::{
+    plan_analytics,
+    side::Headline2,
+    storage::{CookAndRunData, PlanData},
+};
+
+// Answers "why do we have to walk so far?" for every team of the plan
+#[component]
+pub(crate) fn PlanAnalytics(cook_and_run: CookAndRunData, plan: PlanData) -> Element {
+    let analytics = plan_analytics::analyse_plan(&cook_and_run, &plan);
+    let average_distance = format!("{:.2}", analytics.average_distance);
+
+    rsx! {
+        div { class: "bg-white shadow-md rounded-xl p-6 my-4 space-y-4",
+            Headline2 { headline: "Plan analytics" }
+            p { class: "text-sm text-gray-600",
+                "On average a team walks {average_distance} km. Open a team to see its legs, the teams it meets and what fixed its route."
+            }
+
+            for team in analytics.team_list.iter() {
+                {
+                    let total_distance = format!("{:.2}", team.total_distance);
+                    let difference = format!("{:+.2}", team.difference_to_average);
+                    let difference_class = if team.difference_to_average > 0.0 {
+                        "text-red-600"
+                    } else {
+                        "text-[#4CAF50]"
+                    };
+                    rsx! {
+                        details {
+                            key: "{team.contact_id}",
+                            class: "border-b border-gray-200 pb-2",
+                            summary { class: "cursor-pointer text-[#543D2B]",
+                                span { class: "font-bold", "{team.team_name}" }
+                                span { class: "ml-2", "{total_distance} km" }
+                                span { class: "ml-2 text-sm {difference_class}", "{difference} km to the average" }
+                            }
+                            div { class: "grid grid-cols-1 md:grid-cols-3 gap-4 mt-2 text-sm text-[#543D2B]",
+                                div {
+                                    p { class: "font-bold", "Legs" }
+                                    for leg in team.leg_list.iter() {
+                                        p { {format!("{} → {}: {:.2} km", leg.from, leg.to, leg.distance)} }
+                                    }
+                                }
+                                div {
+                                    p { class: "font-bold", "Meets" }
+                                    for met_team in team.met_team_list.iter() {
+                                        p { "{met_team}" }
+                                    }
+                                }
+                                div {
+                                    p { class: "font-bold", "Fixed by" }
+                                    for constraint in team.constraint_list.iter() {
+                                        p { "{constraint}" }
+                                    }
+                                }
+                            }
+                        }
+                    }
+                }
+            }
+        }
+    }
+}
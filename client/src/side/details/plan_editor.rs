@@ -0,0 +1,296 @@
+use dioxus::prelude::*;
+use uuid::Uuid;
+use web_sys::console;
+
+use crate::{
+    calculator::{Calculator, PlanEditData},
+    side::{ConfirmButton, Headline2, SecondaryButton, WarningSVG},
+    storage::{CookAndRunData, PlanData, PlanVersionKind},
+};
+
+use super::plan_versions::save_plan_version;
+
+fn team_name(cook_and_run: &CookAndRunData, id: Uuid) -> String {
+    cook_and_run
+        .contact_list
+        .iter()
+        .find(|c| c.id == id)
+        .map_or("Unknown team".to_string(), |c| c.team_name.clone())
+}
+
+fn check_top_plan(
+    cook_and_run: &CookAndRunData,
+    top_plan: Option<PlanData>,
+) -> Result<PlanEditData, String> {
+    let top_plan = top_plan.ok_or("No plan has been calculated yet!".to_string())?;
+    Calculator::new(cook_and_run)?.check_plan(&top_plan)
+}
+
+// Lets the organizer drag guests to other tables of the same course and swap hosts.
+// Every change is checked right away, only plans without errors can be saved.
+#[component]
+pub(crate) fn PlanEditor(
+    cook_and_run: CookAndRunData,
+    top_plan_signal: Signal<Option<PlanData>>,
+) -> Element {
+    let mut edit_signal =
+        use_signal(|| check_top_plan(&cook_and_run, top_plan_signal.peek().clone()));
+    let mut drag_signal = use_signal(|| None::<Uuid>);
+    let mut swap_signal = use_signal(|| None::<Uuid>);
+    let mut error_signal = use_signal(|| "".to_string());
+
+    // A new calculation or a saved plan replaces the plan being edited
+    let effect_cook_and_run = cook_and_run.clone();
+    use_effect(move || {
+        let top_plan = top_plan_signal.read().clone();
+        swap_signal.set(None);
+        edit_signal.set(check_top_plan(&effect_cook_and_run, top_plan));
+    });
+
+    // Runs a change against the plan currently shown and keeps the result
+    let drop_cook_and_run = cook_and_run.clone();
+    let on_drop = use_callback(move |hosting_id: Uuid| {
+        let Some(guest) = drag_signal.take() else {
+            return;
+        };
+        let Ok(edit) = edit_signal.peek().clone() else {
+            return;
+        };
+        match Calculator::new(&drop_cook_and_run)
+            .and_then(|c| c.move_guest(&edit.plan, guest, hosting_id))
+        {
+            Ok(edit) => {
+                error_signal.set("".to_string());
+                edit_signal.set(Ok(edit));
+            }
+            Err(e) => error_signal.set(e),
+        }
+    });
+    let swap_cook_and_run = cook_and_run.clone();
+    let on_swap = use_callback(move |hosting_id: Uuid| {
+        let first_hosting_id = match swap_signal.take() {
+            None => {
+                swap_signal.set(Some(hosting_id));
+                return;
+            }
+            Some(first_hosting_id) if first_hosting_id == hosting_id => return,
+            Some(first_hosting_id) => first_hosting_id,
+        };
+        let Ok(edit) = edit_signal.peek().clone() else {
+            return;
+        };
+        match Calculator::new(&swap_cook_and_run)
+            .and_then(|c| c.swap_hosts(&edit.plan, first_hosting_id, hosting_id))
+        {
+            Ok(edit) => {
+                error_signal.set("".to_string());
+                edit_signal.set(Ok(edit));
+            }
+            Err(e) => error_signal.set(e),
+        }
+    });
+
+    let edit = match edit_signal.read().clone() {
+        Ok(edit) => edit,
+        Err(e) => return rsx!("Plan can't be edited: {e}"),
+    };
+    let PlanEditData {
+        plan,
+        error_list,
+        warning_list,
+    } = edit;
+
+    let mut course_list = cook_and_run.course_list.clone();
+    course_list.sort_by_key(|c| c.time);
+    let cook_and_run_id = cook_and_run.id;
+    let has_errors = !error_list.is_empty();
+    let greatest_distance = format!("{:.2}", plan.greatest_distance);
+    let save_plan_data = plan.clone();
+    let reset_cook_and_run = cook_and_run.clone();
+    let cancel_cook_and_run = cook_and_run.clone();
+
+    rsx! {
+        div { class: "bg-white shadow-md rounded-xl p-6 my-4 space-y-4",
+            Headline2 { headline: "Edit plan" }
+            p { class: "text-sm text-gray-600",
+                "Drag a guest to another table of the same course. Click two hosts to swap them."
+            }
+
+            div { class: "grid grid-cols-1 xl:grid-cols-3 gap-4",
+                for course in course_list.iter() {
+                    div { key: "{course.id}", class: "space-y-2",
+                        p { class: "font-bold text-[#543D2B]",
+                            {format!("{} {}", course.time.format("%H:%M"), course.name)}
+                        }
+                        for hosting in plan.hosting_list.iter().filter(|h| h.name == course.id) {
+                            {
+                                let hosting_id = hosting.id;
+                                let selected = *swap_signal.read() == Some(hosting_id);
+                                let border = if selected { "border-[#C66741]" } else { "border-gray-200" };
+                                rsx! {
+                                    div {
+                                        key: "{hosting_id}",
+                                        class: "border-2 {border} rounded-lg p-2",
+                                        ondragover: move |evt| evt.prevent_default(),
+                                        ondrop: move |evt| {
+                                            evt.prevent_default();
+                                            on_drop.call(hosting_id);
+                                        },
+                                        button {
+                                            class: "font-bold text-[#543D2B] hover:text-[#C66741]",
+                                            onclick: move |_| on_swap.call(hosting_id),
+                                            "{team_name(&cook_and_run, hosting.host)}"
+                                        }
+                                        div { class: "flex flex-wrap gap-1 mt-1",
+                                            for guest in hosting.guest_list.iter().copied() {
+                                                span {
+                                                    key: "{guest}",
+                                                    class: "bg-[#fdfaf6] rounded px-2 py-1 text-sm cursor-move",
+                                                    draggable: "true",
+                                                    ondragstart: move |_| drag_signal.set(Some(guest)),
+                                                    "{team_name(&cook_and_run, guest)}"
+                                                }
+                                            }
+                                        }
+                                    }
+                                }
+                            }
+                        }
+                    }
+                }
+            }
+
+            p { class: "text-[#543D2B]", "Greatest distance: {greatest_distance} km" }
+
+            for error in error_list.iter() {
+                p { class: "text-red-600 text-sm", "{error}" }
+            }
+            for warning in warning_list.iter() {
+                div { class: "flex items-start text-sm text-[#543D2B]",
+                    WarningSVG {}
+                    span { class: "mx-2", "{warning}" }
+                }
+            }
+            if !error_signal.read().is_empty() {
+                p { class: "text-red-600 text-sm", "{error_signal}" }
+            }
+
+            div { class: "flex gap-2",
+                if !has_errors {
+                    ConfirmButton {
+                        text: "Save plan",
+                        onclick: move |_| {
+                            match save_plan_version(
+                                cook_and_run_id,
+                                PlanVersionKind::ManuallyEdited,
+                                save_plan_data.clone(),
+                            ) {
+                                Ok(_) => top_plan_signal.set(Some(save_plan_data.clone())),
+                                Err(e) => {
+                                    console::error_1(&format!("Error saving plan: {}", e).into());
+                                    error_signal.set("Plan could not be saved.".to_string());
+                                }
+                            }
+                        },
+                    }
+                }
+                SecondaryButton {
+                    text: "Reset",
+                    onclick: move |_| {
+                        swap_signal.set(None);
+                        error_signal.set("".to_string());
+                        edit_signal
+                            .set(check_top_plan(&reset_cook_and_run, top_plan_signal.peek().clone()));
+                    },
+                }
+            }
+
+            LateCancellation { cook_and_run: cancel_cook_and_run, top_plan_signal }
+        }
+    }
+}
+
+// Replans the published plan without a team that dropped out, the result is kept as a new version
+#[component]
+fn LateCancellation(
+    cook_and_run: CookAndRunData,
+    top_plan_signal: Signal<Option<PlanData>>,
+) -> Element {
+    let mut team_signal = use_signal(|| None::<Uuid>);
+    let mut affected_signal = use_signal(Vec::<String>::new);
+    let mut error_signal = use_signal(|| "".to_string());
+
+    let Some(top_plan) = top_plan_signal.read().clone() else {
+        return rsx!();
+    };
+    let team_list: Vec<(Uuid, String)> = cook_and_run
+        .contact_list
+        .iter()
+        .filter(|c| top_plan.walking_path.contains_key(&c.id))
+        .map(|c| (c.id, c.team_name.clone()))
+        .collect();
+    let cook_and_run_id = cook_and_run.id;
+
+    rsx! {
+        div { class: "border-t border-gray-200 pt-4 space-y-2",
+            p { class: "font-bold text-[#543D2B]", "Late cancellation" }
+            div { class: "flex flex-wrap items-center gap-2",
+                select {
+                    class: "border border-gray-300 rounded-lg p-2 text-sm",
+                    onchange: move |evt| team_signal.set(evt.value().parse::<Uuid>().ok()),
+                    option { value: "", "Select the team that dropped out" }
+                    for (id, name) in team_list.iter() {
+                        option { key: "{id}", value: "{id}", "{name}" }
+                    }
+                }
+                if let Some(team_id) = *team_signal.read() {
+                    SecondaryButton {
+                        text: "Replan without team",
+                        onclick: move |_| {
+                            let cancellation = Calculator::new(&cook_and_run)
+                                .and_then(|c| c.cancel_team(&top_plan, team_id));
+                            let cancellation = match cancellation {
+                                Ok(cancellation) => cancellation,
+                                Err(e) => {
+                                    error_signal.set(e);
+                                    return;
+                                }
+                            };
+                            match save_plan_version(
+                                cook_and_run_id,
+                                PlanVersionKind::AfterCancellation,
+                                cancellation.plan.clone(),
+                            ) {
+                                Ok(_) => {
+                                    affected_signal
+                                        .set(
+                                            cancellation
+                                                .affected_team_list
+                                                .iter()
+                                                .map(|id| team_name(&cook_and_run, *id))
+                                                .collect(),
+                                        );
+                                    error_signal.set("".to_string());
+                                    team_signal.set(None);
+                                    top_plan_signal.set(Some(cancellation.plan));
+                                }
+                                Err(e) => {
+                                    console::error_1(&format!("Error saving plan: {}", e).into());
+                                    error_signal.set("Plan could not be saved.".to_string());
+                                }
+                            }
+                        },
+                    }
+                }
+            }
+            if !affected_signal.read().is_empty() {
+                p { class: "text-sm text-[#543D2B]",
+                    {format!("Please inform the teams with a changed evening: {}", affected_signal.read().join(", "))}
+                }
+            }
+            if !error_signal.read().is_empty() {
+                p { class: "text-red-600 text-sm", "{error_signal}" }
+            }
+        }
+    }
+}
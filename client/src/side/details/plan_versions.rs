@@ -0,0 +1,246 @@
+use std::sync::{Arc, Mutex};
+
+use dioxus::prelude::*;
+use uuid::Uuid;
+use web_sys::console;
+
+use crate::{
+    plan_comparison::{self, PlanScore},
+    side::{Headline2, SecondaryButton, WarnButton},
+    storage::{LocalStorage, PlanData, PlanVersionKind, StorageR, StorageW},
+};
+
+// Keeps the plan as a new version and publishes it, the published version is the top plan
+pub(super) fn save_plan_version(
+    cook_and_run_id: Uuid,
+    kind: PlanVersionKind,
+    plan: PlanData,
+) -> Result<(), String> {
+    let storage = use_context::<Arc<Mutex<LocalStorage>>>();
+    let mut storage = storage.lock().expect("Expected storage lock");
+    let plan_version = storage.add_plan_version_in_cook_and_run(cook_and_run_id, kind, plan)?;
+    storage.publish_plan_version_in_cook_and_run(cook_and_run_id, plan_version.id)
+}
+
+// Returns the new top plan
+fn publish_plan_version(
+    cook_and_run_id: Uuid,
+    plan_version_id: Uuid,
+) -> Result<Option<PlanData>, String> {
+    let storage = use_context::<Arc<Mutex<LocalStorage>>>();
+    let mut storage = storage.lock().expect("Expected storage lock");
+    storage.publish_plan_version_in_cook_and_run(cook_and_run_id, plan_version_id)?;
+    Ok(storage.select_cook_and_run(cook_and_run_id)?.top_plan)
+}
+
+fn delete_plan_version(cook_and_run_id: Uuid, plan_version_id: Uuid) -> Result<(), String> {
+    let storage = use_context::<Arc<Mutex<LocalStorage>>>();
+    let mut storage = storage.lock().expect("Expected storage lock");
+    storage.delete_plan_version_in_cook_and_run(cook_and_run_id, plan_version_id)
+}
+
+fn score_row_list(score_a: &PlanScore, score_b: &PlanScore) -> Vec<(&'static str, String, String)> {
+    vec![
+        (
+            "Greatest distance",
+            format!("{:.2} km", score_a.greatest_distance),
+            format!("{:.2} km", score_b.greatest_distance),
+        ),
+        (
+            "Total distance",
+            format!("{:.2} km", score_a.total_distance),
+            format!("{:.2} km", score_b.total_distance),
+        ),
+        (
+            "Average distance",
+            format!("{:.2} km", score_a.average_distance),
+            format!("{:.2} km", score_b.average_distance),
+        ),
+        (
+            "Repeated meetings",
+            score_a.repeated_meeting_count.to_string(),
+            score_b.repeated_meeting_count.to_string(),
+        ),
+    ]
+}
+
+// All kept plan versions of the project, two of them can be compared side by side
+#[component]
+pub(crate) fn PlanVersions(
+    cook_and_run_id: Uuid,
+    top_plan_signal: Signal<Option<PlanData>>,
+) -> Element {
+    let mut compare_signal = use_signal(Vec::<Uuid>::new);
+    let mut error_signal = use_signal(|| "".to_string());
+    // Deleting a version doesn't touch the top plan, so the list is reloaded by hand
+    let mut reload_signal = use_signal(|| 0);
+
+    let _ = top_plan_signal.read();
+    let _ = reload_signal.read();
+    let storage = use_context::<Arc<Mutex<LocalStorage>>>();
+    let cook_and_run = match storage
+        .lock()
+        .expect("Expected storage lock")
+        .select_cook_and_run(cook_and_run_id)
+    {
+        Ok(cook_and_run) => cook_and_run,
+        Err(e) => return rsx!("Error while loading plan versions: {e}"),
+    };
+    if cook_and_run.plan_version_list.is_empty() {
+        return rsx!();
+    }
+
+    let compare_list = compare_signal.read().clone();
+    let comparison = match compare_list.as_slice() {
+        [a, b] => {
+            let version_a = cook_and_run.plan_version_list.iter().find(|v| v.id == *a);
+            let version_b = cook_and_run.plan_version_list.iter().find(|v| v.id == *b);
+            version_a.zip(version_b).map(|(version_a, version_b)| {
+                (
+                    version_a.name.clone(),
+                    version_b.name.clone(),
+                    plan_comparison::compare_plans(&cook_and_run, &version_a.plan, &version_b.plan),
+                )
+            })
+        }
+        _ => None,
+    };
+
+    rsx! {
+        div { class: "bg-white shadow-md rounded-xl p-6 my-4 space-y-4",
+            Headline2 { headline: "Plan versions" }
+            p { class: "text-sm text-gray-600",
+                "Every calculated or edited plan is kept. Teams only see the published one. Select two versions to compare them."
+            }
+
+            for plan_version in cook_and_run.plan_version_list.iter().rev() {
+                {
+                    let plan_version_id = plan_version.id;
+                    let is_published = cook_and_run.published_plan_version == Some(plan_version_id);
+                    let is_selected = compare_list.contains(&plan_version_id);
+                    let greatest_distance = format!("{:.2}", plan_version.plan.greatest_distance);
+                    rsx! {
+                        div {
+                            key: "{plan_version_id}",
+                            class: "flex flex-wrap items-center gap-4 border-b border-gray-200 pb-2",
+                            input {
+                                r#type: "checkbox",
+                                checked: is_selected,
+                                onchange: move |_| {
+                                    let mut compare_list = compare_signal.write();
+                                    if let Some(index) = compare_list.iter().position(|id| *id == plan_version_id) {
+                                        compare_list.remove(index);
+                                    } else {
+                                        if compare_list.len() == 2 {
+                                            compare_list.remove(0);
+                                        }
+                                        compare_list.push(plan_version_id);
+                                    }
+                                },
+                            }
+                            div { class: "flex-1",
+                                p { class: "font-bold text-[#543D2B]",
+                                    "{plan_version.name}"
+                                    if is_published {
+                                        span { class: "ml-2 bg-[#4CAF50] text-white text-xs rounded-full px-2 py-1",
+                                            "Published"
+                                        }
+                                    }
+                                }
+                                p { class: "text-sm text-gray-600",
+                                    {format!("{} · {} km greatest distance", plan_version.created.format("%d.%m.%Y %H:%M"), greatest_distance)}
+                                }
+                            }
+                            if !is_published {
+                                SecondaryButton {
+                                    text: "Publish",
+                                    onclick: move |_| {
+                                        match publish_plan_version(cook_and_run_id, plan_version_id) {
+                                            Ok(top_plan) => {
+                                                error_signal.set("".to_string());
+                                                top_plan_signal.set(top_plan);
+                                            }
+                                            Err(e) => {
+                                                console::error_1(&format!("Error publishing plan version: {}", e).into());
+                                                error_signal.set("Plan version could not be published.".to_string());
+                                            }
+                                        }
+                                    },
+                                }
+                                WarnButton {
+                                    text: "Delete",
+                                    onclick: move |_| {
+                                        match delete_plan_version(cook_and_run_id, plan_version_id) {
+                                            Ok(_) => {
+                                                compare_signal.write().retain(|id| *id != plan_version_id);
+                                                error_signal.set("".to_string());
+                                                reload_signal += 1;
+                                            }
+                                            Err(e) => {
+                                                console::error_1(&format!("Error deleting plan version: {}", e).into());
+                                                error_signal.set("Plan version could not be deleted.".to_string());
+                                            }
+                                        }
+                                    },
+                                }
+                            }
+                        }
+                    }
+                }
+            }
+            if !error_signal.read().is_empty() {
+                p { class: "text-red-600 text-sm", "{error_signal}" }
+            }
+
+            if let Some((name_a, name_b, comparison)) = comparison {
+                div { class: "space-y-4",
+                    table { class: "w-full text-sm text-left text-[#543D2B]",
+                        thead {
+                            tr {
+                                th { class: "py-1", "" }
+                                th { class: "py-1", "{name_a}" }
+                                th { class: "py-1", "{name_b}" }
+                            }
+                        }
+                        tbody {
+                            for (label, value_a, value_b) in score_row_list(&comparison.score_a, &comparison.score_b) {
+                                tr { key: "{label}",
+                                    td { class: "py-1 font-bold", "{label}" }
+                                    td { class: "py-1", "{value_a}" }
+                                    td { class: "py-1", "{value_b}" }
+                                }
+                            }
+                        }
+                    }
+
+                    if comparison.team_difference_list.is_empty() {
+                        p { class: "text-sm text-gray-600", "Every team has the same evening in both versions." }
+                    } else {
+                        p { class: "font-bold text-[#543D2B]",
+                            "{comparison.team_difference_list.len()} teams with a different evening"
+                        }
+                        table { class: "w-full text-sm text-left text-[#543D2B]",
+                            tbody {
+                                for difference in comparison.team_difference_list.iter() {
+                                    tr { key: "{difference.contact_id}", class: "border-b border-gray-200",
+                                        td { class: "py-1 font-bold align-top", "{difference.team_name}" }
+                                        td { class: "py-1 align-top",
+                                            for stop in difference.stop_list_a.iter() {
+                                                p { "{stop}" }
+                                            }
+                                        }
+                                        td { class: "py-1 align-top",
+                                            for stop in difference.stop_list_b.iter() {
+                                                p { "{stop}" }
+                                            }
+                                        }
+                                    }
+                                }
+                            }
+                        }
+                    }
+                }
+            }
+        }
+    }
+}
@@ -0,0 +1,135 @@
+use std::sync::{Arc, Mutex};
+
+use dioxus::prelude::*;
+use uuid::Uuid;
+use web_sys::console;
+
+use crate::{
+    side::{AddressSVG, ConfirmButton, Headline1, Headline2, Input, Text, WarnButton},
+    storage::{ContactData, LocalStorage, StorageW},
+};
+
+fn review_team(id: Uuid, team_id: Uuid, approved: bool, reason: String) -> Result<(), String> {
+    let storage = use_context::<Arc<Mutex<LocalStorage>>>();
+    let mut storage = storage.lock().expect("Expected storage lock");
+    let result = storage.review_team_in_cook_and_run(id, team_id, approved, Some(reason));
+    result
+}
+
+#[component]
+pub fn Review(project_id: Uuid, team_list: Vec<ContactData>) -> Element {
+    let pending_list_signal = use_signal(|| {
+        team_list
+            .into_iter()
+            .filter(|t| t.needs_check && !t.rejected)
+            .collect::<Vec<ContactData>>()
+    });
+
+    rsx! {
+        section {
+            Headline1 { headline: "Review" }
+
+            if pending_list_signal.read().is_empty() {
+                Text { text: "No teams are waiting for approval." }
+            }
+
+            div { class: "grid grid-cols-1 md:grid-cols-2 gap-4 p-6 max-h-[calc(100vh-16rem)] overflow-y-auto pr-2",
+                {
+                    pending_list_signal
+                        .read()
+                        .clone()
+                        .into_iter()
+                        .map(|team| {
+                            rsx! {
+                                ReviewCard {
+                                    key: "{team.id}",
+                                    project_id,
+                                    team,
+                                    pending_list_signal,
+                                }
+                            }
+                        })
+                }
+            }
+        }
+    }
+}
+
+fn on_review(
+    project_id: Uuid,
+    team_id: Uuid,
+    approved: bool,
+    reason: String,
+    mut pending_list_signal: Signal<Vec<ContactData>>,
+) {
+    let result = review_team(project_id, team_id, approved, reason);
+    if result.is_err() {
+        console::error_1(
+            &format!(
+                "Error reviewing team: {}",
+                result.err().expect("Expected error"),
+            )
+            .into(),
+        );
+    } else {
+        pending_list_signal.write().retain(|t| t.id != team_id);
+    }
+}
+
+#[component]
+fn ReviewCard(
+    project_id: Uuid,
+    team: ContactData,
+    pending_list_signal: Signal<Vec<ContactData>>,
+) -> Element {
+    let mut reason_signal = use_signal(|| "".to_string());
+    let team_id = team.id;
+    let diets = team.diets.join(", ");
+
+    rsx! {
+        div { class: "bg-orange-100 relative shadow-md rounded-xl p-6",
+            Headline2 { headline: team.team_name.clone() }
+            div { class: "flex items-center space-x-2 mb-1",
+                AddressSVG {}
+                p { class: "text-sm text-gray-600 inline-flex items-center",
+                    "{team.address.address}"
+                }
+            }
+            p { class: "text-sm text-gray-600 mb-1", "Mail: {team.mail}" }
+            p { class: "text-sm text-gray-600 mb-1", "Phone: {team.phone_number}" }
+            p { class: "text-sm text-gray-600 mb-1", "Members: {team.members}" }
+            p { class: "text-sm text-gray-600 mb-4", "Diets: {diets}" }
+            Input {
+                place_holer: "Reason (optional)",
+                value: reason_signal.read().clone(),
+                oninput: move |e: Event<FormData>| reason_signal.set(e.value()),
+            }
+            div { class: "flex justify-end space-x-2",
+                WarnButton {
+                    text: "Reject",
+                    onclick: move |_| {
+                        on_review(
+                            project_id,
+                            team_id,
+                            false,
+                            reason_signal.read().trim().to_string(),
+                            pending_list_signal,
+                        )
+                    },
+                }
+                ConfirmButton {
+                    text: "Approve",
+                    onclick: move |_| {
+                        on_review(
+                            project_id,
+                            team_id,
+                            true,
+                            reason_signal.read().trim().to_string(),
+                            pending_list_signal,
+                        )
+                    },
+                }
+            }
+        }
+    }
+}
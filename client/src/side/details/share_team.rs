@@ -22,7 +22,7 @@ fn add_team(
     tel: String,
     members: u32,
     address_data: AddressData,
-) -> Result<(), String> {
+) -> Result<bool, String> {
     let storage = use_context::<Arc<Mutex<LocalStorage>>>();
     let mut storage = storage.lock().expect("Expected storage lock");
 
@@ -35,9 +35,10 @@ fn add_team(
         phone_number: tel,
         members,
         needs_check: true,
+        rejected: false,
         notes: vec![],
     };
-    let result = storage.add_team_to_cook_and_run(id, team);
+    let result = storage.register_team_in_cook_and_run(id, team);
     result
 }
 
@@ -67,6 +68,7 @@ fn AddTeamDialog(project_id: Uuid, share_id: Uuid) -> Element {
     let address_param = AddressParam::default();
 
     let shared = use_signal(|| false);
+    let mut waitlisted_signal = use_signal(|| false);
     if *shared.read() {
         rsx! {}
     } else {
@@ -91,6 +93,12 @@ fn AddTeamDialog(project_id: Uuid, share_id: Uuid) -> Element {
                     }
 
 
+                    if *waitlisted_signal.read() {
+                        p { class: "text-sm text-orange-600 mt-4",
+                            "The maximum number of teams is reached, your team was put on the waitlist."
+                        }
+                    }
+
                     // Create team button
                     div { class: "flex justify-center mt-4",
                         ConfirmButton {
@@ -120,14 +128,11 @@ fn AddTeamDialog(project_id: Uuid, share_id: Uuid) -> Element {
                                         .get_address_data()
                                         .expect("Expext no errors when getting address_data!"),
                                 );
-                                if result.is_err() {
-                                    console::error_1(
-                                        &format!(
-                                            "Error creating team: {}",
-                                            result.err().expect("Expected error"),
-                                        )
-                                            .into(),
-                                    );
+                                match result {
+                                    Ok(waitlisted) => waitlisted_signal.set(waitlisted),
+                                    Err(e) => {
+                                        console::error_1(&format!("Error creating team: {}", e).into());
+                                    }
                                 }
                             },
                         }
@@ -6,12 +6,15 @@ use uuid::Uuid;
 use web_sys::console;
 
 use crate::side::details::address::{Address, AddressParam};
-use crate::side::{AddressSVG, Headline1, Headline2, InputPhoneNumber};
-use crate::storage::{AddressData, ContactData, LocalStorage, NoteData};
+use crate::side::{AddressSVG, Headline1, Headline2, InputPhoneNumber, PlanMap, Text};
+use crate::storage::{
+    AddressData, ContactData, LocalStorage, MeetingPointData, NoteData, ParticipantData,
+};
 
 use crate::{
     side::{
         CloseButton, ConfirmButton, DeleteButton, Input, InputError, InputMultirow, InputNumber,
+        SecondaryButton,
     },
     storage::StorageW,
 };
@@ -37,6 +40,7 @@ fn add_team(
         phone_number: tel,
         members,
         needs_check: false,
+        rejected: false,
         notes: vec![],
     };
     let result = storage.add_team_to_cook_and_run(id, team);
@@ -69,6 +73,41 @@ fn update_team_needs_check(id: Uuid, team_id: Uuid, needs_check: bool) -> Result
     result
 }
 
+fn update_max_teams(id: Uuid, max_teams: Option<u32>) -> Result<(), String> {
+    let storage = use_context::<Arc<Mutex<LocalStorage>>>();
+    let mut storage = storage.lock().expect("Expected storage lock");
+    let result = storage.update_max_teams_in_cook_and_run(id, max_teams);
+    result
+}
+
+fn reorder_waitlist(id: Uuid, team_id_list: Vec<Uuid>) -> Result<(), String> {
+    let storage = use_context::<Arc<Mutex<LocalStorage>>>();
+    let mut storage = storage.lock().expect("Expected storage lock");
+    let result = storage.reorder_waitlist_in_cook_and_run(id, team_id_list);
+    result
+}
+
+fn add_participant(id: Uuid, participant: ParticipantData) -> Result<(), String> {
+    let storage = use_context::<Arc<Mutex<LocalStorage>>>();
+    let mut storage = storage.lock().expect("Expected storage lock");
+    let result = storage.add_participant_to_cook_and_run(id, participant);
+    result
+}
+
+fn delete_participant(id: Uuid, participant_id: Uuid) -> Result<(), String> {
+    let storage = use_context::<Arc<Mutex<LocalStorage>>>();
+    let mut storage = storage.lock().expect("Expected storage lock");
+    let result = storage.delete_participant_in_cook_and_run(id, participant_id);
+    result
+}
+
+fn pair_participants(id: Uuid) -> Result<usize, String> {
+    let storage = use_context::<Arc<Mutex<LocalStorage>>>();
+    let mut storage = storage.lock().expect("Expected storage lock");
+    let result = storage.pair_participants_in_cook_and_run(id);
+    result
+}
+
 fn delete_team(id: Uuid, team_id: Uuid) -> Result<(), String> {
     let storage = use_context::<Arc<Mutex<LocalStorage>>>();
     let mut storage = storage.lock().expect("Expected storage lock");
@@ -79,6 +118,11 @@ fn delete_team(id: Uuid, team_id: Uuid) -> Result<(), String> {
 pub(crate) struct TeamsProps {
     pub project_id: Uuid,
     pub team_list: Vec<ContactData>,
+    pub max_teams: Option<u32>,
+    pub waitlist: Vec<ContactData>,
+    pub participant_list: Vec<ParticipantData>,
+    pub start_point: Option<MeetingPointData>,
+    pub end_point: Option<MeetingPointData>,
 }
 
 #[component]
@@ -105,7 +149,9 @@ pub(crate) fn Teams(props: &TeamsProps) -> Element {
                         .map(|team| {
                             let project_id = props.project_id;
                             let contact_data = team.clone();
-                            let background = if team.needs_check {
+                            let background = if team.rejected {
+                                "bg-gray-200"
+                            } else if team.needs_check {
                                 "bg-orange-100"
                             } else {
                                 "bg-[#fdfaf6]"
@@ -142,12 +188,399 @@ pub(crate) fn Teams(props: &TeamsProps) -> Element {
                     }
                 }
             }
-        
+
+            PlanMap {
+                contact_list: props.team_list.clone(),
+                start_point: props.start_point.clone(),
+                end_point: props.end_point.clone(),
+                route_list: vec![],
+            }
+
+            Waitlist {
+                project_id: props.project_id,
+                max_teams: props.max_teams,
+                waitlist: props.waitlist.clone(),
+            }
+
+            Participants {
+                project_id: props.project_id,
+                participant_list: props.participant_list.clone(),
+            }
         }
         {team_dialog_signal}
     }
 }
 
+#[component]
+fn Participants(project_id: Uuid, participant_list: Vec<ParticipantData>) -> Element {
+    let mut participant_list_signal = use_signal(|| participant_list);
+    let mut pairing_error_signal = use_signal(|| "".to_string());
+    let mut pairing_result_signal = use_signal(|| "".to_string());
+    let mut participant_dialog_signal: Signal<Element> = use_signal(|| rsx!());
+
+    let participant_name = move |participant_id: Option<Uuid>| {
+        participant_id.and_then(|id| {
+            participant_list_signal
+                .read()
+                .iter()
+                .find(|p| p.id == id)
+                .map(|p| p.name.clone())
+        })
+    };
+
+    rsx! {
+        div { class: "px-6",
+            Headline2 { headline: "Single participants" }
+            p { class: "text-sm text-gray-600 mb-2",
+                "Participants without a team are paired into teams of two, every team needs at least one kitchen."
+            }
+
+            if participant_list_signal.read().is_empty() {
+                p { class: "text-sm text-gray-600", "No single participants." }
+            }
+            ul { class: "space-y-2",
+                {
+                    participant_list_signal
+                        .read()
+                        .clone()
+                        .into_iter()
+                        .map(|participant| {
+                            let kitchen = if participant.has_kitchen { "Kitchen" } else { "No kitchen" };
+                            let partner = participant_name(participant.preferred_partner)
+                                .map(|name| format!(", wants to cook with {}", name))
+                                .unwrap_or_default();
+                            rsx! {
+                                li {
+                                    key: "{participant.id}",
+                                    class: "flex items-center justify-between bg-[#fdfaf6] shadow-md rounded-xl px-4 py-2",
+                                    span { class: "text-gray-700", "{participant.name} ({kitchen}{partner})" }
+                                    button {
+                                        class: "text-[#70513E] hover:text-[#C66741]",
+                                        onclick: move |_| {
+                                            let result = delete_participant(project_id, participant.id);
+                                            if result.is_err() {
+                                                console::error_1(
+                                                    &format!(
+                                                        "Error deleting participant: {}",
+                                                        result.err().expect("Expected error"),
+                                                    )
+                                                        .into(),
+                                                );
+                                                return;
+                                            }
+                                            let mut participant_list = participant_list_signal.write();
+                                            participant_list.retain(|p| p.id != participant.id);
+                                            for other in participant_list.iter_mut() {
+                                                if other.preferred_partner == Some(participant.id) {
+                                                    other.preferred_partner = None;
+                                                }
+                                            }
+                                        },
+                                        "✕"
+                                    }
+                                }
+                            }
+                        })
+                }
+            }
+
+            div { class: "flex gap-2 mt-4",
+                SecondaryButton {
+                    text: "Add participant",
+                    onclick: move |_| {
+                        participant_dialog_signal
+                            .set(rsx! {
+                                AddParticipantDialog {
+                                    participant_dialog_signal,
+                                    project_id,
+                                    participant_list_signal,
+                                }
+                            });
+                    },
+                }
+                if !participant_list_signal.read().is_empty() {
+                    ConfirmButton {
+                        text: "Pair into teams".to_string(),
+                        onclick: move |_| {
+                            match pair_participants(project_id) {
+                                Ok(number_of_teams) => {
+                                    pairing_error_signal.set("".to_string());
+                                    pairing_result_signal
+                                        .set(format!("{} new teams were registered.", number_of_teams));
+                                    participant_list_signal.set(vec![]);
+                                }
+                                Err(err) => {
+                                    pairing_result_signal.set("".to_string());
+                                    pairing_error_signal.set(err);
+                                }
+                            }
+                        },
+                    }
+                }
+            }
+            InputError { error: pairing_error_signal.read().clone() }
+            if !pairing_result_signal.read().is_empty() {
+                Text { text: pairing_result_signal.read().clone() }
+            }
+        }
+        {participant_dialog_signal}
+    }
+}
+
+#[component]
+fn AddParticipantDialog(
+    participant_dialog_signal: Signal<Element>,
+    project_id: Uuid,
+    participant_list_signal: Signal<Vec<ParticipantData>>,
+) -> Element {
+    let mut name_signal = use_signal(|| "".to_string());
+    let mut name_error_signal = use_signal(|| "".to_string());
+
+    let mut contact_email_signal = use_signal(|| "".to_string());
+    let contact_email_error_signal = use_signal(|| "".to_string());
+
+    let mut contact_tel_signal = use_signal(|| "".to_string());
+    let contact_tel_error_signal = use_signal(|| "".to_string());
+
+    let mut diets_signal = use_signal(|| "".to_string());
+    let mut has_kitchen_signal = use_signal(|| true);
+    let mut preferred_partner_signal: Signal<Option<Uuid>> = use_signal(|| None);
+
+    let address_param = AddressParam::default();
+    rsx! {
+        div { class: "backdrop-blur fixed inset-0 flex h-screen w-screen justify-center items-center",
+            div { class: "relative bg-white shadow-md rounded-xl p-6 hover:shadow-lg transition-all cursor-pointer w-224",
+                h2 { class: "text-2xl font-semibold text-black-600 mb-4", "Add Participant" }
+                div { class: "flex flex-col md:flex-row",
+                    div { class: "flex-1 pr-4 border-r border-gray-300",
+                        label { class: "block font-semibold text-gray-700 mb-1", "Name" }
+                        Input {
+                            place_holer: Some("e.g. Anna".to_string()),
+                            is_error: !name_error_signal.read().is_empty(),
+                            value: name_signal.clone(),
+                            oninput: move |e: Event<FormData>| {
+                                name_signal.set(e.value());
+                                check_participant_name(name_signal, name_error_signal);
+                            },
+                        }
+                        InputError { error: name_error_signal.read() }
+
+                        label { class: "block font-semibold text-gray-700 mb-1", "E-Mail" }
+                        Input {
+                            place_holer: Some("e.g. anna@example.de".to_string()),
+                            is_error: !contact_email_error_signal.read().is_empty(),
+                            value: contact_email_signal.clone(),
+                            oninput: move |e: Event<FormData>| {
+                                contact_email_signal.set(e.value());
+                                check_contact_email(contact_email_signal, contact_email_error_signal);
+                            },
+                        }
+                        InputError { error: contact_email_error_signal.read() }
+
+                        label { class: "block font-semibold text-gray-700 mb-1", "Phone Number" }
+                        InputPhoneNumber {
+                            place_holer: Some("e.g. +49 1234 56789".to_string()),
+                            is_error: !contact_tel_error_signal.read().is_empty(),
+                            value: contact_tel_signal.clone(),
+                            oninput: move |e: Event<FormData>| {
+                                contact_tel_signal.set(e.value());
+                                check_contact_tel(contact_tel_signal, contact_tel_error_signal);
+                            },
+                        }
+                        InputError { error: contact_tel_error_signal.read() }
+
+                        label { class: "block font-semibold text-gray-700 mb-1", "Dietary requirements" }
+                        Input {
+                            place_holer: Some("e.g. vegetarian, nut allergy, halal ...".to_string()),
+                            is_error: false,
+                            value: diets_signal.clone(),
+                            oninput: move |e: Event<FormData>| {
+                                diets_signal.set(e.value());
+                            },
+                        }
+
+                        div { class: "flex items-center gap-2 mb-4",
+                            input {
+                                r#type: "checkbox",
+                                checked: has_kitchen_signal,
+                                class: "rounded",
+                                onclick: move |_| {
+                                    let has_kitchen = !*has_kitchen_signal.read();
+                                    has_kitchen_signal.set(has_kitchen);
+                                },
+                            }
+                            label { class: "text-sm text-gray-700", "Has a kitchen to cook in" }
+                        }
+
+                        label { class: "block font-semibold text-gray-700 mb-1", "Preferred partner" }
+                        select {
+                            class: "border border-gray-300 rounded-lg p-2 text-sm",
+                            onchange: move |evt| preferred_partner_signal.set(evt.value().parse::<Uuid>().ok()),
+                            option { value: "", "No preference" }
+                            for participant in participant_list_signal.read().iter() {
+                                option {
+                                    key: "{participant.id}",
+                                    value: "{participant.id}",
+                                    "{participant.name}"
+                                }
+                            }
+                        }
+                    }
+
+                    div { class: "flex-1 pl-4",
+                        Address { param: address_param.clone() }
+                    }
+                }
+
+                CloseButton {
+                    onclick: move |_| {
+                        participant_dialog_signal.set(rsx! {});
+                    },
+                }
+
+                div { class: "flex justify-center mt-4",
+                    ConfirmButton {
+                        text: "Add Participant".to_string(),
+                        onclick: move |_| {
+                            let name_check = check_participant_name(name_signal, name_error_signal);
+                            let contact_email_check = check_contact_email(
+                                contact_email_signal,
+                                contact_email_error_signal,
+                            );
+                            let contact_tel_check = check_contact_tel(
+                                contact_tel_signal,
+                                contact_tel_error_signal,
+                            );
+                            let address_check = address_param.check_address_data().is_ok();
+                            if !(name_check && contact_email_check && contact_tel_check && address_check) {
+                                return;
+                            }
+                            let participant = ParticipantData {
+                                id: Uuid::new_v4(),
+                                name: name_signal.read().trim().to_string(),
+                                address: address_param
+                                    .get_address_data()
+                                    .expect("Expext no errors when getting address_data!"),
+                                mail: contact_email_signal.read().trim().to_string(),
+                                phone_number: contact_tel_signal.read().trim().to_string(),
+                                diets: diets_signal
+                                    .read()
+                                    .split(',')
+                                    .map(|s| s.trim().to_string())
+                                    .filter(|s| !s.is_empty())
+                                    .collect(),
+                                has_kitchen: *has_kitchen_signal.read(),
+                                preferred_partner: *preferred_partner_signal.read(),
+                            };
+                            let result = add_participant(project_id, participant.clone());
+                            if result.is_err() {
+                                console::error_1(
+                                    &format!(
+                                        "Error adding participant: {}",
+                                        result.err().expect("Expected error"),
+                                    )
+                                        .into(),
+                                );
+                            } else {
+                                participant_list_signal.write().push(participant);
+                                participant_dialog_signal.set(rsx! {});
+                            }
+                        },
+                    }
+                }
+            }
+        }
+    }
+}
+
+#[component]
+fn Waitlist(project_id: Uuid, max_teams: Option<u32>, waitlist: Vec<ContactData>) -> Element {
+    let mut max_teams_signal = use_signal(|| max_teams.map(|m| m.to_string()).unwrap_or_default());
+    let mut max_teams_error_signal = use_signal(|| "".to_string());
+    let mut waitlist_signal = use_signal(|| waitlist);
+
+    // Swaps the team at the given position with the team before it
+    let mut move_up = move |index: usize| {
+        if index == 0 {
+            return;
+        }
+        let mut new_waitlist = waitlist_signal.read().clone();
+        new_waitlist.swap(index - 1, index);
+        let result = reorder_waitlist(project_id, new_waitlist.iter().map(|t| t.id).collect());
+        if result.is_err() {
+            console::error_1(
+                &format!(
+                    "Error reordering waitlist: {}",
+                    result.err().expect("Expected error"),
+                )
+                .into(),
+            );
+        } else {
+            waitlist_signal.set(new_waitlist);
+        }
+    };
+
+    rsx! {
+        div { class: "px-6",
+            Headline2 { headline: "Waitlist" }
+            label { class: "block font-semibold text-gray-700 mb-1", "Maximum number of teams" }
+            InputNumber {
+                place_holer: "No limit",
+                value: max_teams_signal.read().clone(),
+                is_error: !max_teams_error_signal.read().is_empty(),
+                oninput: move |e: Event<FormData>| {
+                    let value = e.value();
+                    max_teams_signal.set(value.clone());
+                    let max_teams = if value.trim().is_empty() {
+                        None
+                    } else {
+                        match value.trim().parse::<u32>() {
+                            Ok(m) => Some(m),
+                            Err(_) => {
+                                max_teams_error_signal.set("Please enter a valid number".to_string());
+                                return;
+                            }
+                        }
+                    };
+                    max_teams_error_signal.set("".to_string());
+                    if let Err(e) = update_max_teams(project_id, max_teams) {
+                        console::error_1(&format!("Error updating max teams: {}", e).into());
+                    }
+                },
+            }
+            InputError { error: max_teams_error_signal.read().clone() }
+
+            if waitlist_signal.read().is_empty() {
+                p { class: "text-sm text-gray-600", "No teams on the waitlist." }
+            }
+            ol { class: "space-y-2",
+                {
+                    waitlist_signal
+                        .read()
+                        .clone()
+                        .into_iter()
+                        .enumerate()
+                        .map(|(index, team)| {
+                            rsx! {
+                                li {
+                                    key: "{team.id}",
+                                    class: "flex items-center justify-between bg-[#fdfaf6] shadow-md rounded-xl px-4 py-2",
+                                    span { class: "text-gray-700", "{index + 1}. {team.team_name}" }
+                                    button {
+                                        class: "text-[#70513E] hover:text-[#C66741] disabled:text-gray-300",
+                                        disabled: index == 0,
+                                        onclick: move |_| move_up(index),
+                                        "▲"
+                                    }
+                                }
+                            }
+                        })
+                }
+            }
+        }
+    }
+}
+
 #[component]
 fn TeamCard(props: ContactData) -> Element {
     rsx! {
@@ -167,6 +600,12 @@ fn TeamCard(props: ContactData) -> Element {
                     "!"
                 }
             }
+            // Rejected Indicator
+            if props.rejected {
+                div { class: "absolute top-2 right-2 bg-gray-500 text-white text-xs font-bold rounded-full px-2 py-1",
+                    "Rejected"
+                }
+            }
         }
     }
 }
@@ -433,6 +872,7 @@ fn EditTeamDialog(
                                             .map(|s| s.trim().to_string())
                                             .collect(),
                                         needs_check: *needs_check_signal.read(),
+                                        rejected: contact_data.rejected,
                                         notes: vec![],
                                     },
                                 );
@@ -766,6 +1206,19 @@ fn check_team_name(
     }
 }
 
+fn check_participant_name(
+    name_signal: Signal<String>,
+    mut name_error_signal: Signal<String>,
+) -> bool {
+    if name_signal.read().trim().is_empty() {
+        name_error_signal.set("Name cannot be empty!".to_string());
+        false
+    } else {
+        name_error_signal.set("".to_string());
+        true
+    }
+}
+
 fn check_contact_email(
     contact_email_signal: Signal<String>,
     mut contact_email_error_signal: Signal<String>,
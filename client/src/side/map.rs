@@ -0,0 +1,162 @@
+use dioxus::prelude::*;
+use uuid::Uuid;
+
+use crate::{
+    map_projection::{Projection, TeamRoute},
+    storage::{AddressData, ContactData, MeetingPointData},
+};
+
+const MAP_WIDTH: f64 = 800.0;
+const MAP_HEIGHT: f64 = 500.0;
+const MAP_PADDING: f64 = 30.0;
+
+fn points(projection: &Projection, route: &TeamRoute) -> String {
+    route
+        .stop_list
+        .iter()
+        .map(|(_, address)| {
+            let (x, y) = projection.project(address);
+            format!("{:.1},{:.1}", x, y)
+        })
+        .collect::<Vec<String>>()
+        .join(" ")
+}
+
+// Map of all teams and meeting points drawn as SVG, after the calculation with the walking
+// path of every team
+#[component]
+pub(crate) fn PlanMap(
+    contact_list: Vec<ContactData>,
+    start_point: Option<MeetingPointData>,
+    end_point: Option<MeetingPointData>,
+    route_list: Vec<TeamRoute>,
+) -> Element {
+    let mut hover_signal = use_signal(|| None::<Uuid>);
+
+    let mut address_list: Vec<&AddressData> = contact_list.iter().map(|c| &c.address).collect();
+    address_list.extend(start_point.iter().map(|s| &s.address));
+    address_list.extend(end_point.iter().map(|e| &e.address));
+    let Some(projection) = Projection::new(&address_list, MAP_WIDTH, MAP_HEIGHT, MAP_PADDING)
+    else {
+        return rsx!();
+    };
+
+    let hover = *hover_signal.read();
+    let hover_route = hover.and_then(|id| route_list.iter().find(|r| r.contact.id == id).cloned());
+    let hover_contact = hover.and_then(|id| contact_list.iter().find(|c| c.id == id).cloned());
+
+    rsx!(
+        div { class: "bg-white shadow-md rounded-xl p-4 my-4",
+            svg {
+                class: "w-full h-auto",
+                view_box: "0 0 {MAP_WIDTH} {MAP_HEIGHT}",
+                xmlns: "http://www.w3.org/2000/svg",
+                rect {
+                    width: "{MAP_WIDTH}",
+                    height: "{MAP_HEIGHT}",
+                    fill: "#fdfaf6",
+                }
+
+                // Walking paths
+                for route in route_list.iter() {
+                    polyline {
+                        key: "{route.contact.id}-path",
+                        points: points(&projection, route),
+                        fill: "none",
+                        stroke: "{route.color}",
+                        stroke_width: if hover == Some(route.contact.id) { "4" } else { "2" },
+                        stroke_opacity: if hover.is_none() || hover == Some(route.contact.id) { "0.9" } else { "0.15" },
+                    }
+                }
+
+                // Teams
+                for contact in contact_list.iter() {
+                    {
+                        let (x, y) = projection.project(&contact.address);
+                        let contact_id = contact.id;
+                        let color = route_list
+                            .iter()
+                            .find(|r| r.contact.id == contact_id)
+                            .map_or("#543D2B".to_string(), |r| r.color.clone());
+                        rsx! {
+                            circle {
+                                key: "{contact_id}",
+                                cx: "{x}",
+                                cy: "{y}",
+                                r: if hover == Some(contact_id) { "9" } else { "6" },
+                                fill: "{color}",
+                                stroke: "white",
+                                stroke_width: "2",
+                                class: "cursor-pointer",
+                                onmouseenter: move |_| hover_signal.set(Some(contact_id)),
+                                onmouseleave: move |_| hover_signal.set(None),
+                                title { "{contact.team_name}\n{contact.address.address}" }
+                            }
+                        }
+                    }
+                }
+
+                // Meeting points
+                if let Some(start_point) = &start_point {
+                    MeetingPointMarker {
+                        meeting_point: start_point.clone(),
+                        position: projection.project(&start_point.address),
+                        color: "#4CAF50",
+                    }
+                }
+                if let Some(end_point) = &end_point {
+                    MeetingPointMarker {
+                        meeting_point: end_point.clone(),
+                        position: projection.project(&end_point.address),
+                        color: "#C66741",
+                    }
+                }
+            }
+
+            // Details of the team under the mouse
+            div { class: "min-h-16 text-sm text-[#543D2B] mt-2",
+                if let Some(contact) = hover_contact {
+                    p { class: "font-bold", "{contact.team_name}" }
+                    p { "{contact.address.address}" }
+                    if let Some(route) = hover_route {
+                        p {
+                            {
+                                route
+                                    .stop_list
+                                    .iter()
+                                    .map(|(name, _)| name.clone())
+                                    .collect::<Vec<String>>()
+                                    .join(" → ")
+                            }
+                        }
+                    }
+                } else {
+                    p { class: "text-gray-400", "Point at a team to see its details" }
+                }
+            }
+        }
+    )
+}
+
+#[component]
+fn MeetingPointMarker(
+    meeting_point: MeetingPointData,
+    position: (f64, f64),
+    color: String,
+) -> Element {
+    let (x, y) = position;
+    rsx!(
+        rect {
+            x: "{x - 8.0}",
+            y: "{y - 8.0}",
+            width: "16",
+            height: "16",
+            fill: "{color}",
+            stroke: "white",
+            stroke_width: "2",
+            title {
+                "{meeting_point.name} ({meeting_point.time.format(\"%H:%M\")})\n{meeting_point.address.address}"
+            }
+        }
+    )
+}
@@ -1,15 +1,23 @@
+mod convergence_chart;
 mod dashboard;
 mod details;
+mod map;
 mod run_schedule;
 
 pub use dashboard::Dashboard;
 pub use details::ProjectCalculationPage;
 pub use details::ProjectCoursesPage;
 pub use details::ProjectOverviewPage;
+pub use details::ProjectReviewPage;
 pub use details::ProjectStartEndPage;
 pub use details::ProjectTeamsPage;
 pub use details::ShareTeam;
 pub use run_schedule::RunSchedule;
+pub use run_schedule::SharedRunSchedule;
+
+pub(crate) use convergence_chart::ConvergenceChart;
+pub(crate) use details::download_file;
+pub(crate) use map::PlanMap;
 
 use dioxus::prelude::*;
 use dioxus::signals::{Readable, Signal};
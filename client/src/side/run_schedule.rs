@@ -1,7 +1,8 @@
 use std::sync::{Arc, Mutex};
 
-use chrono::NaiveTime;
+use chrono::{Local, NaiveDateTime};
 use dioxus::prelude::*;
+use gloo_timers::future::TimeoutFuture;
 use uuid::Uuid;
 use web_sys::{
     console, js_sys,
@@ -10,7 +11,13 @@ use web_sys::{
 };
 
 use crate::{
-    side::{AddressSVG, PersonSVG, PhoneSVG, StartSVG, WarningSVG},
+    calendar,
+    host_briefing::HostBriefing,
+    server_connector::{self, ScheduleStep, TeamSchedule},
+    side::{
+        download_file, AddressSVG, PersonSVG, PhoneSVG, SecondaryButton, StartSVG, WarnButton,
+        WarningSVG,
+    },
     storage::{
         mapper::{Hosting, Plan},
         AddressData, ContactData, CookAndRunData, CourseData, HostingData, LocalStorage,
@@ -26,6 +33,8 @@ const LEAF_2: Asset = asset!("/assets/leaf_2.png");
 const CAKE: Asset = asset!("/assets/cake.png");
 const CARROT: Asset = asset!("/assets/carrot.png");
 
+const REFRESH_MILLIS: u32 = 30_000;
+
 fn get_cook_and_run(cook_and_run_id: Uuid) -> Result<CookAndRunData, String> {
     let storage = use_context::<Arc<Mutex<LocalStorage>>>();
     let storage = storage.lock().expect("Expected storage lock");
@@ -36,6 +45,15 @@ fn get_cook_and_run(cook_and_run_id: Uuid) -> Result<CookAndRunData, String> {
 
 #[component]
 pub fn RunSchedule(cook_and_run_id: Uuid, contact_id: Uuid) -> Element {
+    // Checks regularly if the next address can be revealed
+    let mut now_signal = use_signal(|| Local::now().naive_local());
+    use_future(move || async move {
+        loop {
+            TimeoutFuture::new(REFRESH_MILLIS).await;
+            now_signal.set(Local::now().naive_local());
+        }
+    });
+
     let cook_and_run = get_cook_and_run(cook_and_run_id);
     if cook_and_run.is_err() {
         console::error_1(
@@ -70,7 +88,7 @@ pub fn RunSchedule(cook_and_run_id: Uuid, contact_id: Uuid) -> Element {
     }
     let current_contact = current_contact.expect("Expect current contact").clone();
 
-    let plan_data = cook_and_run.top_plan;
+    let plan_data = cook_and_run.top_plan.clone();
     if plan_data.is_none() {
         console::error_1(&format!("Error while loading top plan: {}", "No top plan found",).into());
         return rsx!(
@@ -93,7 +111,14 @@ pub fn RunSchedule(cook_and_run_id: Uuid, contact_id: Uuid) -> Element {
         .iter()
         .filter(|current_walking_path| current_walking_path.0.id.eq(&current_contact.id))
         .flat_map(|current_walking_path| current_walking_path.1.iter())
-        .map(|hosting| HostingParam::new(hosting, current_contact.id))
+        .map(|hosting| {
+            HostingParam::new(
+                hosting,
+                current_contact.id,
+                cook_and_run.reveal_time(&hosting.course),
+                *now_signal.read(),
+            )
+        })
         .collect();
 
     let current_hosting = plan
@@ -103,6 +128,9 @@ pub fn RunSchedule(cook_and_run_id: Uuid, contact_id: Uuid) -> Element {
         .cloned()
         .expect("Expect one hosting for current contact");
 
+    let calendar_file_name = format!("{} - {}.ics", cook_and_run.name, current_contact.team_name);
+    let calendar_content = calendar::team_calendar(&cook_and_run, &plan, current_contact.id);
+
     rsx!(
         div { class: "fixed bottom-4 right-4 z-50 flex gap-4",
 
@@ -126,8 +154,32 @@ pub fn RunSchedule(cook_and_run_id: Uuid, contact_id: Uuid) -> Element {
                     path { d: "M15 19l-7-7 7-7" }
                 }
             }
-        
 
+            // Calendar Button
+            button {
+                class: "bg-gray-600 hover:bg-gray-700 text-white p-3 rounded-full shadow-lg",
+                title: "Download calendar",
+                onclick: move |_| {
+                    match &calendar_content {
+                        Ok(content) => download_file(&calendar_file_name, content),
+                        Err(e) => {
+                            console::error_1(&format!("Error while creating calendar: {}", e).into())
+                        }
+                    }
+                },
+                svg {
+                    class: "w-6 h-6",
+                    fill: "none",
+                    stroke: "currentColor",
+                    stroke_width: "2",
+                    view_box: "0 0 24 24",
+                    xmlns: "http://www.w3.org/2000/svg",
+                    path { d: "M8 7V3m8 4V3m-9 8h10M5 21h14a2 2 0 002-2V7a2 2 0 00-2-2H5a2 2 0 00-2 2v12a2 2 0 002 2z" }
+                }
+            }
+        }
+        if cook_and_run.is_in_cloud {
+            ScheduleLink { cook_and_run_id, contact_id }
         }
         div {
             id: "section-to-print",
@@ -195,13 +247,20 @@ struct HostingParam {
     address: String,
     guests: Vec<(String, u32)>,
     diets: String,
+    reveal_time: Option<String>,
 }
 
 impl HostingParam {
-    fn new(hosting: &Hosting, current_contact_id: Uuid) -> Self {
+    fn new(
+        hosting: &Hosting,
+        current_contact_id: Uuid,
+        reveal_time: Option<NaiveDateTime>,
+        now: NaiveDateTime,
+    ) -> Self {
+        let you_are_hosting = hosting.host.id.eq(&current_contact_id);
         HostingParam {
             id: hosting.id,
-            you_are_hosting: hosting.host.id.eq(&current_contact_id),
+            you_are_hosting,
             course_name: hosting.course.name.clone(),
             course_team_name: hosting.host.team_name.clone(),
             course_team_tel: hosting.host.phone_number.clone(),
@@ -218,10 +277,223 @@ impl HostingParam {
                 .flat_map(|g| g.diets.clone())
                 .collect::<Vec<String>>()
                 .join(", "),
+            // Guests don't see where they are going before the reveal time
+            reveal_time: reveal_time
+                .filter(|r| !you_are_hosting && *r > now)
+                .map(|r| r.format("%H:%M").to_string()),
+        }
+    }
+}
+
+impl HostingParam {
+    fn from_schedule_step(step: &ScheduleStep) -> Self {
+        let reveal_time = step
+            .reveal_time
+            .filter(|_| step.host.is_none())
+            .map(|r| r.format("%H:%M").to_string());
+        HostingParam {
+            id: step.course_id,
+            you_are_hosting: step.you_are_hosting,
+            course_name: step.course_name.clone(),
+            course_team_name: step
+                .host
+                .as_ref()
+                .map_or("".to_string(), |h| h.name.clone()),
+            course_team_tel: step
+                .host
+                .as_ref()
+                .and_then(|h| h.phone.clone())
+                .unwrap_or_default(),
+            course_time: step.course_time.clone(),
+            address: step
+                .host
+                .as_ref()
+                .map_or("".to_string(), |h| h.address.address.clone()),
+            guests: step.guests.iter().map(|g| (g.clone(), 0)).collect(),
+            diets: "".to_string(),
+            reveal_time,
         }
     }
 }
 
+fn schedule_url(token: &str) -> String {
+    let origin = window()
+        .and_then(|w| w.location().origin().ok())
+        .unwrap_or_default();
+    format!("{}/schedule/{}", origin, token)
+}
+
+// Lets the organizer hand out or revoke the link with which a team opens its schedule
+#[component]
+fn ScheduleLink(cook_and_run_id: Uuid, contact_id: Uuid) -> Element {
+    let mut link_signal = use_signal(|| None::<(String, String)>);
+    let mut error_signal = use_signal(|| "".to_string());
+
+    rsx!(
+        div { class: "fixed top-4 right-4 z-50 bg-white shadow-lg rounded-xl p-4 max-w-sm space-y-2",
+            div { class: "flex gap-2",
+                SecondaryButton {
+                    text: "Create schedule link",
+                    onclick: move |_| {
+                        spawn(async move {
+                            match server_connector::create_schedule_token(cook_and_run_id, contact_id)
+                                .await
+                            {
+                                Ok(schedule_token) => {
+                                    error_signal.set("".to_string());
+                                    link_signal.set(Some((
+                                        schedule_url(&schedule_token.token),
+                                        server_connector::schedule_calendar_url(&schedule_token.token),
+                                    )));
+                                }
+                                Err(e) => {
+                                    console::error_1(
+                                        &format!("Error creating schedule link: {}", e).into(),
+                                    );
+                                    error_signal.set("Schedule link could not be created.".to_string());
+                                }
+                            }
+                        });
+                    },
+                }
+                WarnButton {
+                    text: "Revoke",
+                    onclick: move |_| {
+                        spawn(async move {
+                            match server_connector::revoke_schedule_token(cook_and_run_id, contact_id)
+                                .await
+                            {
+                                Ok(_) => {
+                                    error_signal.set("".to_string());
+                                    link_signal.set(None);
+                                }
+                                Err(e) => {
+                                    console::error_1(
+                                        &format!("Error revoking schedule link: {}", e).into(),
+                                    );
+                                    error_signal.set("Schedule link could not be revoked.".to_string());
+                                }
+                            }
+                        });
+                    },
+                }
+            }
+            if let Some((link, calendar_link)) = link_signal.read().clone() {
+                p { class: "text-sm text-gray-600 break-all", "{link}" }
+                p { class: "text-sm text-gray-600 break-all", "Calendar: {calendar_link}" }
+            }
+            if !error_signal.read().is_empty() {
+                p { class: "text-sm text-red-600", "{error_signal}" }
+            }
+        }
+    )
+}
+
+// Schedule of a team opened via its schedule link, loaded from the server
+#[component]
+pub fn SharedRunSchedule(token: String) -> Element {
+    let mut schedule_signal = use_signal(|| None::<Result<TeamSchedule, String>>);
+    let future_token = token.clone();
+    use_future(move || {
+        let token = future_token.clone();
+        async move {
+            loop {
+                let result = server_connector::get_schedule(&token).await;
+                if let Err(e) = &result {
+                    console::error_1(&format!("Error while loading schedule: {}", e).into());
+                }
+                schedule_signal.set(Some(result));
+                TimeoutFuture::new(REFRESH_MILLIS).await;
+            }
+        }
+    });
+
+    let schedule = match schedule_signal.read().clone() {
+        None => {
+            return rsx!(
+                div { "Loading schedule..." }
+            )
+        }
+        Some(Err(_)) => {
+            return rsx!(
+                div { "Schedule not found!" }
+            )
+        }
+        Some(Ok(schedule)) => schedule,
+    };
+
+    let cook_and_run_date = schedule.occur.format("%d.%m.%Y").to_string();
+    let contact = ContactData {
+        id: schedule.team.id,
+        team_name: schedule.team.name.clone(),
+        address: AddressData {
+            address: schedule.team.address.address.clone(),
+            latitude: schedule.team.address.latitude,
+            longitude: schedule.team.address.longitude,
+        },
+        mail: schedule.team.mail.clone().unwrap_or_default(),
+        phone_number: schedule.team.phone.clone().unwrap_or_default(),
+        members: schedule.team.members.unwrap_or_default(),
+        diets: schedule
+            .team
+            .diets
+            .clone()
+            .map(|d| d.split(',').map(|s| s.trim().to_string()).collect())
+            .unwrap_or_default(),
+        ..Default::default()
+    };
+    let walking_path: Vec<HostingParam> = schedule
+        .plan
+        .steps
+        .iter()
+        .map(HostingParam::from_schedule_step)
+        .collect();
+    let introduction = schedule.plan.introduction.clone().unwrap_or_default();
+    let start_address = schedule.plan.start_point.clone().map(|a| a.address);
+    let end_address = schedule.plan.end_point.clone().map(|a| a.address);
+    let calendar_url = server_connector::schedule_calendar_url(&token);
+
+    rsx!(
+        div { class: "flex justify-center w-full h-full",
+            div { class: "space-y-8 w-full max-w-3xl",
+                div { class: "text-center",
+                    h1 { class: "font-chewy text-9xl text-[#543D2B] tracking-wide",
+                        "COOK"
+                    }
+                    h1 { class: "font-chewy text-9xl text-[#543D2B] tracking-wide",
+                        "AND RUN"
+                    }
+                }
+
+                p { class: "font-gluten text-[#543D2B] mt-2", "{introduction}" }
+
+                a {
+                    class: "font-gluten text-[#C66741] underline",
+                    href: "{calendar_url}",
+                    "Subscribe to calendar"
+                }
+
+                MyInfo { contact }
+
+                if let Some(start_address) = start_address {
+                    p { class: "font-gluten text-[#543D2B]", "Start: {start_address}" }
+                }
+
+                TimeLine {
+                    cook_and_run_date,
+                    start_point: None,
+                    end_point: None,
+                    walking_path,
+                }
+
+                if let Some(end_address) = end_address {
+                    p { class: "font-gluten text-[#543D2B]", "End: {end_address}" }
+                }
+            }
+        }
+    )
+}
+
 #[component]
 fn TimeLine(
     cook_and_run_date: String,
@@ -291,6 +563,7 @@ fn TimeLine(
                         course_time: event.course_time.clone(),
                         address: event.address.clone(),
                         you_are_hosting: event.you_are_hosting,
+                        reveal_time: event.reveal_time.clone(),
                     }
                 }
                 if end_point.is_some() {
@@ -319,9 +592,9 @@ fn TimeLine(
 
 #[component]
 fn Placeholder() -> Element {
-    rsx!(
-        div { class: "flex-1 text-center items-center" }
-    )
+    rsx!(div {
+        class: "flex-1 text-center items-center"
+    })
 }
 
 #[component]
@@ -333,14 +606,25 @@ fn TimeLineElement(
     course_time: String,
     address: String,
     you_are_hosting: bool,
+    reveal_time: Option<String>,
 ) -> Element {
+    let is_hidden = reveal_time.is_some();
     let point_format = if you_are_hosting {
         "bg-[#C66741] rotate-45"
     } else {
         "bg-[#543D2B] rounded-full"
     };
 
-    let content = if you_are_hosting {
+    let content = if let Some(reveal_time) = reveal_time {
+        rsx!(
+            div { class: "font-thin flex items-start",
+                div { class: "my-2", AddressSVG {} }
+                div { class: "mx-0 text-[#543D2B] font-gluten leading-tight",
+                    span { "Revealed at {reveal_time}" }
+                }
+            }
+        )
+    } else if you_are_hosting {
         rsx!(
             div { class: "font-thin flex items-start",
                 StartSVG {}
@@ -376,7 +660,7 @@ fn TimeLineElement(
                         span { "{address}" }
                     }
                 }
-            
+
             }
         )
     };
@@ -385,7 +669,7 @@ fn TimeLineElement(
             div { class: "absolute h-[calc(100%-var(--item-margin)*2)] inset-y-0 -inset-x-(--item-overflow) m-(--item-margin) flex flex-col items-center justify-center",
                 span { class: "font-bold", "{course_time} - {course_name}" }
                 div {
-                    if course_team_name.is_some() {
+                    if course_team_name.is_some() && !is_hidden {
                         div { class: "font-thin flex items-start",
                             PersonSVG {}
                             div { class: "mx-0   text-[#543D2B] font-gluten",
@@ -403,6 +687,18 @@ fn TimeLineElement(
 
 #[component]
 fn MyHosting(hosting: Hosting) -> Element {
+    let briefing = HostBriefing::new(&hosting);
+    let arrival_time = briefing.arrival_time.format("%H:%M").to_string();
+    let headcount = briefing.headcount();
+    let guest_headcount = briefing.guest_headcount();
+    let diets = briefing
+        .diet_list
+        .iter()
+        .map(|(diet, count)| format!("{} ({})", diet, count))
+        .collect::<Vec<String>>()
+        .join(", ");
+    let briefing_file_name = format!("{} - {}.pdf", briefing.course_name, briefing.host_name);
+
     rsx!(
         div { class: "flex items-center my-4",
             div { class: "flex-grow h-1 bg-[#C66741]" }
@@ -410,6 +706,23 @@ fn MyHosting(hosting: Hosting) -> Element {
             div { class: "flex-grow h-1 bg-[#C66741]" }
         }
 
+        // Briefing
+        div { class: "text-[#543D2B] font-gluten mb-4",
+            p { "Guests arrive at {arrival_time}" }
+            p { class: "font-bold", "{headcount} people at the table ({guest_headcount} guests)" }
+            if !diets.is_empty() {
+                div { class: "flex items-start",
+                    WarningSVG {}
+                    span { class: "mx-2", "{diets}" }
+                }
+            }
+            button {
+                class: "print:hidden underline text-[#C66741]",
+                onclick: move |_| download_file(&briefing_file_name, &briefing.to_pdf()),
+                "Download briefing"
+            }
+        }
+
 
         for guest in hosting.guest_list {
             // Guest
@@ -478,7 +791,7 @@ fn MyInfo(contact: ContactData) -> Element {
             div { class: "mx-2 text-[#543D2B] font-gluten",
                 span { "{contact.mail}" }
             }
-        
+
         }
 
         // Phone
@@ -488,7 +801,7 @@ fn MyInfo(contact: ContactData) -> Element {
             div { class: "mx-2 text-[#543D2B] font-gluten",
                 span { "{contact.phone_number}" }
             }
-        
+
         }
 
         // Address
@@ -498,11 +498,27 @@ impl StorageW for LocalStorage {
         Err(format!("Cook and run project with ID {} not found", id))
     }
 
-    fn delete_team_in_cook_and_run(&mut self, id: Uuid, team_id: Uuid) -> Result<(), String> {
+    fn review_team_in_cook_and_run(
+        &mut self,
+        id: Uuid,
+        team_id: Uuid,
+        approved: bool,
+        reason: Option<String>,
+    ) -> Result<(), String> {
         for data in &mut self.cook_and_run_data {
             if data.id == id {
-                if let Some(index) = data.contact_list.iter().position(|x| x.id == team_id) {
-                    data.contact_list.remove(index);
+                if let Some(team) = data.contact_list.iter_mut().find(|x| x.id == team_id) {
+                    team.needs_check = false;
+                    team.rejected = !approved;
+                    if let Some(reason) = reason.filter(|r| !r.trim().is_empty()) {
+                        let headline = if approved { "Approved" } else { "Rejected" };
+                        team.notes.push(super::NoteData {
+                            id: Uuid::new_v4(),
+                            headline: headline.to_string(),
+                            description: reason,
+                            created: chrono::Utc::now(),
+                        });
+                    }
                     let cook_and_run_data_string = serde_json::to_string(&self.cook_and_run_data);
 
                     if cook_and_run_data_string.is_err() {
@@ -538,6 +554,318 @@ impl StorageW for LocalStorage {
         Err(format!("Cook and run project with ID {} not found", id))
     }
 
+    fn delete_team_in_cook_and_run(&mut self, id: Uuid, team_id: Uuid) -> Result<(), String> {
+        for data in &mut self.cook_and_run_data {
+            if data.id == id {
+                data.remove_team(team_id)?;
+                let cook_and_run_data_string = serde_json::to_string(&self.cook_and_run_data);
+
+                if cook_and_run_data_string.is_err() {
+                    return Err(format!(
+                        "Struct could not be parsed into json: {}",
+                        cook_and_run_data_string
+                            .err()
+                            .expect("Expected serde error")
+                    ));
+                }
+
+                let cook_and_run_data_string =
+                    cook_and_run_data_string.expect("Expected parsed data");
+
+                let result = self.storage.set_item(DATA_KEY, &cook_and_run_data_string);
+
+                if result.is_err() {
+                    return Err(format!(
+                        "Data could not be stored: {}",
+                        result
+                            .err()
+                            .expect("Expected storage error")
+                            .as_string()
+                            .expect("Expected storage error to be string")
+                    ));
+                }
+                return Ok(());
+            }
+        }
+        Err(format!("Cook and run project with ID {} not found", id))
+    }
+
+    fn register_team_in_cook_and_run(
+        &mut self,
+        id: Uuid,
+        team: super::ContactData,
+    ) -> Result<bool, String> {
+        for data in &mut self.cook_and_run_data {
+            if data.id == id {
+                let waitlisted = data.register_team(team);
+                let cook_and_run_data_string = serde_json::to_string(&self.cook_and_run_data);
+
+                if cook_and_run_data_string.is_err() {
+                    return Err(format!(
+                        "Struct could not be parsed into json: {}",
+                        cook_and_run_data_string
+                            .err()
+                            .expect("Expected serde error")
+                    ));
+                }
+
+                let cook_and_run_data_string =
+                    cook_and_run_data_string.expect("Expected parsed data");
+
+                let result = self.storage.set_item(DATA_KEY, &cook_and_run_data_string);
+
+                if result.is_err() {
+                    return Err(format!(
+                        "Data could not be stored: {}",
+                        result
+                            .err()
+                            .expect("Expected storage error")
+                            .as_string()
+                            .expect("Expected storage error to be string")
+                    ));
+                }
+                return Ok(waitlisted);
+            }
+        }
+        Err(format!("Cook and run project with ID {} not found", id))
+    }
+
+    fn update_max_teams_in_cook_and_run(
+        &mut self,
+        id: Uuid,
+        max_teams: Option<u32>,
+    ) -> Result<(), String> {
+        for data in &mut self.cook_and_run_data {
+            if data.id == id {
+                data.max_teams = max_teams;
+                let cook_and_run_data_string = serde_json::to_string(&self.cook_and_run_data);
+
+                if cook_and_run_data_string.is_err() {
+                    return Err(format!(
+                        "Struct could not be parsed into json: {}",
+                        cook_and_run_data_string
+                            .err()
+                            .expect("Expected serde error")
+                    ));
+                }
+
+                let cook_and_run_data_string =
+                    cook_and_run_data_string.expect("Expected parsed data");
+
+                let result = self.storage.set_item(DATA_KEY, &cook_and_run_data_string);
+
+                if result.is_err() {
+                    return Err(format!(
+                        "Data could not be stored: {}",
+                        result
+                            .err()
+                            .expect("Expected storage error")
+                            .as_string()
+                            .expect("Expected storage error to be string")
+                    ));
+                }
+                return Ok(());
+            }
+        }
+        Err(format!("Cook and run project with ID {} not found", id))
+    }
+
+    fn update_reveal_minutes_in_cook_and_run(
+        &mut self,
+        id: Uuid,
+        reveal_minutes: Option<u32>,
+    ) -> Result<(), String> {
+        for data in &mut self.cook_and_run_data {
+            if data.id == id {
+                data.reveal_minutes = reveal_minutes;
+                let cook_and_run_data_string = serde_json::to_string(&self.cook_and_run_data);
+
+                if cook_and_run_data_string.is_err() {
+                    return Err(format!(
+                        "Struct could not be parsed into json: {}",
+                        cook_and_run_data_string
+                            .err()
+                            .expect("Expected serde error")
+                    ));
+                }
+
+                let cook_and_run_data_string =
+                    cook_and_run_data_string.expect("Expected parsed data");
+
+                let result = self.storage.set_item(DATA_KEY, &cook_and_run_data_string);
+
+                if result.is_err() {
+                    return Err(format!(
+                        "Data could not be stored: {}",
+                        result
+                            .err()
+                            .expect("Expected storage error")
+                            .as_string()
+                            .expect("Expected storage error to be string")
+                    ));
+                }
+                return Ok(());
+            }
+        }
+        Err(format!("Cook and run project with ID {} not found", id))
+    }
+
+    fn reorder_waitlist_in_cook_and_run(
+        &mut self,
+        id: Uuid,
+        team_id_list: Vec<Uuid>,
+    ) -> Result<(), String> {
+        for data in &mut self.cook_and_run_data {
+            if data.id == id {
+                data.reorder_waitlist(&team_id_list)?;
+                let cook_and_run_data_string = serde_json::to_string(&self.cook_and_run_data);
+
+                if cook_and_run_data_string.is_err() {
+                    return Err(format!(
+                        "Struct could not be parsed into json: {}",
+                        cook_and_run_data_string
+                            .err()
+                            .expect("Expected serde error")
+                    ));
+                }
+
+                let cook_and_run_data_string =
+                    cook_and_run_data_string.expect("Expected parsed data");
+
+                let result = self.storage.set_item(DATA_KEY, &cook_and_run_data_string);
+
+                if result.is_err() {
+                    return Err(format!(
+                        "Data could not be stored: {}",
+                        result
+                            .err()
+                            .expect("Expected storage error")
+                            .as_string()
+                            .expect("Expected storage error to be string")
+                    ));
+                }
+                return Ok(());
+            }
+        }
+        Err(format!("Cook and run project with ID {} not found", id))
+    }
+
+    fn add_participant_to_cook_and_run(
+        &mut self,
+        id: Uuid,
+        participant: super::ParticipantData,
+    ) -> Result<(), String> {
+        for data in &mut self.cook_and_run_data {
+            if data.id == id {
+                data.participant_list.push(participant);
+                let cook_and_run_data_string = serde_json::to_string(&self.cook_and_run_data);
+
+                if cook_and_run_data_string.is_err() {
+                    return Err(format!(
+                        "Struct could not be parsed into json: {}",
+                        cook_and_run_data_string
+                            .err()
+                            .expect("Expected serde error")
+                    ));
+                }
+
+                let cook_and_run_data_string =
+                    cook_and_run_data_string.expect("Expected parsed data");
+
+                let result = self.storage.set_item(DATA_KEY, &cook_and_run_data_string);
+
+                if result.is_err() {
+                    return Err(format!(
+                        "Data could not be stored: {}",
+                        result
+                            .err()
+                            .expect("Expected storage error")
+                            .as_string()
+                            .expect("Expected storage error to be string")
+                    ));
+                }
+                return Ok(());
+            }
+        }
+        Err(format!("Cook and run project with ID {} not found", id))
+    }
+
+    fn delete_participant_in_cook_and_run(
+        &mut self,
+        id: Uuid,
+        participant_id: Uuid,
+    ) -> Result<(), String> {
+        for data in &mut self.cook_and_run_data {
+            if data.id == id {
+                data.remove_participant(participant_id)?;
+                let cook_and_run_data_string = serde_json::to_string(&self.cook_and_run_data);
+
+                if cook_and_run_data_string.is_err() {
+                    return Err(format!(
+                        "Struct could not be parsed into json: {}",
+                        cook_and_run_data_string
+                            .err()
+                            .expect("Expected serde error")
+                    ));
+                }
+
+                let cook_and_run_data_string =
+                    cook_and_run_data_string.expect("Expected parsed data");
+
+                let result = self.storage.set_item(DATA_KEY, &cook_and_run_data_string);
+
+                if result.is_err() {
+                    return Err(format!(
+                        "Data could not be stored: {}",
+                        result
+                            .err()
+                            .expect("Expected storage error")
+                            .as_string()
+                            .expect("Expected storage error to be string")
+                    ));
+                }
+                return Ok(());
+            }
+        }
+        Err(format!("Cook and run project with ID {} not found", id))
+    }
+
+    fn pair_participants_in_cook_and_run(&mut self, id: Uuid) -> Result<usize, String> {
+        for data in &mut self.cook_and_run_data {
+            if data.id == id {
+                let number_of_teams = data.pair_participants()?;
+                let cook_and_run_data_string = serde_json::to_string(&self.cook_and_run_data);
+
+                if cook_and_run_data_string.is_err() {
+                    return Err(format!(
+                        "Struct could not be parsed into json: {}",
+                        cook_and_run_data_string
+                            .err()
+                            .expect("Expected serde error")
+                    ));
+                }
+
+                let cook_and_run_data_string =
+                    cook_and_run_data_string.expect("Expected parsed data");
+
+                let result = self.storage.set_item(DATA_KEY, &cook_and_run_data_string);
+
+                if result.is_err() {
+                    return Err(format!(
+                        "Data could not be stored: {}",
+                        result
+                            .err()
+                            .expect("Expected storage error")
+                            .as_string()
+                            .expect("Expected storage error to be string")
+                    ));
+                }
+                return Ok(number_of_teams);
+            }
+        }
+        Err(format!("Cook and run project with ID {} not found", id))
+    }
+
     fn update_start_point_in_cook_and_run(
         &mut self,
         id: Uuid,
@@ -707,6 +1035,7 @@ impl StorageW for LocalStorage {
             if data.id == id {
                 if let Some(index) = data.course_list.iter().position(|x| x.id == course_id) {
                     data.course_list.remove(index);
+                    data.course_with_more_hosts_list.retain(|x| *x != course_id);
                     let cook_and_run_data_string = serde_json::to_string(&self.cook_and_run_data);
 
                     if cook_and_run_data_string.is_err() {
@@ -742,50 +1071,87 @@ impl StorageW for LocalStorage {
         Err(format!("Cook and run project with ID {} not found", id))
     }
 
-    fn update_course_with_more_hosts_in_cook_and_run(
+    fn update_course_with_more_hosts_list_in_cook_and_run(
         &mut self,
         id: Uuid,
-        course_data_id: Uuid,
+        course_data_id_list: Vec<Uuid>,
     ) -> Result<(), String> {
         for data in &mut self.cook_and_run_data {
             if data.id == id {
-                if data
-                    .course_list
-                    .iter_mut()
-                    .find(|x| x.id == course_data_id)
-                    .is_some()
+                if let Some(course_data_id) = course_data_id_list
+                    .iter()
+                    .find(|course_id| !data.course_list.iter().any(|x| x.id == **course_id))
                 {
-                    data.course_with_more_hosts = Some(course_data_id);
-                    let cook_and_run_data_string = serde_json::to_string(&self.cook_and_run_data);
+                    return Err(format!("Course with ID {} not found", course_data_id));
+                }
+                data.course_with_more_hosts_list = course_data_id_list;
+                let cook_and_run_data_string = serde_json::to_string(&self.cook_and_run_data);
 
-                    if cook_and_run_data_string.is_err() {
-                        return Err(format!(
-                            "Struct could not be parsed into json: {}",
-                            cook_and_run_data_string
-                                .err()
-                                .expect("Expected serde error")
-                        ));
-                    }
+                if cook_and_run_data_string.is_err() {
+                    return Err(format!(
+                        "Struct could not be parsed into json: {}",
+                        cook_and_run_data_string
+                            .err()
+                            .expect("Expected serde error")
+                    ));
+                }
 
-                    let cook_and_run_data_string =
-                        cook_and_run_data_string.expect("Expected parsed data");
+                let cook_and_run_data_string =
+                    cook_and_run_data_string.expect("Expected parsed data");
 
-                    let result = self.storage.set_item(DATA_KEY, &cook_and_run_data_string);
+                let result = self.storage.set_item(DATA_KEY, &cook_and_run_data_string);
 
-                    if result.is_err() {
-                        return Err(format!(
-                            "Data could not be stored: {}",
-                            result
-                                .err()
-                                .expect("Expected storage error")
-                                .as_string()
-                                .expect("Expected storage error to be string")
-                        ));
-                    }
-                    return Ok(());
-                } else {
-                    return Err(format!("Course with ID {} not found", course_data_id));
+                if result.is_err() {
+                    return Err(format!(
+                        "Data could not be stored: {}",
+                        result
+                            .err()
+                            .expect("Expected storage error")
+                            .as_string()
+                            .expect("Expected storage error to be string")
+                    ));
                 }
+                return Ok(());
+            }
+        }
+        Err(format!("Cook and run project with ID {} not found", id))
+    }
+
+    fn update_guests_per_hosting_in_cook_and_run(
+        &mut self,
+        id: Uuid,
+        guests_per_hosting: Option<u32>,
+    ) -> Result<(), String> {
+        for data in &mut self.cook_and_run_data {
+            if data.id == id {
+                data.guests_per_hosting = guests_per_hosting;
+                let cook_and_run_data_string = serde_json::to_string(&self.cook_and_run_data);
+
+                if cook_and_run_data_string.is_err() {
+                    return Err(format!(
+                        "Struct could not be parsed into json: {}",
+                        cook_and_run_data_string
+                            .err()
+                            .expect("Expected serde error")
+                    ));
+                }
+
+                let cook_and_run_data_string =
+                    cook_and_run_data_string.expect("Expected parsed data");
+
+                let result = self.storage.set_item(DATA_KEY, &cook_and_run_data_string);
+
+                if result.is_err() {
+                    return Err(format!(
+                        "Data could not be stored: {}",
+                        result
+                            .err()
+                            .expect("Expected storage error")
+                            .as_string()
+                            .expect("Expected storage error to be string")
+                    ));
+                }
+                return Ok(());
             }
         }
         Err(format!("Cook and run project with ID {} not found", id))
@@ -831,6 +1197,127 @@ impl StorageW for LocalStorage {
         Err(format!("Cook and run project with ID {} not found", id))
     }
 
+    fn add_plan_version_in_cook_and_run(
+        &mut self,
+        id: Uuid,
+        kind: super::PlanVersionKind,
+        plan: super::PlanData,
+    ) -> Result<super::PlanVersionData, String> {
+        for data in &mut self.cook_and_run_data {
+            if data.id == id {
+                let plan_version = data.add_plan_version(kind, plan);
+                let cook_and_run_data_string = serde_json::to_string(&self.cook_and_run_data);
+
+                if cook_and_run_data_string.is_err() {
+                    return Err(format!(
+                        "Struct could not be parsed into json: {}",
+                        cook_and_run_data_string
+                            .err()
+                            .expect("Expected serde error")
+                    ));
+                }
+
+                let cook_and_run_data_string =
+                    cook_and_run_data_string.expect("Expected parsed data");
+
+                let result = self.storage.set_item(DATA_KEY, &cook_and_run_data_string);
+
+                if result.is_err() {
+                    return Err(format!(
+                        "Data could not be stored: {}",
+                        result
+                            .err()
+                            .expect("Expected storage error")
+                            .as_string()
+                            .expect("Expected storage error to be string")
+                    ));
+                }
+                return Ok(plan_version);
+            }
+        }
+        Err(format!("Cook and run project with ID {} not found", id))
+    }
+
+    fn publish_plan_version_in_cook_and_run(
+        &mut self,
+        id: Uuid,
+        plan_version_id: Uuid,
+    ) -> Result<(), String> {
+        for data in &mut self.cook_and_run_data {
+            if data.id == id {
+                data.publish_plan_version(plan_version_id)?;
+                let cook_and_run_data_string = serde_json::to_string(&self.cook_and_run_data);
+
+                if cook_and_run_data_string.is_err() {
+                    return Err(format!(
+                        "Struct could not be parsed into json: {}",
+                        cook_and_run_data_string
+                            .err()
+                            .expect("Expected serde error")
+                    ));
+                }
+
+                let cook_and_run_data_string =
+                    cook_and_run_data_string.expect("Expected parsed data");
+
+                let result = self.storage.set_item(DATA_KEY, &cook_and_run_data_string);
+
+                if result.is_err() {
+                    return Err(format!(
+                        "Data could not be stored: {}",
+                        result
+                            .err()
+                            .expect("Expected storage error")
+                            .as_string()
+                            .expect("Expected storage error to be string")
+                    ));
+                }
+                return Ok(());
+            }
+        }
+        Err(format!("Cook and run project with ID {} not found", id))
+    }
+
+    fn delete_plan_version_in_cook_and_run(
+        &mut self,
+        id: Uuid,
+        plan_version_id: Uuid,
+    ) -> Result<(), String> {
+        for data in &mut self.cook_and_run_data {
+            if data.id == id {
+                data.delete_plan_version(plan_version_id)?;
+                let cook_and_run_data_string = serde_json::to_string(&self.cook_and_run_data);
+
+                if cook_and_run_data_string.is_err() {
+                    return Err(format!(
+                        "Struct could not be parsed into json: {}",
+                        cook_and_run_data_string
+                            .err()
+                            .expect("Expected serde error")
+                    ));
+                }
+
+                let cook_and_run_data_string =
+                    cook_and_run_data_string.expect("Expected parsed data");
+
+                let result = self.storage.set_item(DATA_KEY, &cook_and_run_data_string);
+
+                if result.is_err() {
+                    return Err(format!(
+                        "Data could not be stored: {}",
+                        result
+                            .err()
+                            .expect("Expected storage error")
+                            .as_string()
+                            .expect("Expected storage error to be string")
+                    ));
+                }
+                return Ok(());
+            }
+        }
+        Err(format!("Cook and run project with ID {} not found", id))
+    }
+
     fn create_cook_and_run_json(&mut self, uuid: Uuid, json: String) -> Result<(), String> {
         console::log_1(&format!("LocalStorage - Create cook and run from JSON!").into());
 
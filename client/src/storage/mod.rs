@@ -1,8 +1,10 @@
 use std::{collections::HashMap, hash::Hash};
 
-use chrono::{DateTime, NaiveDate, NaiveTime, Utc};
+use chrono::{DateTime, Duration, NaiveDate, NaiveDateTime, NaiveTime, Utc};
 use serde::{Deserialize, Serialize};
 use uuid::Uuid;
+
+use crate::pairing;
 mod local_storage;
 pub mod mapper;
 pub use local_storage::LocalStorage;
@@ -37,7 +39,45 @@ pub trait StorageW {
         team_id: Uuid,
         needs_check: bool,
     ) -> Result<(), String>;
+    fn review_team_in_cook_and_run(
+        &mut self,
+        id: Uuid,
+        team_id: Uuid,
+        approved: bool,
+        reason: Option<String>,
+    ) -> Result<(), String>;
     fn delete_team_in_cook_and_run(&mut self, id: Uuid, team_id: Uuid) -> Result<(), String>;
+    fn register_team_in_cook_and_run(
+        &mut self,
+        id: Uuid,
+        team: ContactData,
+    ) -> Result<bool, String>;
+    fn add_participant_to_cook_and_run(
+        &mut self,
+        id: Uuid,
+        participant: ParticipantData,
+    ) -> Result<(), String>;
+    fn delete_participant_in_cook_and_run(
+        &mut self,
+        id: Uuid,
+        participant_id: Uuid,
+    ) -> Result<(), String>;
+    fn pair_participants_in_cook_and_run(&mut self, id: Uuid) -> Result<usize, String>;
+    fn update_max_teams_in_cook_and_run(
+        &mut self,
+        id: Uuid,
+        max_teams: Option<u32>,
+    ) -> Result<(), String>;
+    fn reorder_waitlist_in_cook_and_run(
+        &mut self,
+        id: Uuid,
+        team_id_list: Vec<Uuid>,
+    ) -> Result<(), String>;
+    fn update_reveal_minutes_in_cook_and_run(
+        &mut self,
+        id: Uuid,
+        reveal_minutes: Option<u32>,
+    ) -> Result<(), String>;
     fn update_start_point_in_cook_and_run(
         &mut self,
         id: Uuid,
@@ -64,10 +104,16 @@ pub trait StorageW {
         course_data_id: Uuid,
     ) -> Result<(), String>;
 
-    fn update_course_with_more_hosts_in_cook_and_run(
+    fn update_course_with_more_hosts_list_in_cook_and_run(
         &mut self,
         id: Uuid,
-        course_data_id: Uuid,
+        course_data_id_list: Vec<Uuid>,
+    ) -> Result<(), String>;
+
+    fn update_guests_per_hosting_in_cook_and_run(
+        &mut self,
+        id: Uuid,
+        guests_per_hosting: Option<u32>,
     ) -> Result<(), String>;
 
     fn update_top_plan_in_cook_and_run(
@@ -75,6 +121,23 @@ pub trait StorageW {
         id: Uuid,
         top_plan: Option<PlanData>,
     ) -> Result<(), String>;
+
+    fn add_plan_version_in_cook_and_run(
+        &mut self,
+        id: Uuid,
+        kind: PlanVersionKind,
+        plan: PlanData,
+    ) -> Result<PlanVersionData, String>;
+    fn publish_plan_version_in_cook_and_run(
+        &mut self,
+        id: Uuid,
+        plan_version_id: Uuid,
+    ) -> Result<(), String>;
+    fn delete_plan_version_in_cook_and_run(
+        &mut self,
+        id: Uuid,
+        plan_version_id: Uuid,
+    ) -> Result<(), String>;
 }
 
 pub trait StorageR {
@@ -134,9 +197,30 @@ pub struct ContactData {
     pub members: u32,
     pub diets: Vec<String>,
     pub needs_check: bool,
+    #[serde(default)]
+    pub rejected: bool,
     pub notes: Vec<NoteData>,
 }
 
+impl ContactData {
+    // Only approved teams take part in the calculation
+    pub fn is_approved(&self) -> bool {
+        !self.needs_check && !self.rejected
+    }
+}
+
+#[derive(Default, Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ParticipantData {
+    pub id: Uuid,
+    pub name: String,
+    pub address: AddressData,
+    pub mail: String,
+    pub phone_number: String,
+    pub diets: Vec<String>,
+    pub has_kitchen: bool,
+    pub preferred_partner: Option<Uuid /*Participant ID */>,
+}
+
 #[derive(Default, Debug, Clone, Serialize, Deserialize)]
 pub struct AddressData {
     pub address: String,
@@ -204,6 +288,33 @@ pub struct PlanData {
     pub greatest_distance: f64,
 }
 
+#[derive(Default, Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum PlanVersionKind {
+    #[default]
+    BestFound,
+    ManuallyEdited,
+    AfterCancellation,
+}
+
+impl PlanVersionKind {
+    pub fn label(&self) -> &'static str {
+        match self {
+            PlanVersionKind::BestFound => "Best found",
+            PlanVersionKind::ManuallyEdited => "Manually edited",
+            PlanVersionKind::AfterCancellation => "After cancellation",
+        }
+    }
+}
+
+#[derive(Default, Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct PlanVersionData {
+    pub id: Uuid,
+    pub name: String,
+    pub kind: PlanVersionKind,
+    pub created: DateTime<Utc>,
+    pub plan: PlanData,
+}
+
 #[derive(Default, Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct CookAndRunData {
     pub id: Uuid,
@@ -214,13 +325,51 @@ pub struct CookAndRunData {
     pub is_in_cloud: bool,
     pub contact_list: Vec<ContactData>,
     pub course_list: Vec<CourseData>,
-    pub course_with_more_hosts: Option<Uuid>,
+    // Projects saved before several courses were allowed hold a single course or null
+    #[serde(
+        default,
+        alias = "course_with_more_hosts",
+        deserialize_with = "deserialize_course_with_more_hosts_list"
+    )]
+    pub course_with_more_hosts_list: Vec<Uuid>,
+    pub guests_per_hosting: Option<u32>,
     pub start_point: Option<MeetingPointData>,
     pub end_point: Option<MeetingPointData>,
     pub top_plan: Option<PlanData>,
     pub plan_text: Option<String>,
     pub invite_allowed: bool,
     pub invite_text: Option<String>,
+    #[serde(default)]
+    pub max_teams: Option<u32>,
+    #[serde(default)]
+    pub waitlist: Vec<ContactData>,
+    // Single participants who still have to be paired into teams
+    #[serde(default)]
+    pub participant_list: Vec<ParticipantData>,
+    #[serde(default)]
+    pub reveal_minutes: Option<u32>,
+    #[serde(default)]
+    pub plan_version_list: Vec<PlanVersionData>,
+    // The published version is also kept as top plan
+    #[serde(default)]
+    pub published_plan_version: Option<Uuid>,
+}
+
+fn deserialize_course_with_more_hosts_list<'de, D>(deserializer: D) -> Result<Vec<Uuid>, D::Error>
+where
+    D: serde::Deserializer<'de>,
+{
+    #[derive(Deserialize)]
+    #[serde(untagged)]
+    enum CourseWithMoreHosts {
+        List(Vec<Uuid>),
+        Single(Option<Uuid>),
+    }
+
+    Ok(match CourseWithMoreHosts::deserialize(deserializer)? {
+        CourseWithMoreHosts::List(course_list) => course_list,
+        CourseWithMoreHosts::Single(course) => course.into_iter().collect(),
+    })
 }
 
 impl CookAndRunData {
@@ -242,14 +391,155 @@ impl CookAndRunData {
             is_in_cloud: false,
             contact_list: vec![],
             course_list: vec![],
-            course_with_more_hosts: None,
+            course_with_more_hosts_list: vec![],
+            guests_per_hosting: None,
             start_point: None,
             end_point: None,
             top_plan: None,
             plan_text: None,
             invite_allowed: false,
             invite_text: None,
+            max_teams: None,
+            waitlist: vec![],
+            participant_list: vec![],
+            reveal_minutes: None,
+            plan_version_list: vec![],
+            published_plan_version: None,
+        }
+    }
+
+    pub fn is_full(&self) -> bool {
+        self.max_teams
+            .is_some_and(|max_teams| self.contact_list.len() >= max_teams as usize)
+    }
+
+    // Adds the team or puts it on the waitlist if the maximum number of teams is reached.
+    // Returns true if the team is waitlisted.
+    pub fn register_team(&mut self, team: ContactData) -> bool {
+        if self.is_full() {
+            self.waitlist.push(team);
+            true
+        } else {
+            self.contact_list.push(team);
+            false
+        }
+    }
+
+    // Removes a registered or waitlisted team, a freed place goes to the first waitlisted team
+    pub fn remove_team(&mut self, team_id: Uuid) -> Result<(), String> {
+        if let Some(index) = self.waitlist.iter().position(|x| x.id == team_id) {
+            self.waitlist.remove(index);
+            return Ok(());
+        }
+
+        let index = self
+            .contact_list
+            .iter()
+            .position(|x| x.id == team_id)
+            .ok_or(format!("Team with ID {} not found", team_id))?;
+        self.contact_list.remove(index);
+        while !self.is_full() && !self.waitlist.is_empty() {
+            let team = self.waitlist.remove(0);
+            self.contact_list.push(team);
+        }
+        Ok(())
+    }
+
+    pub fn remove_participant(&mut self, participant_id: Uuid) -> Result<(), String> {
+        let index = self
+            .participant_list
+            .iter()
+            .position(|x| x.id == participant_id)
+            .ok_or(format!("Participant with ID {} not found", participant_id))?;
+        self.participant_list.remove(index);
+        for participant in self.participant_list.iter_mut() {
+            if participant.preferred_partner == Some(participant_id) {
+                participant.preferred_partner = None;
+            }
+        }
+        Ok(())
+    }
+
+    // Pairs all single participants and registers the pairs as teams. Returns the number
+    // of new teams, participants are only removed if everybody could be paired.
+    pub fn pair_participants(&mut self) -> Result<usize, String> {
+        let team_list = pairing::pair_participants(&self.participant_list)?;
+        let number_of_teams = team_list.len();
+        for team in team_list {
+            self.register_team(team);
+        }
+        self.participant_list.clear();
+        Ok(number_of_teams)
+    }
+
+    // Time from which guests see the address of a course, None if addresses are shown right away
+    pub fn reveal_time(&self, course: &CourseData) -> Option<NaiveDateTime> {
+        self.reveal_minutes
+            .map(|minutes| self.occur.and_time(course.time) - Duration::minutes(minutes as i64))
+    }
+
+    // Keeps the plan as a new version named after its kind, e.g. "Manually edited 2"
+    pub fn add_plan_version(&mut self, kind: PlanVersionKind, plan: PlanData) -> PlanVersionData {
+        let number = self
+            .plan_version_list
+            .iter()
+            .filter(|v| v.kind == kind)
+            .count()
+            + 1;
+        let plan_version = PlanVersionData {
+            id: Uuid::new_v4(),
+            name: format!("{} {}", kind.label(), number),
+            kind,
+            created: Utc::now(),
+            plan,
+        };
+        self.plan_version_list.push(plan_version.clone());
+        plan_version
+    }
+
+    pub fn publish_plan_version(&mut self, plan_version_id: Uuid) -> Result<(), String> {
+        let plan_version = self
+            .plan_version_list
+            .iter()
+            .find(|v| v.id == plan_version_id)
+            .ok_or(format!(
+                "Plan version with ID {} not found",
+                plan_version_id
+            ))?;
+        self.top_plan = Some(plan_version.plan.clone());
+        self.published_plan_version = Some(plan_version_id);
+        Ok(())
+    }
+
+    pub fn delete_plan_version(&mut self, plan_version_id: Uuid) -> Result<(), String> {
+        if self.published_plan_version == Some(plan_version_id) {
+            return Err("The published plan version can't be deleted".to_string());
+        }
+        let index = self
+            .plan_version_list
+            .iter()
+            .position(|v| v.id == plan_version_id)
+            .ok_or(format!(
+                "Plan version with ID {} not found",
+                plan_version_id
+            ))?;
+        self.plan_version_list.remove(index);
+        Ok(())
+    }
+
+    pub fn reorder_waitlist(&mut self, team_id_list: &[Uuid]) -> Result<(), String> {
+        if team_id_list.len() != self.waitlist.len()
+            || self.waitlist.iter().any(|t| !team_id_list.contains(&t.id))
+        {
+            return Err("New order has to contain every waitlisted team once".to_string());
         }
+        self.waitlist.sort_by_key(|t| {
+            team_id_list
+                .iter()
+                .position(|id| *id == t.id)
+                .expect("Expected team in new order")
+        });
+        Ok(())
     }
 }
 
@@ -260,3 +550,192 @@ pub struct CookAndRunMinimalData {
     pub created: DateTime<Utc>,
     pub edited: DateTime<Utc>,
 }
+
+#[cfg(test)]
+mod tests {
+    use uuid::Uuid;
+
+    use chrono::{NaiveDate, NaiveTime};
+    use serde_json::json;
+
+    use crate::storage::{
+        AddressData, ContactData, CookAndRunData, CourseData, ParticipantData, PlanData,
+        PlanVersionKind,
+    };
+
+    fn get_team() -> ContactData {
+        ContactData {
+            id: Uuid::new_v4(),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_course_with_more_hosts_migration() {
+        let course_id = Uuid::new_v4();
+        let cook_and_run_data = CookAndRunData::new(Uuid::new_v4(), "Test".to_string());
+        let mut json = serde_json::to_value(&cook_and_run_data).unwrap();
+        let object = json.as_object_mut().unwrap();
+        object.remove("course_with_more_hosts_list");
+
+        object.insert("course_with_more_hosts".to_string(), json!(course_id));
+        let migrated: CookAndRunData = serde_json::from_value(json.clone()).unwrap();
+        assert_eq!(migrated.course_with_more_hosts_list, vec![course_id]);
+
+        let object = json.as_object_mut().unwrap();
+        object.insert("course_with_more_hosts".to_string(), json!(null));
+        let migrated: CookAndRunData = serde_json::from_value(json.clone()).unwrap();
+        assert!(migrated.course_with_more_hosts_list.is_empty());
+
+        let object = json.as_object_mut().unwrap();
+        object.remove("course_with_more_hosts");
+        let migrated: CookAndRunData = serde_json::from_value(json).unwrap();
+        assert!(migrated.course_with_more_hosts_list.is_empty());
+
+        let mut cook_and_run_data = cook_and_run_data;
+        cook_and_run_data.course_with_more_hosts_list = vec![course_id, Uuid::new_v4()];
+        let json = serde_json::to_string(&cook_and_run_data).unwrap();
+        assert_eq!(
+            serde_json::from_str::<CookAndRunData>(&json).unwrap(),
+            cook_and_run_data
+        );
+    }
+
+    #[test]
+    fn test_waitlist_promotion() {
+        let mut cook_and_run_data = CookAndRunData::new(Uuid::new_v4(), "Test".to_string());
+        cook_and_run_data.max_teams = Some(2);
+
+        let team_list: Vec<ContactData> = (0..4).map(|_| get_team()).collect();
+        let waitlisted: Vec<bool> = team_list
+            .iter()
+            .map(|t| cook_and_run_data.register_team(t.clone()))
+            .collect();
+        assert_eq!(waitlisted, vec![false, false, true, true]);
+
+        // Removing a waitlisted team doesn't promote anybody
+        cook_and_run_data.remove_team(team_list[3].id).unwrap();
+        assert_eq!(cook_and_run_data.contact_list.len(), 2);
+        assert_eq!(cook_and_run_data.waitlist.len(), 1);
+
+        cook_and_run_data.remove_team(team_list[0].id).unwrap();
+        assert_eq!(cook_and_run_data.waitlist.len(), 0);
+        assert!(cook_and_run_data
+            .contact_list
+            .iter()
+            .any(|t| t.id == team_list[2].id));
+
+        assert!(cook_and_run_data.remove_team(Uuid::new_v4()).is_err());
+    }
+
+    #[test]
+    fn test_pair_participants() {
+        let mut cook_and_run_data = CookAndRunData::new(Uuid::new_v4(), "Test".to_string());
+        cook_and_run_data.max_teams = Some(1);
+        let participant = |name: &str, latitude: f64, has_kitchen: bool| ParticipantData {
+            id: Uuid::new_v4(),
+            name: name.to_string(),
+            address: AddressData {
+                address: format!("{} street", name),
+                latitude,
+                longitude: 9.67,
+            },
+            has_kitchen,
+            ..Default::default()
+        };
+        cook_and_run_data.participant_list = vec![
+            participant("Anna", 50.50, true),
+            participant("Ben", 50.60, true),
+            participant("Clara", 50.61, false),
+        ];
+        cook_and_run_data.participant_list[0].preferred_partner =
+            Some(cook_and_run_data.participant_list[2].id);
+
+        // Nobody is paired while one participant is left over
+        assert!(cook_and_run_data.pair_participants().is_err());
+        assert_eq!(cook_and_run_data.participant_list.len(), 3);
+
+        let clara_id = cook_and_run_data.participant_list[2].id;
+        cook_and_run_data.remove_participant(clara_id).unwrap();
+        assert_eq!(
+            cook_and_run_data.participant_list[0].preferred_partner,
+            None
+        );
+        cook_and_run_data
+            .participant_list
+            .push(participant("David", 50.51, false));
+        cook_and_run_data
+            .participant_list
+            .push(participant("Eva", 50.52, false));
+
+        assert_eq!(cook_and_run_data.pair_participants(), Ok(2));
+        assert!(cook_and_run_data.participant_list.is_empty());
+        // Pairs beyond the maximum number of teams are waitlisted
+        assert_eq!(cook_and_run_data.contact_list.len(), 1);
+        assert_eq!(cook_and_run_data.waitlist.len(), 1);
+    }
+
+    #[test]
+    fn test_reorder_waitlist() {
+        let mut cook_and_run_data = CookAndRunData::new(Uuid::new_v4(), "Test".to_string());
+        cook_and_run_data.max_teams = Some(0);
+
+        let team_list: Vec<ContactData> = (0..3).map(|_| get_team()).collect();
+        for team in team_list.iter() {
+            cook_and_run_data.register_team(team.clone());
+        }
+
+        let new_order = vec![team_list[2].id, team_list[0].id, team_list[1].id];
+        cook_and_run_data.reorder_waitlist(&new_order).unwrap();
+        let order: Vec<Uuid> = cook_and_run_data.waitlist.iter().map(|t| t.id).collect();
+        assert_eq!(order, new_order);
+
+        assert!(cook_and_run_data
+            .reorder_waitlist(&[team_list[0].id, team_list[1].id])
+            .is_err());
+    }
+
+    #[test]
+    fn test_reveal_time() {
+        let mut cook_and_run_data = CookAndRunData::new(Uuid::new_v4(), "Test".to_string());
+        cook_and_run_data.occur = NaiveDate::from_ymd_opt(2025, 10, 18).unwrap();
+        let course = CourseData {
+            id: Uuid::new_v4(),
+            name: "Starter".to_string(),
+            time: NaiveTime::from_hms_opt(0, 15, 0).unwrap(),
+        };
+        assert_eq!(cook_and_run_data.reveal_time(&course), None);
+
+        cook_and_run_data.reveal_minutes = Some(30);
+        assert_eq!(
+            cook_and_run_data.reveal_time(&course),
+            NaiveDate::from_ymd_opt(2025, 10, 17)
+                .unwrap()
+                .and_hms_opt(23, 45, 0)
+        );
+    }
+
+    #[test]
+    fn test_plan_versions() {
+        let mut cook_and_run_data = CookAndRunData::new(Uuid::new_v4(), "Test".to_string());
+        let best =
+            cook_and_run_data.add_plan_version(PlanVersionKind::BestFound, PlanData::default());
+        let edited = cook_and_run_data
+            .add_plan_version(PlanVersionKind::ManuallyEdited, PlanData::default());
+        let second_best =
+            cook_and_run_data.add_plan_version(PlanVersionKind::BestFound, PlanData::default());
+        assert_eq!(best.name, "Best found 1");
+        assert_eq!(edited.name, "Manually edited 1");
+        assert_eq!(second_best.name, "Best found 2");
+        assert_eq!(cook_and_run_data.top_plan, None);
+
+        cook_and_run_data.publish_plan_version(edited.id).unwrap();
+        assert_eq!(cook_and_run_data.published_plan_version, Some(edited.id));
+        assert_eq!(cook_and_run_data.top_plan, Some(edited.plan.clone()));
+
+        assert!(cook_and_run_data.delete_plan_version(edited.id).is_err());
+        cook_and_run_data.delete_plan_version(best.id).unwrap();
+        assert_eq!(cook_and_run_data.plan_version_list.len(), 2);
+        assert!(cook_and_run_data.publish_plan_version(best.id).is_err());
+    }
+}
@@ -0,0 +1,317 @@
+use std::fmt;
+
+use uuid::Uuid;
+
+use crate::storage::{CookAndRunData, PlanData};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Rule {
+    UnknownCourse,
+    UnknownTeam,
+    // Every team hosts exactly once
+    HostCount,
+    // Every team is at exactly one table per course
+    CourseAttendance,
+    // Guests of a course are spread as evenly as possible over its hostings
+    GuestBalance,
+    // Walking path of a team lists its hostings in course order
+    WalkingPath,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Violation {
+    pub rule: Rule,
+    pub team_name: Option<String>,
+    pub course_name: Option<String>,
+    pub message: String,
+}
+
+impl fmt::Display for Violation {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.message)
+    }
+}
+
+#[derive(Default, Debug, Clone, PartialEq)]
+pub struct ValidationReport {
+    pub violation_list: Vec<Violation>,
+}
+
+impl ValidationReport {
+    pub fn is_valid(&self) -> bool {
+        self.violation_list.is_empty()
+    }
+
+    pub fn message_list(&self) -> Vec<String> {
+        self.violation_list.iter().map(|v| v.to_string()).collect()
+    }
+
+    fn push(
+        &mut self,
+        rule: Rule,
+        team_name: Option<&str>,
+        course_name: Option<&str>,
+        message: String,
+    ) {
+        self.violation_list.push(Violation {
+            rule,
+            team_name: team_name.map(str::to_string),
+            course_name: course_name.map(str::to_string),
+            message,
+        });
+    }
+}
+
+// Checks the structural rules of a plan against the approved teams and the courses of the
+// project, independent of the solver that created it
+pub fn validate_plan(cook_and_run: &CookAndRunData, plan: &PlanData) -> ValidationReport {
+    let mut course_list = cook_and_run.course_list.clone();
+    course_list.sort_by_key(|c| c.time);
+    let course_list: Vec<(Uuid, String)> =
+        course_list.into_iter().map(|c| (c.id, c.name)).collect();
+    let team_list: Vec<(Uuid, String)> = cook_and_run
+        .contact_list
+        .iter()
+        .filter(|c| c.is_approved())
+        .map(|c| (c.id, c.team_name.clone()))
+        .collect();
+    validate_hosting_list(&course_list, &team_list, plan)
+}
+
+// Course list has to be in course order
+pub(crate) fn validate_hosting_list(
+    course_list: &[(Uuid, String)],
+    team_list: &[(Uuid, String)],
+    plan: &PlanData,
+) -> ValidationReport {
+    let mut report = ValidationReport::default();
+    let team_name = |id: &Uuid| {
+        team_list
+            .iter()
+            .find(|(team_id, _)| team_id == id)
+            .map(|(_, name)| name.clone())
+    };
+    let course_name = |id: &Uuid| {
+        course_list
+            .iter()
+            .find(|(course_id, _)| course_id == id)
+            .map(|(_, name)| name.clone())
+    };
+
+    for hosting in plan.hosting_list.iter() {
+        let course = course_name(&hosting.name);
+        if course.is_none() {
+            let host = team_name(&hosting.host).unwrap_or(hosting.host.to_string());
+            report.push(
+                Rule::UnknownCourse,
+                Some(&host),
+                None,
+                format!("Hosting of {} belongs to an unknown course", host),
+            );
+        }
+        for id in std::iter::once(&hosting.host).chain(hosting.guest_list.iter()) {
+            if team_name(id).is_none() {
+                let course = course.clone().unwrap_or("an unknown course".to_string());
+                report.push(
+                    Rule::UnknownTeam,
+                    None,
+                    Some(&course),
+                    format!("Team {} at {} is not an approved team", id, course),
+                );
+            }
+        }
+    }
+
+    for (team_id, name) in team_list.iter() {
+        let number_of_hostings = plan
+            .hosting_list
+            .iter()
+            .filter(|h| h.host == *team_id)
+            .count();
+        if number_of_hostings != 1 {
+            report.push(
+                Rule::HostCount,
+                Some(name),
+                None,
+                format!("{} hosts {} times", name, number_of_hostings),
+            );
+        }
+
+        for (course_id, course) in course_list.iter() {
+            let number_of_visits = plan
+                .hosting_list
+                .iter()
+                .filter(|h| h.name == *course_id)
+                .filter(|h| h.host == *team_id || h.guest_list.contains(team_id))
+                .count();
+            if number_of_visits != 1 {
+                report.push(
+                    Rule::CourseAttendance,
+                    Some(name),
+                    Some(course),
+                    format!("{} is {} times at {}", name, number_of_visits, course),
+                );
+            }
+        }
+
+        let expected_path: Vec<Uuid> = course_list
+            .iter()
+            .flat_map(|(course_id, _)| {
+                plan.hosting_list
+                    .iter()
+                    .filter(|h| h.name == *course_id)
+                    .filter(|h| h.host == *team_id || h.guest_list.contains(team_id))
+                    .map(|h| h.id)
+            })
+            .collect();
+        if plan.walking_path.get(team_id) != Some(&expected_path) {
+            report.push(
+                Rule::WalkingPath,
+                Some(name),
+                None,
+                format!("Walking path of {} doesn't match its hostings", name),
+            );
+        }
+    }
+
+    for (course_id, course) in course_list.iter() {
+        let hosting_list: Vec<_> = plan
+            .hosting_list
+            .iter()
+            .filter(|h| h.name == *course_id)
+            .collect();
+        if hosting_list.is_empty() {
+            continue;
+        }
+        let number_of_guests: usize = hosting_list.iter().map(|h| h.guest_list.len()).sum();
+        let min_guests = number_of_guests / hosting_list.len();
+        let max_guests = number_of_guests.div_ceil(hosting_list.len());
+        for hosting in hosting_list {
+            let guest_count = hosting.guest_list.len();
+            if guest_count < min_guests || guest_count > max_guests {
+                let host = team_name(&hosting.host).unwrap_or(hosting.host.to_string());
+                let expected = if min_guests == max_guests {
+                    min_guests.to_string()
+                } else {
+                    format!("{} or {}", min_guests, max_guests)
+                };
+                report.push(
+                    Rule::GuestBalance,
+                    Some(&host),
+                    Some(course),
+                    format!(
+                        "{} has {} guests at {}, expected {}",
+                        host, guest_count, course, expected
+                    ),
+                );
+            }
+        }
+    }
+
+    report
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashMap;
+
+    use chrono::NaiveTime;
+    use uuid::Uuid;
+
+    use crate::{
+        storage::{ContactData, CookAndRunData, CourseData, HostingData, PlanData},
+        validation::{validate_plan, Rule},
+    };
+
+    fn get_plan(hosting_list: Vec<HostingData>) -> PlanData {
+        let mut walking_path: HashMap<Uuid, Vec<Uuid>> = HashMap::new();
+        for hosting in hosting_list.iter() {
+            for team in std::iter::once(&hosting.host).chain(hosting.guest_list.iter()) {
+                walking_path.entry(*team).or_default().push(hosting.id);
+            }
+        }
+        PlanData {
+            id: Uuid::new_v4(),
+            hosting_list,
+            walking_path,
+            greatest_distance: 0.0,
+        }
+    }
+
+    #[test]
+    fn test_validate_plan() {
+        let mut cook_and_run = CookAndRunData::new(Uuid::new_v4(), "Summer Run".to_string());
+        let team_list: Vec<ContactData> = (0..4)
+            .map(|i| ContactData {
+                id: Uuid::new_v4(),
+                team_name: format!("Team {}", i),
+                ..Default::default()
+            })
+            .collect();
+        let main = CourseData {
+            id: Uuid::new_v4(),
+            name: "Main".to_string(),
+            time: NaiveTime::from_hms_opt(20, 0, 0).unwrap(),
+        };
+        let starter = CourseData {
+            id: Uuid::new_v4(),
+            name: "Starter".to_string(),
+            time: NaiveTime::from_hms_opt(18, 0, 0).unwrap(),
+        };
+        cook_and_run.contact_list = team_list.clone();
+        // Course order comes from the time, not from the list
+        cook_and_run.course_list = vec![main.clone(), starter.clone()];
+
+        let hosting = |course: &CourseData, host: usize, guest_list: Vec<usize>| HostingData {
+            id: Uuid::new_v4(),
+            name: course.id,
+            host: team_list[host].id,
+            guest_list: guest_list.iter().map(|g| team_list[*g].id).collect(),
+        };
+        let plan = get_plan(vec![
+            hosting(&starter, 0, vec![1]),
+            hosting(&starter, 2, vec![3]),
+            hosting(&main, 1, vec![2]),
+            hosting(&main, 3, vec![0]),
+        ]);
+        let report = validate_plan(&cook_and_run, &plan);
+        assert!(report.is_valid(), "{:?}", report.message_list());
+
+        // Team 0 hosts twice and sits at the table of team 3 as well
+        let broken_plan = get_plan(vec![
+            hosting(&starter, 0, vec![1]),
+            hosting(&starter, 2, vec![3]),
+            hosting(&main, 0, vec![]),
+            hosting(&main, 3, vec![1, 0, 2]),
+        ]);
+        let report = validate_plan(&cook_and_run, &broken_plan);
+        let rule_list: Vec<Rule> = report.violation_list.iter().map(|v| v.rule).collect();
+        assert!(rule_list.contains(&Rule::HostCount));
+        assert!(rule_list.contains(&Rule::CourseAttendance));
+        assert!(rule_list.contains(&Rule::GuestBalance));
+        assert!(report
+            .message_list()
+            .contains(&"Team 0 is 2 times at Main".to_string()));
+        assert!(report
+            .message_list()
+            .contains(&"Team 3 has 3 guests at Main, expected 1 or 2".to_string()));
+
+        // Walking paths out of course order and teams that are no longer approved
+        let mut reversed_plan = plan.clone();
+        reversed_plan
+            .walking_path
+            .values_mut()
+            .for_each(|path| path.reverse());
+        cook_and_run.contact_list[3].rejected = true;
+        let report = validate_plan(&cook_and_run, &reversed_plan);
+        let rule_list: Vec<Rule> = report.violation_list.iter().map(|v| v.rule).collect();
+        assert_eq!(
+            rule_list
+                .iter()
+                .filter(|r| **r == Rule::WalkingPath)
+                .count(),
+            3
+        );
+        assert!(rule_list.contains(&Rule::UnknownTeam));
+    }
+}
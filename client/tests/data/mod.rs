@@ -17,6 +17,7 @@ pub fn real_live_data() -> Vec<ContactData> {
             members: 2,
             diets: vec![],
             needs_check: false,
+            rejected: false,
             notes: vec![],
         },
         ContactData {
@@ -32,6 +33,7 @@ pub fn real_live_data() -> Vec<ContactData> {
             members: 2,
             diets: vec!["vegetarisch".into()],
             needs_check: false,
+            rejected: false,
             notes: vec![],
         },
         ContactData {
@@ -47,6 +49,7 @@ pub fn real_live_data() -> Vec<ContactData> {
             members: 2,
             diets: vec![],
             needs_check: false,
+            rejected: false,
             notes: vec![],
         },
         ContactData {
@@ -62,6 +65,7 @@ pub fn real_live_data() -> Vec<ContactData> {
             members: 2,
             diets: vec![],
             needs_check: false,
+            rejected: false,
             notes: vec![],
         },
         ContactData {
@@ -77,6 +81,7 @@ pub fn real_live_data() -> Vec<ContactData> {
             members: 2,
             diets: vec!["vegetarisch".into()],
             needs_check: false,
+            rejected: false,
             notes: vec![],
         },
         ContactData {
@@ -92,6 +97,7 @@ pub fn real_live_data() -> Vec<ContactData> {
             members: 2,
             diets: vec![],
             needs_check: false,
+            rejected: false,
             notes: vec![],
         },
         ContactData {
@@ -107,6 +113,7 @@ pub fn real_live_data() -> Vec<ContactData> {
             members: 2,
             diets: vec!["vegetarisch".into()],
             needs_check: false,
+            rejected: false,
             notes: vec![],
         },
         ContactData {
@@ -122,6 +129,7 @@ pub fn real_live_data() -> Vec<ContactData> {
             members: 2,
             diets: vec!["vegetarisch".into()],
             needs_check: false,
+            rejected: false,
             notes: vec![],
         },
         ContactData {
@@ -137,6 +145,7 @@ pub fn real_live_data() -> Vec<ContactData> {
             members: 2,
             diets: vec![],
             needs_check: false,
+            rejected: false,
             notes: vec![],
         },
         ContactData {
@@ -152,6 +161,7 @@ pub fn real_live_data() -> Vec<ContactData> {
             members: 2,
             diets: vec!["vegetarisch".into()],
             needs_check: false,
+            rejected: false,
             notes: vec![],
         },
         ContactData {
@@ -167,6 +177,7 @@ pub fn real_live_data() -> Vec<ContactData> {
             members: 2,
             diets: vec!["vegan".into()],
             needs_check: false,
+            rejected: false,
             notes: vec![],
         },
         ContactData {
@@ -182,6 +193,7 @@ pub fn real_live_data() -> Vec<ContactData> {
             members: 2,
             diets: vec!["vegetarisch".into()],
             needs_check: false,
+            rejected: false,
             notes: vec![],
         },
         ContactData {
@@ -197,6 +209,7 @@ pub fn real_live_data() -> Vec<ContactData> {
             members: 2,
             diets: vec![],
             needs_check: false,
+            rejected: false,
             notes: vec![],
         },
         ContactData {
@@ -212,6 +225,7 @@ pub fn real_live_data() -> Vec<ContactData> {
             members: 2,
             diets: vec!["vegetarisch".into()],
             needs_check: false,
+            rejected: false,
             notes: vec![],
         },
         ContactData {
@@ -227,6 +241,7 @@ pub fn real_live_data() -> Vec<ContactData> {
             members: 2,
             diets: vec!["vegetarisch".into()],
             needs_check: false,
+            rejected: false,
             notes: vec![],
         },
         ContactData {
@@ -242,6 +257,7 @@ pub fn real_live_data() -> Vec<ContactData> {
             members: 2,
             diets: vec![],
             needs_check: false,
+            rejected: false,
             notes: vec![],
         },
         ContactData {
@@ -257,6 +273,7 @@ pub fn real_live_data() -> Vec<ContactData> {
             members: 2,
             diets: vec!["vegetarisch".into()],
             needs_check: false,
+            rejected: false,
             notes: vec![],
         },
         ContactData {
@@ -272,6 +289,7 @@ pub fn real_live_data() -> Vec<ContactData> {
             members: 2,
             diets: vec![],
             needs_check: false,
+            rejected: false,
             notes: vec![],
         },
         ContactData {
@@ -287,6 +305,7 @@ pub fn real_live_data() -> Vec<ContactData> {
             members: 2,
             diets: vec!["vegetarisch".into()],
             needs_check: false,
+            rejected: false,
             notes: vec![],
         },
         ContactData {
@@ -302,6 +321,7 @@ pub fn real_live_data() -> Vec<ContactData> {
             members: 2,
             diets: vec!["vegetarisch".into()],
             needs_check: false,
+            rejected: false,
             notes: vec![],
         },
         ContactData {
@@ -317,6 +337,7 @@ pub fn real_live_data() -> Vec<ContactData> {
             members: 2,
             diets: vec![],
             needs_check: false,
+            rejected: false,
             notes: vec![],
         },
         ContactData {
@@ -332,6 +353,7 @@ pub fn real_live_data() -> Vec<ContactData> {
             members: 2,
             diets: vec![],
             needs_check: false,
+            rejected: false,
             notes: vec![],
         },
         ContactData {
@@ -347,6 +369,7 @@ pub fn real_live_data() -> Vec<ContactData> {
             members: 2,
             diets: vec![],
             needs_check: false,
+            rejected: false,
             notes: vec![],
         },
         ContactData {
@@ -362,6 +385,7 @@ pub fn real_live_data() -> Vec<ContactData> {
             members: 2,
             diets: vec![],
             needs_check: false,
+            rejected: false,
             notes: vec![],
         },
         ContactData {
@@ -377,6 +401,7 @@ pub fn real_live_data() -> Vec<ContactData> {
             members: 2,
             diets: vec!["vegan".into()],
             needs_check: false,
+            rejected: false,
             notes: vec![],
         },
         ContactData {
@@ -392,6 +417,7 @@ pub fn real_live_data() -> Vec<ContactData> {
             members: 2,
             diets: vec!["vegetarisch".into()],
             needs_check: false,
+            rejected: false,
             notes: vec![],
         },
         ContactData {
@@ -407,6 +433,7 @@ pub fn real_live_data() -> Vec<ContactData> {
             members: 2,
             diets: vec!["vegetarisch".into()],
             needs_check: false,
+            rejected: false,
             notes: vec![],
         },
         ContactData {
@@ -422,6 +449,7 @@ pub fn real_live_data() -> Vec<ContactData> {
             members: 2,
             diets: vec!["vegetarisch".into()],
             needs_check: false,
+            rejected: false,
             notes: vec![],
         },
         ContactData {
@@ -437,6 +465,7 @@ pub fn real_live_data() -> Vec<ContactData> {
             members: 2,
             diets: vec!["vegetarisch".into()],
             needs_check: false,
+            rejected: false,
             notes: vec![],
         },
         ContactData {
@@ -452,6 +481,7 @@ pub fn real_live_data() -> Vec<ContactData> {
             members: 2,
             diets: vec!["vegetarisch".into()],
             needs_check: false,
+            rejected: false,
             notes: vec![],
         },
         ContactData {
@@ -467,6 +497,7 @@ pub fn real_live_data() -> Vec<ContactData> {
             members: 2,
             diets: vec!["vegetarisch".into()],
             needs_check: false,
+            rejected: false,
             notes: vec![],
         },
         ContactData {
@@ -482,6 +513,7 @@ pub fn real_live_data() -> Vec<ContactData> {
             members: 2,
             diets: vec!["vegan".into()],
             needs_check: false,
+            rejected: false,
             notes: vec![],
         },
         ContactData {
@@ -497,6 +529,7 @@ pub fn real_live_data() -> Vec<ContactData> {
             members: 2,
             diets: vec![],
             needs_check: false,
+            rejected: false,
             notes: vec![],
         },
         ContactData {
@@ -512,6 +545,7 @@ pub fn real_live_data() -> Vec<ContactData> {
             members: 2,
             diets: vec![],
             needs_check: false,
+            rejected: false,
             notes: vec![],
         },
         ContactData {
@@ -527,6 +561,7 @@ pub fn real_live_data() -> Vec<ContactData> {
             members: 2,
             diets: vec!["vegetarisch".into()],
             needs_check: false,
+            rejected: false,
             notes: vec![],
         },
     ]
@@ -548,6 +583,7 @@ pub fn get_contact_list(number_of_contacts: usize) -> Vec<ContactData> {
             members: 2,
             diets: vec![],
             needs_check: false,
+            rejected: false,
             notes: vec![],
         },
         ContactData {
@@ -563,6 +599,7 @@ pub fn get_contact_list(number_of_contacts: usize) -> Vec<ContactData> {
             members: 2,
             diets: vec![],
             needs_check: false,
+            rejected: false,
             notes: vec![],
         },
         ContactData {
@@ -578,6 +615,7 @@ pub fn get_contact_list(number_of_contacts: usize) -> Vec<ContactData> {
             members: 2,
             diets: vec![],
             needs_check: false,
+            rejected: false,
             notes: vec![],
         },
         ContactData {
@@ -593,6 +631,7 @@ pub fn get_contact_list(number_of_contacts: usize) -> Vec<ContactData> {
             members: 2,
             diets: vec![],
             needs_check: false,
+            rejected: false,
             notes: vec![],
         },
         ContactData {
@@ -608,6 +647,7 @@ pub fn get_contact_list(number_of_contacts: usize) -> Vec<ContactData> {
             members: 2,
             diets: vec![],
             needs_check: false,
+            rejected: false,
             notes: vec![],
         },
         ContactData {
@@ -623,6 +663,7 @@ pub fn get_contact_list(number_of_contacts: usize) -> Vec<ContactData> {
             members: 2,
             diets: vec![],
             needs_check: false,
+            rejected: false,
             notes: vec![],
         },
         ContactData {
@@ -638,6 +679,7 @@ pub fn get_contact_list(number_of_contacts: usize) -> Vec<ContactData> {
             members: 2,
             diets: vec![],
             needs_check: false,
+            rejected: false,
             notes: vec![],
         },
         ContactData {
@@ -653,6 +695,7 @@ pub fn get_contact_list(number_of_contacts: usize) -> Vec<ContactData> {
             members: 2,
             diets: vec![],
             needs_check: false,
+            rejected: false,
             notes: vec![],
         },
         ContactData {
@@ -668,6 +711,7 @@ pub fn get_contact_list(number_of_contacts: usize) -> Vec<ContactData> {
             members: 2,
             diets: vec![],
             needs_check: false,
+            rejected: false,
             notes: vec![],
         },
         ContactData {
@@ -683,6 +727,7 @@ pub fn get_contact_list(number_of_contacts: usize) -> Vec<ContactData> {
             members: 2,
             diets: vec![],
             needs_check: false,
+            rejected: false,
             notes: vec![],
         },
         ContactData {
@@ -698,6 +743,7 @@ pub fn get_contact_list(number_of_contacts: usize) -> Vec<ContactData> {
             members: 2,
             diets: vec![],
             needs_check: false,
+            rejected: false,
             notes: vec![],
         },
         ContactData {
@@ -713,6 +759,7 @@ pub fn get_contact_list(number_of_contacts: usize) -> Vec<ContactData> {
             members: 2,
             diets: vec![],
             needs_check: false,
+            rejected: false,
             notes: vec![],
         },
         ContactData {
@@ -728,6 +775,7 @@ pub fn get_contact_list(number_of_contacts: usize) -> Vec<ContactData> {
             members: 2,
             diets: vec![],
             needs_check: false,
+            rejected: false,
             notes: vec![],
         },
         ContactData {
@@ -743,6 +791,7 @@ pub fn get_contact_list(number_of_contacts: usize) -> Vec<ContactData> {
             members: 2,
             diets: vec![],
             needs_check: false,
+            rejected: false,
             notes: vec![],
         },
         ContactData {
@@ -758,6 +807,7 @@ pub fn get_contact_list(number_of_contacts: usize) -> Vec<ContactData> {
             members: 2,
             diets: vec![],
             needs_check: false,
+            rejected: false,
             notes: vec![],
         },
         ContactData {
@@ -773,6 +823,7 @@ pub fn get_contact_list(number_of_contacts: usize) -> Vec<ContactData> {
             members: 2,
             diets: vec![],
             needs_check: false,
+            rejected: false,
             notes: vec![],
         },
         ContactData {
@@ -788,6 +839,7 @@ pub fn get_contact_list(number_of_contacts: usize) -> Vec<ContactData> {
             members: 2,
             diets: vec![],
             needs_check: false,
+            rejected: false,
             notes: vec![],
         },
         ContactData {
@@ -803,6 +855,7 @@ pub fn get_contact_list(number_of_contacts: usize) -> Vec<ContactData> {
             members: 2,
             diets: vec![],
             needs_check: false,
+            rejected: false,
             notes: vec![],
         },
     ];
@@ -867,12 +920,19 @@ pub fn get_cook_and_run(
         contact_list: contact_list.clone(),
         course_list: course_list.clone(),
         invite_allowed: false,
-        course_with_more_hosts: course_with_more_hosts,
+        course_with_more_hosts_list: course_with_more_hosts.into_iter().collect(),
+        guests_per_hosting: None,
         is_in_cloud: false,
         plan_text: None,
         invite_text: None,
         start_point: start_point,
         end_point: end_point,
         top_plan: None,
+        max_teams: None,
+        waitlist: vec![],
+        participant_list: vec![],
+        reveal_minutes: None,
+        plan_version_list: vec![],
+        published_plan_version: None,
     }
 }
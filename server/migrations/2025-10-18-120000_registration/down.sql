@@ -0,0 +1,3 @@
+DROP INDEX IF EXISTS idx_team_management_token;
+
+ALTER TABLE "team" DROP COLUMN IF EXISTS "management_token";
@@ -0,0 +1,6 @@
+-- ========================================
+-- Team Registration
+-- ========================================
+ALTER TABLE "team" ADD COLUMN "management_token" TEXT NULL;
+
+CREATE UNIQUE INDEX idx_team_management_token ON "team" ("management_token");
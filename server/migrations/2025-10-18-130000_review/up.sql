@@ -0,0 +1,4 @@
+-- ========================================
+-- Team Review
+-- ========================================
+ALTER TABLE "team" ADD COLUMN "rejected" BOOLEAN NOT NULL DEFAULT FALSE;
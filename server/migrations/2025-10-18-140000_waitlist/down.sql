@@ -0,0 +1,3 @@
+DROP INDEX IF EXISTS idx_team_waitlist_position;
+
+ALTER TABLE "team" DROP COLUMN IF EXISTS "waitlist_position";
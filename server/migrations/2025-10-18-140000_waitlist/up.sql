@@ -0,0 +1,6 @@
+-- ========================================
+-- Team Waitlist
+-- ========================================
+ALTER TABLE "team" ADD COLUMN "waitlist_position" INTEGER NULL;
+
+CREATE INDEX idx_team_waitlist_position ON "team" ("cook_and_run_id", "waitlist_position");
@@ -0,0 +1,4 @@
+DROP INDEX IF EXISTS idx_mail_log_team_id;
+
+DROP TABLE IF EXISTS "mail_log";
+DROP TABLE IF EXISTS "mail_template";
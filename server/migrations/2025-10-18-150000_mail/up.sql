@@ -0,0 +1,29 @@
+-- ========================================
+-- Mail Template
+-- ========================================
+CREATE TABLE "mail_template" (
+    "id" UUID PRIMARY KEY,
+    "cook_and_run_id" UUID NOT NULL,
+    "kind" TEXT NOT NULL,
+    "subject" TEXT NOT NULL,
+    "body" TEXT NOT NULL,
+    FOREIGN KEY ("cook_and_run_id") REFERENCES "cook_and_run"("id") ON DELETE CASCADE,
+    UNIQUE ("cook_and_run_id", "kind")
+);
+
+-- ========================================
+-- Mail Log
+-- ========================================
+CREATE TABLE "mail_log" (
+    "id" UUID PRIMARY KEY,
+    "team_id" UUID NOT NULL,
+    "kind" TEXT NOT NULL,
+    "recipient" TEXT NOT NULL,
+    "subject" TEXT NOT NULL,
+    "sent" TIMESTAMPTZ NOT NULL,
+    "success" BOOLEAN NOT NULL,
+    "error" TEXT NULL,
+    FOREIGN KEY ("team_id") REFERENCES "team"("id") ON DELETE CASCADE
+);
+
+CREATE INDEX idx_mail_log_team_id ON "mail_log" ("team_id");
@@ -0,0 +1 @@
+ALTER TABLE "plan" DROP COLUMN IF EXISTS "reveal_minutes";
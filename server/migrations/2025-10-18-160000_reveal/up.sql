@@ -0,0 +1,4 @@
+-- ========================================
+-- Staged Address Reveal
+-- ========================================
+ALTER TABLE "plan" ADD COLUMN "reveal_minutes" INTEGER NULL;
@@ -0,0 +1,3 @@
+DROP INDEX IF EXISTS idx_schedule_token_team_id;
+
+DROP TABLE IF EXISTS "schedule_token";
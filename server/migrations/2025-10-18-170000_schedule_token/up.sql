@@ -0,0 +1,12 @@
+-- ========================================
+-- Schedule Token
+-- ========================================
+CREATE TABLE "schedule_token" (
+    "token" TEXT PRIMARY KEY,
+    "team_id" UUID NOT NULL,
+    "created" TIMESTAMPTZ NOT NULL,
+    "revoked" TIMESTAMPTZ NULL,
+    FOREIGN KEY ("team_id") REFERENCES "team"("id") ON DELETE CASCADE
+);
+
+CREATE INDEX idx_schedule_token_team_id ON "schedule_token" ("team_id");
@@ -0,0 +1,86 @@
+use axum::{
+    extract::{FromRequestParts, OptionalFromRequestParts},
+    http::{header, request::Parts},
+};
+use jsonwebtoken::{decode, Algorithm, DecodingKey, Validation};
+use serde::Deserialize;
+use tracing::{event, Level};
+
+use crate::error::RestError;
+
+const ISSUER: &str = "api.travelingcook.com";
+
+#[derive(Debug, Deserialize)]
+struct Claims {
+    sub: String,
+}
+
+// User of a verified bearer token. Handlers that need a login take `AuthUser`, handlers
+// where the login is optional take `Option<AuthUser>`. An invalid token is rejected in
+// both cases.
+#[derive(Debug, Clone, PartialEq)]
+pub struct AuthUser {
+    pub user_id: String,
+}
+
+fn get_bearer_token(parts: &Parts) -> Result<Option<&str>, RestError> {
+    let Some(value) = parts.headers.get(header::AUTHORIZATION) else {
+        return Ok(None);
+    };
+    value
+        .to_str()
+        .ok()
+        .and_then(|value| value.strip_prefix("Bearer "))
+        .map(|token| Some(token.trim()))
+        .ok_or_else(|| RestError::unauthorized_error("Authorization header is not a bearer token!"))
+}
+
+fn verify_token(token: &str) -> Result<AuthUser, RestError> {
+    let secret = std::env::var("JWT_SECRET").map_err(|_| {
+        event!(Level::ERROR, "JWT_SECRET is not set, tokens can't be verified");
+        RestError::internal_server_error("Tokens can't be verified!")
+    })?;
+
+    let mut validation = Validation::new(Algorithm::HS256);
+    validation.set_issuer(&[ISSUER]);
+    validation.set_required_spec_claims(&["exp", "iss", "sub"]);
+
+    let claims = decode::<Claims>(
+        token,
+        &DecodingKey::from_secret(secret.as_bytes()),
+        &validation,
+    )
+    .map_err(|e| {
+        event!(Level::INFO, "Rejected bearer token: {}", e);
+        RestError::unauthorized_error("Token is invalid or expired!")
+    })?
+    .claims;
+
+    if claims.sub.trim().is_empty() {
+        return Err(RestError::unauthorized_error("Token has no user!"));
+    }
+    Ok(AuthUser {
+        user_id: claims.sub,
+    })
+}
+
+impl<S: Send + Sync> FromRequestParts<S> for AuthUser {
+    type Rejection = RestError;
+
+    async fn from_request_parts(parts: &mut Parts, _state: &S) -> Result<Self, Self::Rejection> {
+        let token = get_bearer_token(parts)?
+            .ok_or_else(|| RestError::unauthorized_error("A bearer token is required!"))?;
+        verify_token(token)
+    }
+}
+
+impl<S: Send + Sync> OptionalFromRequestParts<S> for AuthUser {
+    type Rejection = RestError;
+
+    async fn from_request_parts(
+        parts: &mut Parts,
+        _state: &S,
+    ) -> Result<Option<Self>, Self::Rejection> {
+        get_bearer_token(parts)?.map(verify_token).transpose()
+    }
+}
@@ -0,0 +1,187 @@
+use chrono::{Duration, NaiveDateTime, Utc};
+
+use crate::{
+    cook_and_run::CookAndRun, error::RestError, schedule_access::TeamSchedule,
+    team_plan::course_start,
+};
+
+// Length of the last course, courses before last until the next one starts
+const DEFAULT_COURSE_MINUTES: i64 = 120;
+
+struct Event {
+    uid: String,
+    start: NaiveDateTime,
+    end: NaiveDateTime,
+    summary: String,
+    location: String,
+    description: String,
+}
+
+fn escape(text: &str) -> String {
+    text.replace('\\', "\\\\")
+        .replace(';', "\\;")
+        .replace(',', "\\,")
+        .replace("\r\n", "\\n")
+        .replace('\n', "\\n")
+}
+
+// Lines longer than 75 bytes are continued on the next line starting with a space
+fn fold(line: &str) -> String {
+    let mut folded = String::new();
+    let mut length = 0;
+    for c in line.chars() {
+        if length + c.len_utf8() > 75 {
+            folded.push_str("\r\n ");
+            length = 1;
+        }
+        folded.push(c);
+        length += c.len_utf8();
+    }
+    folded
+}
+
+fn format_time(time: &NaiveDateTime) -> String {
+    time.format("%Y%m%dT%H%M%S").to_string()
+}
+
+fn to_ics(name: &str, event_list: &[Event]) -> String {
+    let stamp = Utc::now().format("%Y%m%dT%H%M%SZ").to_string();
+    let mut line_list = vec![
+        "BEGIN:VCALENDAR".to_string(),
+        "VERSION:2.0".to_string(),
+        "PRODID:-//tcc//Cook and Run//EN".to_string(),
+        "CALSCALE:GREGORIAN".to_string(),
+        format!("X-WR-CALNAME:{}", escape(name)),
+        // Subscribed calendars pick up revealed addresses and plan changes
+        "REFRESH-INTERVAL;VALUE=DURATION:PT15M".to_string(),
+        "X-PUBLISHED-TTL:PT15M".to_string(),
+    ];
+    for event in event_list {
+        line_list.push("BEGIN:VEVENT".to_string());
+        line_list.push(format!("UID:{}", event.uid));
+        line_list.push(format!("DTSTAMP:{}", stamp));
+        line_list.push(format!("DTSTART:{}", format_time(&event.start)));
+        line_list.push(format!("DTEND:{}", format_time(&event.end)));
+        line_list.push(format!("SUMMARY:{}", escape(&event.summary)));
+        line_list.push(format!("LOCATION:{}", escape(&event.location)));
+        line_list.push(format!("DESCRIPTION:{}", escape(&event.description)));
+        line_list.push("END:VEVENT".to_string());
+    }
+    line_list.push("END:VCALENDAR".to_string());
+
+    line_list
+        .iter()
+        .map(|l| fold(l))
+        .collect::<Vec<String>>()
+        .join("\r\n")
+        + "\r\n"
+}
+
+// Sorts the events and lets every course last until the next one starts
+fn set_end_times(event_list: &mut [Event]) {
+    event_list.sort_by_key(|e| e.start);
+    let start_list: Vec<NaiveDateTime> = event_list.iter().map(|e| e.start).collect();
+    for event in event_list.iter_mut() {
+        event.end = start_list
+            .iter()
+            .find(|s| **s > event.start)
+            .cloned()
+            .unwrap_or(event.start + Duration::minutes(DEFAULT_COURSE_MINUTES));
+    }
+}
+
+pub fn team_calendar(schedule: &TeamSchedule) -> String {
+    let mut event_list = Vec::new();
+    for step in schedule.team_plan.step_list.iter() {
+        let Some(start) = course_start(&schedule.occur, &step.course_time) else {
+            continue;
+        };
+        let event = match &step.host {
+            Some(host) => Event {
+                uid: format!("{}-{}@tcc", step.course_id, schedule.team.id),
+                start,
+                end: start,
+                summary: format!("{} at {}", step.course_name, host.name),
+                location: host.address.address.clone(),
+                description: format!(
+                    "Course: {}\nHost: {}\nGuests: {}",
+                    step.course_name,
+                    host.name,
+                    step.guest_name_list.join(", ")
+                ),
+            },
+            None => Event {
+                uid: format!("{}-{}@tcc", step.course_id, schedule.team.id),
+                start,
+                end: start,
+                summary: step.course_name.clone(),
+                location: "".to_string(),
+                description: match step.reveal_time {
+                    Some(reveal_time) => format!(
+                        "Course: {}\nThe address is revealed at {}",
+                        step.course_name,
+                        reveal_time.format("%H:%M")
+                    ),
+                    None => format!("Course: {}", step.course_name),
+                },
+            },
+        };
+        event_list.push(event);
+    }
+    set_end_times(&mut event_list);
+
+    to_ics(
+        &format!("{} - {}", schedule.cook_and_run_name, schedule.team.name),
+        &event_list,
+    )
+}
+
+pub fn project_calendar(cook_and_run: &CookAndRun) -> Result<String, RestError> {
+    let plan = cook_and_run
+        .plan
+        .as_ref()
+        .ok_or_else(|| RestError::not_found_error("No plan has been calculated yet!"))?;
+
+    let mut event_list = Vec::new();
+    for hosting in plan.hosting_assignments.iter() {
+        let Some(course) = cook_and_run
+            .course_list
+            .iter()
+            .find(|c| c.id == hosting.course_id)
+        else {
+            continue;
+        };
+        let Some(host) = cook_and_run
+            .team_list
+            .iter()
+            .find(|t| t.id == hosting.team_id)
+        else {
+            continue;
+        };
+        let Some(start) = course_start(&cook_and_run.occur, &course.time) else {
+            continue;
+        };
+        let guest_name_list: Vec<String> = hosting
+            .guest_team_ids
+            .iter()
+            .filter_map(|id| cook_and_run.team_list.iter().find(|t| &t.id == id))
+            .map(|t| t.name.clone())
+            .collect();
+        event_list.push(Event {
+            uid: format!("{}@tcc", hosting.id),
+            start,
+            end: start,
+            summary: format!("{} at {}", course.name, host.name),
+            location: host.address.address.clone(),
+            description: format!(
+                "Course: {}\nHost: {}\nGuests: {}",
+                course.name,
+                host.name,
+                guest_name_list.join(", ")
+            ),
+        });
+    }
+    set_end_times(&mut event_list);
+
+    Ok(to_ics(&cook_and_run.name, &event_list))
+}
@@ -5,6 +5,7 @@ use uuid::Uuid;
 
 use crate::{
     address::{self, Address},
+    auth::AuthUser,
     course::{self, Course},
     db::{self, Database},
     error::RestError,
@@ -122,6 +123,40 @@ pub fn get_list_of_cook_and_run_meta(
         })
 }
 
+// Only the organizer who created a cook and run project may manage it
+pub fn check_owner(
+    db: &mut Database,
+    cook_and_run_id: &Uuid,
+    user: &AuthUser,
+) -> Result<(), RestError> {
+    let cook_and_run = db.select_cook_and_run(cook_and_run_id).map_err(|e| match e {
+        diesel::result::Error::NotFound => {
+            RestError::not_found_error("Cook and run project not found!")
+        }
+        e => {
+            event!(
+                Level::ERROR,
+                "Could not get cook and run project with id {} from database: {}",
+                cook_and_run_id,
+                e
+            );
+            RestError::internal_server_error("Could not get cook and run project from database")
+        }
+    })?;
+    if cook_and_run.user_id != user.user_id {
+        event!(
+            Level::INFO,
+            "User {} tried to access cook and run project {} of another user",
+            user.user_id,
+            cook_and_run_id
+        );
+        return Err(RestError::forbidden_error(
+            "You are not the organizer of this cook and run project!",
+        ));
+    }
+    Ok(())
+}
+
 pub fn get_cook_and_run(
     db: &mut Database,
     cook_and_run_id: &Uuid,
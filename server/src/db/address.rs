@@ -1,9 +1,30 @@
-use diesel::{QueryDsl, RunQueryDsl, SelectableHelper};
+use diesel::dsl::{insert_into, update};
+use diesel::{ExpressionMethods, QueryDsl, RunQueryDsl, SelectableHelper};
 use uuid::Uuid;
 
 use crate::db::{models::Address, Database};
 
 impl Database {
+    pub fn create_address(&mut self, data: &Address) -> Result<(), diesel::result::Error> {
+        let conn = &mut self.get_connection()?;
+        use crate::db::schema::address::dsl::*;
+        insert_into(address).values(data).execute(conn)?;
+        Ok(())
+    }
+
+    pub fn update_address(&mut self, data: &Address) -> Result<(), diesel::result::Error> {
+        let conn = &mut self.get_connection()?;
+        use crate::db::schema::address::dsl::*;
+        update(address.find(data.id))
+            .set((
+                address_text.eq(&data.address_text),
+                latitude.eq(data.latitude),
+                longitude.eq(data.longitude),
+            ))
+            .execute(conn)?;
+        Ok(())
+    }
+
     pub fn select_address(&mut self, id_filter: &Uuid) -> Result<Address, diesel::result::Error> {
         let conn = &mut self.get_connection()?;
         use crate::db::schema::address::dsl::*;
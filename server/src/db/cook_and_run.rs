@@ -1,4 +1,4 @@
-use diesel::dsl::{delete, insert_into};
+use diesel::dsl::{delete, insert_into, update};
 use diesel::{ExpressionMethods, QueryDsl, RunQueryDsl, SelectableHelper};
 use uuid::Uuid;
 
@@ -39,6 +39,31 @@ impl Database {
             .first(conn)
     }
 
+    pub fn select_cook_and_run_by_share(
+        &mut self,
+        share_id_filter: &Uuid,
+    ) -> Result<CookAndRun, diesel::result::Error> {
+        let conn = &mut self.get_connection()?;
+        use crate::db::schema::cook_and_run::dsl::*;
+        cook_and_run
+            .filter(share_team_config.eq(share_id_filter))
+            .select(CookAndRun::as_select())
+            .first(conn)
+    }
+
+    pub fn update_cook_and_run_plan(
+        &mut self,
+        id_filter: &Uuid,
+        plan_id: Option<Uuid>,
+    ) -> Result<(), diesel::result::Error> {
+        let conn = &mut self.get_connection()?;
+        use crate::db::schema::cook_and_run::dsl::*;
+        update(cook_and_run.find(id_filter))
+            .set(plan.eq(plan_id))
+            .execute(conn)?;
+        Ok(())
+    }
+
     pub fn delete_cook_and_run(&mut self, id_filter: &Uuid) -> Result<(), diesel::result::Error> {
         let conn = &mut self.get_connection()?;
         use crate::db::schema::cook_and_run::dsl::*;
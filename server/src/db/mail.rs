@@ -0,0 +1,59 @@
+use diesel::dsl::insert_into;
+use diesel::upsert::excluded;
+use diesel::{ExpressionMethods, OptionalExtension, QueryDsl, RunQueryDsl, SelectableHelper};
+use uuid::Uuid;
+
+use crate::db::models::{MailLog, MailTemplate};
+
+use crate::db::Database;
+impl Database {
+    pub fn select_mail_template(
+        &mut self,
+        cook_and_run_id_filter: &Uuid,
+        kind_filter: &str,
+    ) -> Result<Option<MailTemplate>, diesel::result::Error> {
+        let conn = &mut self.get_connection()?;
+        use crate::db::schema::mail_template::dsl::*;
+        mail_template
+            .filter(cook_and_run_id.eq(cook_and_run_id_filter))
+            .filter(kind.eq(kind_filter))
+            .select(MailTemplate::as_select())
+            .first(conn)
+            .optional()
+    }
+
+    pub fn upsert_mail_template(
+        &mut self,
+        data: &MailTemplate,
+    ) -> Result<(), diesel::result::Error> {
+        let conn = &mut self.get_connection()?;
+        use crate::db::schema::mail_template::dsl::*;
+        insert_into(mail_template)
+            .values(data)
+            .on_conflict((cook_and_run_id, kind))
+            .do_update()
+            .set((subject.eq(excluded(subject)), body.eq(excluded(body))))
+            .execute(conn)?;
+        Ok(())
+    }
+
+    pub fn create_mail_log(&mut self, data: &MailLog) -> Result<(), diesel::result::Error> {
+        let conn = &mut self.get_connection()?;
+        use crate::db::schema::mail_log::dsl::*;
+        insert_into(mail_log).values(data).execute(conn)?;
+        Ok(())
+    }
+
+    pub fn select_all_mail_log(
+        &mut self,
+        team_id_filter: &Uuid,
+    ) -> Result<Vec<MailLog>, diesel::result::Error> {
+        let conn = &mut self.get_connection()?;
+        use crate::db::schema::mail_log::dsl::*;
+        mail_log
+            .filter(team_id.eq(team_id_filter))
+            .order(sent.desc())
+            .select(MailLog::as_select())
+            .load(conn)
+    }
+}
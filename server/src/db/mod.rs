@@ -1,9 +1,11 @@
 mod address;
 mod cook_and_run;
 mod course;
+mod mail;
 pub mod models;
 mod note;
 mod plan;
+mod schedule_token;
 mod schema;
 mod sharing;
 mod team;
@@ -37,6 +37,9 @@ pub struct Team {
     pub members: Option<i32>,
     pub diets: Option<String>,
     pub needs_check: bool,
+    pub management_token: Option<String>,
+    pub rejected: bool,
+    pub waitlist_position: Option<i32>,
 }
 
 // ========================================
@@ -53,6 +56,34 @@ pub struct Note {
     pub created: NaiveDateTime,
 }
 
+// ========================================
+// Mail
+// ========================================
+#[derive(Queryable, Selectable, Insertable)]
+#[diesel(belongs_to(CookAndRun))]
+#[diesel(table_name = crate::db::schema::mail_template)]
+pub struct MailTemplate {
+    pub id: Uuid,
+    pub cook_and_run_id: Uuid,
+    pub kind: String,
+    pub subject: String,
+    pub body: String,
+}
+
+#[derive(Queryable, Selectable, Insertable)]
+#[diesel(belongs_to(Team))]
+#[diesel(table_name = crate::db::schema::mail_log)]
+pub struct MailLog {
+    pub id: Uuid,
+    pub team_id: Uuid,
+    pub kind: String,
+    pub recipient: String,
+    pub subject: String,
+    pub sent: NaiveDateTime,
+    pub success: bool,
+    pub error: Option<String>,
+}
+
 // ========================================
 // Course
 // ========================================
@@ -201,6 +232,20 @@ pub struct Plan {
     pub access: Option<Vec<Option<Access>>>,
     pub introduction: Option<String>,
     pub walking_paths: serde_json::Value,
+    pub reveal_minutes: Option<i32>,
+}
+
+// ========================================
+// Schedule Token
+// ========================================
+#[derive(Queryable, Selectable, Insertable)]
+#[diesel(belongs_to(Team))]
+#[diesel(table_name = crate::db::schema::schedule_token)]
+pub struct ScheduleToken {
+    pub token: String,
+    pub team_id: Uuid,
+    pub created: NaiveDateTime,
+    pub revoked: Option<NaiveDateTime>,
 }
 
 // ========================================
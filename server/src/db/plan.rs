@@ -1,4 +1,4 @@
-use diesel::dsl::{delete, insert_into};
+use diesel::dsl::{delete, insert_into, update};
 use diesel::{ExpressionMethods, QueryDsl, RunQueryDsl, SelectableHelper};
 use uuid::Uuid;
 
@@ -19,6 +19,20 @@ impl Database {
         plan.find(id_filter).select(Plan::as_select()).first(conn)
     }
 
+    pub fn update_plan(&mut self, data: &Plan) -> Result<(), diesel::result::Error> {
+        let conn = &mut self.get_connection()?;
+        use crate::db::schema::plan::dsl::*;
+        update(plan.find(data.id))
+            .set((
+                access.eq(&data.access),
+                introduction.eq(&data.introduction),
+                walking_paths.eq(&data.walking_paths),
+                reveal_minutes.eq(&data.reveal_minutes),
+            ))
+            .execute(conn)?;
+        Ok(())
+    }
+
     pub fn delete_plan(&mut self, id_filter: &Uuid) -> Result<(), diesel::result::Error> {
         let conn = &mut self.get_connection()?;
         use crate::db::schema::plan::dsl::*;
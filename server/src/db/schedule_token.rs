@@ -0,0 +1,62 @@
+use chrono::NaiveDateTime;
+use diesel::dsl::{insert_into, update};
+use diesel::{ExpressionMethods, OptionalExtension, QueryDsl, RunQueryDsl, SelectableHelper};
+use uuid::Uuid;
+
+use crate::db::models::ScheduleToken;
+
+use crate::db::Database;
+impl Database {
+    pub fn create_schedule_token(
+        &mut self,
+        data: &ScheduleToken,
+    ) -> Result<(), diesel::result::Error> {
+        let conn = &mut self.get_connection()?;
+        use crate::db::schema::schedule_token::dsl::*;
+        insert_into(schedule_token).values(data).execute(conn)?;
+        Ok(())
+    }
+
+    pub fn select_schedule_token(
+        &mut self,
+        token_filter: &str,
+    ) -> Result<ScheduleToken, diesel::result::Error> {
+        let conn = &mut self.get_connection()?;
+        use crate::db::schema::schedule_token::dsl::*;
+        schedule_token
+            .find(token_filter)
+            .select(ScheduleToken::as_select())
+            .first(conn)
+    }
+
+    pub fn select_active_schedule_token(
+        &mut self,
+        team_id_filter: &Uuid,
+    ) -> Result<Option<ScheduleToken>, diesel::result::Error> {
+        let conn = &mut self.get_connection()?;
+        use crate::db::schema::schedule_token::dsl::*;
+        schedule_token
+            .filter(team_id.eq(team_id_filter))
+            .filter(revoked.is_null())
+            .select(ScheduleToken::as_select())
+            .first(conn)
+            .optional()
+    }
+
+    pub fn revoke_schedule_token(
+        &mut self,
+        team_id_filter: &Uuid,
+        revoked_value: &NaiveDateTime,
+    ) -> Result<(), diesel::result::Error> {
+        let conn = &mut self.get_connection()?;
+        use crate::db::schema::schedule_token::dsl::*;
+        update(
+            schedule_token
+                .filter(team_id.eq(team_id_filter))
+                .filter(revoked.is_null()),
+        )
+        .set(revoked.eq(revoked_value))
+        .execute(conn)?;
+        Ok(())
+    }
+}
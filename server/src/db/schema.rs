@@ -195,6 +195,100 @@ diesel::table! {
     }
 }
 
+diesel::table! {
+    /// Representation of the `mail_log` table.
+    ///
+    /// (Automatically generated by Diesel.)
+    mail_log (id) {
+        /// The `id` column of the `mail_log` table.
+        ///
+        /// Its SQL type is `Uuid`.
+        ///
+        /// (Automatically generated by Diesel.)
+        id -> Uuid,
+        /// The `team_id` column of the `mail_log` table.
+        ///
+        /// Its SQL type is `Uuid`.
+        ///
+        /// (Automatically generated by Diesel.)
+        team_id -> Uuid,
+        /// The `kind` column of the `mail_log` table.
+        ///
+        /// Its SQL type is `Text`.
+        ///
+        /// (Automatically generated by Diesel.)
+        kind -> Text,
+        /// The `recipient` column of the `mail_log` table.
+        ///
+        /// Its SQL type is `Text`.
+        ///
+        /// (Automatically generated by Diesel.)
+        recipient -> Text,
+        /// The `subject` column of the `mail_log` table.
+        ///
+        /// Its SQL type is `Text`.
+        ///
+        /// (Automatically generated by Diesel.)
+        subject -> Text,
+        /// The `sent` column of the `mail_log` table.
+        ///
+        /// Its SQL type is `Timestamptz`.
+        ///
+        /// (Automatically generated by Diesel.)
+        sent -> Timestamptz,
+        /// The `success` column of the `mail_log` table.
+        ///
+        /// Its SQL type is `Bool`.
+        ///
+        /// (Automatically generated by Diesel.)
+        success -> Bool,
+        /// The `error` column of the `mail_log` table.
+        ///
+        /// Its SQL type is `Nullable<Text>`.
+        ///
+        /// (Automatically generated by Diesel.)
+        error -> Nullable<Text>,
+    }
+}
+
+diesel::table! {
+    /// Representation of the `mail_template` table.
+    ///
+    /// (Automatically generated by Diesel.)
+    mail_template (id) {
+        /// The `id` column of the `mail_template` table.
+        ///
+        /// Its SQL type is `Uuid`.
+        ///
+        /// (Automatically generated by Diesel.)
+        id -> Uuid,
+        /// The `cook_and_run_id` column of the `mail_template` table.
+        ///
+        /// Its SQL type is `Uuid`.
+        ///
+        /// (Automatically generated by Diesel.)
+        cook_and_run_id -> Uuid,
+        /// The `kind` column of the `mail_template` table.
+        ///
+        /// Its SQL type is `Text`.
+        ///
+        /// (Automatically generated by Diesel.)
+        kind -> Text,
+        /// The `subject` column of the `mail_template` table.
+        ///
+        /// Its SQL type is `Text`.
+        ///
+        /// (Automatically generated by Diesel.)
+        subject -> Text,
+        /// The `body` column of the `mail_template` table.
+        ///
+        /// Its SQL type is `Text`.
+        ///
+        /// (Automatically generated by Diesel.)
+        body -> Text,
+    }
+}
+
 diesel::table! {
     /// Representation of the `note` table.
     ///
@@ -265,6 +359,44 @@ diesel::table! {
         ///
         /// (Automatically generated by Diesel.)
         walking_paths -> Jsonb,
+        /// The `reveal_minutes` column of the `plan` table.
+        ///
+        /// Its SQL type is `Nullable<Int4>`.
+        ///
+        /// (Automatically generated by Diesel.)
+        reveal_minutes -> Nullable<Int4>,
+    }
+}
+
+diesel::table! {
+    /// Representation of the `schedule_token` table.
+    ///
+    /// (Automatically generated by Diesel.)
+    schedule_token (token) {
+        /// The `token` column of the `schedule_token` table.
+        ///
+        /// Its SQL type is `Text`.
+        ///
+        /// (Automatically generated by Diesel.)
+        token -> Text,
+        /// The `team_id` column of the `schedule_token` table.
+        ///
+        /// Its SQL type is `Uuid`.
+        ///
+        /// (Automatically generated by Diesel.)
+        team_id -> Uuid,
+        /// The `created` column of the `schedule_token` table.
+        ///
+        /// Its SQL type is `Timestamptz`.
+        ///
+        /// (Automatically generated by Diesel.)
+        created -> Timestamptz,
+        /// The `revoked` column of the `schedule_token` table.
+        ///
+        /// Its SQL type is `Nullable<Timestamptz>`.
+        ///
+        /// (Automatically generated by Diesel.)
+        revoked -> Nullable<Timestamptz>,
     }
 }
 
@@ -404,6 +536,24 @@ diesel::table! {
         ///
         /// (Automatically generated by Diesel.)
         needs_check -> Bool,
+        /// The `management_token` column of the `team` table.
+        ///
+        /// Its SQL type is `Nullable<Text>`.
+        ///
+        /// (Automatically generated by Diesel.)
+        management_token -> Nullable<Text>,
+        /// The `rejected` column of the `team` table.
+        ///
+        /// Its SQL type is `Bool`.
+        ///
+        /// (Automatically generated by Diesel.)
+        rejected -> Bool,
+        /// The `waitlist_position` column of the `team` table.
+        ///
+        /// Its SQL type is `Nullable<Int4>`.
+        ///
+        /// (Automatically generated by Diesel.)
+        waitlist_position -> Nullable<Int4>,
     }
 }
 
@@ -412,7 +562,10 @@ diesel::joinable!(cook_and_run -> share (share_team_config));
 diesel::joinable!(hosting -> course (course_id));
 diesel::joinable!(hosting -> plan (plan_id));
 diesel::joinable!(hosting -> team (team_id));
+diesel::joinable!(mail_log -> team (team_id));
+diesel::joinable!(mail_template -> cook_and_run (cook_and_run_id));
 diesel::joinable!(note -> team (team_id));
+diesel::joinable!(schedule_token -> team (team_id));
 diesel::joinable!(team -> address (address));
 diesel::joinable!(team -> cook_and_run (cook_and_run_id));
 
@@ -421,8 +574,11 @@ diesel::allow_tables_to_appear_in_same_query!(
     cook_and_run,
     course,
     hosting,
+    mail_log,
+    mail_template,
     note,
     plan,
+    schedule_token,
     share,
     team,
 );
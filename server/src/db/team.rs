@@ -1,9 +1,121 @@
-use diesel::dsl::{delete, insert_into};
-use diesel::{ExpressionMethods, QueryDsl, RunQueryDsl, SelectableHelper};
+use diesel::dsl::{delete, insert_into, update};
+use diesel::{
+    Connection, ExpressionMethods, PgConnection, QueryDsl, RunQueryDsl, SelectableHelper,
+};
 use uuid::Uuid;
 
 use crate::db::models::Team;
 use crate::db::Database;
+
+// Locks the cook and run row until the end of the transaction, so registrations and
+// waitlist changes of the same project run one after another.
+fn lock_cook_and_run(
+    conn: &mut PgConnection,
+    cook_and_run_id_filter: &Uuid,
+) -> Result<(), diesel::result::Error> {
+    use crate::db::schema::cook_and_run::dsl::*;
+    cook_and_run
+        .find(cook_and_run_id_filter)
+        .select(id)
+        .for_update()
+        .first::<Uuid>(conn)?;
+    Ok(())
+}
+
+fn count_registered(
+    conn: &mut PgConnection,
+    cook_and_run_id_filter: &Uuid,
+) -> Result<i64, diesel::result::Error> {
+    use crate::db::schema::team::dsl::*;
+    team.filter(cook_and_run_id.eq(cook_and_run_id_filter))
+        .filter(waitlist_position.is_null())
+        .filter(rejected.eq(false))
+        .count()
+        .get_result(conn)
+}
+
+fn load_waitlist(
+    conn: &mut PgConnection,
+    cook_and_run_id_filter: &Uuid,
+) -> Result<Vec<Team>, diesel::result::Error> {
+    use crate::db::schema::team::dsl::*;
+    team.filter(cook_and_run_id.eq(cook_and_run_id_filter))
+        .filter(waitlist_position.is_not_null())
+        .order(waitlist_position.asc())
+        .select(Team::as_select())
+        .load(conn)
+}
+
+fn set_waitlist_position(
+    conn: &mut PgConnection,
+    id_filter: &Uuid,
+    position: Option<i32>,
+) -> Result<(), diesel::result::Error> {
+    use crate::db::schema::team::dsl::*;
+    update(team.find(id_filter))
+        .set(waitlist_position.eq(position))
+        .execute(conn)?;
+    Ok(())
+}
+
+fn renumber(
+    conn: &mut PgConnection,
+    cook_and_run_id_filter: &Uuid,
+) -> Result<(), diesel::result::Error> {
+    for (index, data) in load_waitlist(conn, cook_and_run_id_filter)?
+        .iter()
+        .enumerate()
+    {
+        let position = index as i32 + 1;
+        if data.waitlist_position != Some(position) {
+            set_waitlist_position(conn, &data.id, Some(position))?;
+        }
+    }
+    Ok(())
+}
+
+// A new team gets a regular place only if the project is not full and nobody is waiting
+// for a place already. Returns the waitlist position otherwise.
+fn get_new_waitlist_position(
+    conn: &mut PgConnection,
+    cook_and_run_id_filter: &Uuid,
+    max_teams: Option<i64>,
+) -> Result<Option<i32>, diesel::result::Error> {
+    let waitlist_length = load_waitlist(conn, cook_and_run_id_filter)?.len() as i32;
+    let is_full = match max_teams {
+        Some(max_teams) => count_registered(conn, cook_and_run_id_filter)? >= max_teams,
+        None => false,
+    };
+    Ok((is_full || waitlist_length > 0).then_some(waitlist_length + 1))
+}
+
+// Moves waitlisted teams to a regular place as long as places are free and closes the
+// gaps in the remaining positions. Returns the promoted teams.
+fn promote(
+    conn: &mut PgConnection,
+    cook_and_run_id_filter: &Uuid,
+    max_teams: Option<i64>,
+) -> Result<Vec<Uuid>, diesel::result::Error> {
+    let free_places = match max_teams {
+        Some(max_teams) => {
+            (max_teams - count_registered(conn, cook_and_run_id_filter)?).max(0) as usize
+        }
+        None => usize::MAX,
+    };
+
+    let mut promoted_list = Vec::new();
+    for data in load_waitlist(conn, cook_and_run_id_filter)?
+        .into_iter()
+        .take(free_places)
+    {
+        set_waitlist_position(conn, &data.id, None)?;
+        promoted_list.push(data.id);
+    }
+
+    renumber(conn, cook_and_run_id_filter)?;
+    Ok(promoted_list)
+}
+
 impl Database {
     pub fn create_team(&mut self, data: &Team) -> Result<(), diesel::result::Error> {
         let conn = &mut self.get_connection()?;
@@ -29,6 +141,173 @@ impl Database {
         team.find(id_filter).select(Team::as_select()).first(conn)
     }
 
+    pub fn select_team_by_management_token(
+        &mut self,
+        token_filter: &str,
+    ) -> Result<Team, diesel::result::Error> {
+        let conn = &mut self.get_connection()?;
+        use crate::db::schema::team::dsl::*;
+        team.filter(management_token.eq(token_filter))
+            .select(Team::as_select())
+            .first(conn)
+    }
+
+    pub fn select_waitlist(
+        &mut self,
+        cook_and_run_id_filter: &Uuid,
+    ) -> Result<Vec<Team>, diesel::result::Error> {
+        let conn = &mut self.get_connection()?;
+        load_waitlist(conn, cook_and_run_id_filter)
+    }
+
+    pub fn update_team_waitlist_position(
+        &mut self,
+        id_filter: &Uuid,
+        position: Option<i32>,
+    ) -> Result<(), diesel::result::Error> {
+        let conn = &mut self.get_connection()?;
+        set_waitlist_position(conn, id_filter, position)
+    }
+
+    // Inserts a team and puts it on the waitlist if the project is full or others are
+    // waiting. Counting and inserting run in one transaction, so concurrent registrations
+    // can't exceed the limit or get the same position.
+    pub fn create_team_with_waitlist(
+        &mut self,
+        mut data: Team,
+        max_teams: Option<i64>,
+    ) -> Result<Option<i32>, diesel::result::Error> {
+        let conn = &mut self.get_connection()?;
+        conn.transaction(|conn| {
+            lock_cook_and_run(conn, &data.cook_and_run_id)?;
+            data.waitlist_position =
+                get_new_waitlist_position(conn, &data.cook_and_run_id, max_teams)?;
+
+            use crate::db::schema::team::dsl::*;
+            insert_into(team).values(&data).execute(conn)?;
+            Ok(data.waitlist_position)
+        })
+    }
+
+    pub fn promote_waitlist(
+        &mut self,
+        cook_and_run_id_filter: &Uuid,
+        max_teams: Option<i64>,
+    ) -> Result<Vec<Uuid>, diesel::result::Error> {
+        let conn = &mut self.get_connection()?;
+        conn.transaction(|conn| {
+            lock_cook_and_run(conn, cook_and_run_id_filter)?;
+            promote(conn, cook_and_run_id_filter, max_teams)
+        })
+    }
+
+    // Deletes a team and gives its place to the next team on the waitlist in the same
+    // transaction. Returns the promoted teams.
+    pub fn delete_team_with_waitlist(
+        &mut self,
+        data: &Team,
+        max_teams: Option<i64>,
+    ) -> Result<Vec<Uuid>, diesel::result::Error> {
+        let conn = &mut self.get_connection()?;
+        conn.transaction(|conn| {
+            lock_cook_and_run(conn, &data.cook_and_run_id)?;
+            {
+                use crate::db::schema::team::dsl::*;
+                delete(team.find(data.id)).execute(conn)?;
+            }
+            promote(conn, &data.cook_and_run_id, max_teams)
+        })
+    }
+
+    // Gives the waitlisted teams the positions of the new order. Returns false without a
+    // change if the order does not contain every waitlisted team exactly once.
+    pub fn reorder_waitlist(
+        &mut self,
+        cook_and_run_id_filter: &Uuid,
+        team_id_list: &[Uuid],
+    ) -> Result<bool, diesel::result::Error> {
+        let conn = &mut self.get_connection()?;
+        conn.transaction(|conn| {
+            lock_cook_and_run(conn, cook_and_run_id_filter)?;
+            let waitlist = load_waitlist(conn, cook_and_run_id_filter)?;
+            if team_id_list.len() != waitlist.len()
+                || waitlist.iter().any(|data| !team_id_list.contains(&data.id))
+            {
+                return Ok(false);
+            }
+
+            for (index, team_id) in team_id_list.iter().enumerate() {
+                set_waitlist_position(conn, team_id, Some(index as i32 + 1))?;
+            }
+            Ok(true)
+        })
+    }
+
+    pub fn update_team(&mut self, data: &Team) -> Result<(), diesel::result::Error> {
+        let conn = &mut self.get_connection()?;
+        use crate::db::schema::team::dsl::*;
+        update(team.find(data.id))
+            .set((
+                name.eq(&data.name),
+                edited.eq(&data.edited),
+                address.eq(&data.address),
+                mail.eq(&data.mail),
+                phone.eq(&data.phone),
+                members.eq(&data.members),
+                diets.eq(&data.diets),
+                needs_check.eq(&data.needs_check),
+            ))
+            .execute(conn)?;
+        Ok(())
+    }
+
+    // Approves a team. A rejected team has given up its place, so it gets a regular place
+    // again only like a new team would. Returns the waitlist position of the team.
+    pub fn approve_team_with_waitlist(
+        &mut self,
+        cook_and_run_id_filter: &Uuid,
+        id_filter: &Uuid,
+        max_teams: Option<i64>,
+    ) -> Result<Option<i32>, diesel::result::Error> {
+        let conn = &mut self.get_connection()?;
+        conn.transaction(|conn| {
+            use crate::db::schema::team::dsl::*;
+            lock_cook_and_run(conn, cook_and_run_id_filter)?;
+            let data = team.find(id_filter).select(Team::as_select()).first(conn)?;
+            let position = if data.rejected {
+                get_new_waitlist_position(conn, cook_and_run_id_filter, max_teams)?
+            } else {
+                data.waitlist_position
+            };
+
+            update(team.find(id_filter))
+                .set((
+                    needs_check.eq(false),
+                    rejected.eq(false),
+                    waitlist_position.eq(position),
+                ))
+                .execute(conn)?;
+            Ok(position)
+        })
+    }
+
+    pub fn update_team_review(
+        &mut self,
+        id_filter: &Uuid,
+        needs_check_value: bool,
+        rejected_value: bool,
+    ) -> Result<(), diesel::result::Error> {
+        let conn = &mut self.get_connection()?;
+        use crate::db::schema::team::dsl::*;
+        update(team.find(id_filter))
+            .set((
+                needs_check.eq(needs_check_value),
+                rejected.eq(rejected_value),
+            ))
+            .execute(conn)?;
+        Ok(())
+    }
+
     pub fn delete_team(&mut self, id_filter: &Uuid) -> Result<(), diesel::result::Error> {
         let conn = &mut self.get_connection()?;
         use crate::db::schema::team::dsl::*;
@@ -11,6 +11,16 @@ pub enum RestError {
     BadRequest { message: String },
     InternalServer { message: String },
     Conflict { message: String },
+    NotFound { message: String },
+    Unauthorized { message: String },
+    Forbidden { message: String },
+    Validation { details: Vec<ErrorDetail> },
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct ErrorDetail {
+    pub field: String,
+    pub message: String,
 }
 
 impl RestError {
@@ -31,6 +41,28 @@ impl RestError {
             message: message.to_string(),
         }
     }
+
+    pub fn not_found_error(message: &str) -> Self {
+        RestError::NotFound {
+            message: message.to_string(),
+        }
+    }
+
+    pub fn unauthorized_error(message: &str) -> Self {
+        RestError::Unauthorized {
+            message: message.to_string(),
+        }
+    }
+
+    pub fn forbidden_error(message: &str) -> Self {
+        RestError::Forbidden {
+            message: message.to_string(),
+        }
+    }
+
+    pub fn validation_error(details: Vec<ErrorDetail>) -> Self {
+        RestError::Validation { details }
+    }
 }
 
 #[derive(Serialize)]
@@ -39,15 +71,28 @@ struct ErrorBody {
     status: StatusCode,
     error: String,
     message: String,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    details: Vec<ErrorDetail>,
     timestamp: DateTime<Utc>,
 }
 
 impl ErrorBody {
     fn from_rest_error(rest_error: &RestError) -> Self {
+        let validation_message = "Request validation failed".to_string();
+        let mut details = vec![];
         let (status, message) = match rest_error {
             RestError::BadRequest { message } => (StatusCode::BAD_REQUEST, message),
             RestError::InternalServer { message } => (StatusCode::INTERNAL_SERVER_ERROR, message),
             RestError::Conflict { message } => (StatusCode::CONFLICT, message),
+            RestError::NotFound { message } => (StatusCode::NOT_FOUND, message),
+            RestError::Unauthorized { message } => (StatusCode::UNAUTHORIZED, message),
+            RestError::Forbidden { message } => (StatusCode::FORBIDDEN, message),
+            RestError::Validation {
+                details: detail_list,
+            } => {
+                details = detail_list.clone();
+                (StatusCode::UNPROCESSABLE_ENTITY, &validation_message)
+            }
         };
         ErrorBody {
             status: status,
@@ -56,6 +101,7 @@ impl ErrorBody {
                 .expect("Expect status code to exists!")
                 .to_string(),
             message: message.clone(),
+            details,
             timestamp: Utc::now(),
         }
     }
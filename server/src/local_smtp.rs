@@ -0,0 +1,128 @@
+use std::{
+    io::{BufRead, BufReader, Write},
+    net::{TcpListener, TcpStream},
+    sync::{Arc, Mutex},
+    thread,
+};
+
+use tracing::{event, Level};
+
+#[derive(Debug, Clone)]
+pub struct ReceivedMail {
+    pub sender: String,
+    pub recipient_list: Vec<String>,
+    pub data: String,
+}
+
+// Minimal SMTP server that accepts every mail and keeps it in memory. It stands in for a
+// real mail server in tests and local development.
+pub struct LocalSmtpServer {
+    port: u16,
+    received_list: Arc<Mutex<Vec<ReceivedMail>>>,
+}
+
+impl LocalSmtpServer {
+    pub fn start() -> Result<Self, String> {
+        let listener = TcpListener::bind("127.0.0.1:0")
+            .map_err(|e| format!("Could not start local SMTP server: {}", e))?;
+        let port = listener
+            .local_addr()
+            .map_err(|e| format!("Could not get local SMTP address: {}", e))?
+            .port();
+        let received_list = Arc::new(Mutex::new(Vec::new()));
+
+        let thread_received_list = received_list.clone();
+        thread::spawn(move || {
+            for stream in listener.incoming().flatten() {
+                if let Err(e) = handle_connection(stream, &thread_received_list) {
+                    event!(Level::WARN, "Local SMTP connection failed: {}", e);
+                }
+            }
+        });
+
+        Ok(LocalSmtpServer {
+            port,
+            received_list,
+        })
+    }
+
+    pub fn port(&self) -> u16 {
+        self.port
+    }
+
+    pub fn received(&self) -> Vec<ReceivedMail> {
+        self.received_list
+            .lock()
+            .expect("Expected received mail lock")
+            .clone()
+    }
+}
+
+fn strip_address(value: &str) -> String {
+    value
+        .trim()
+        .trim_start_matches('<')
+        .trim_end_matches('>')
+        .to_string()
+}
+
+fn handle_connection(
+    stream: TcpStream,
+    received_list: &Arc<Mutex<Vec<ReceivedMail>>>,
+) -> std::io::Result<()> {
+    let mut writer = stream.try_clone()?;
+    let mut reader = BufReader::new(stream);
+    writer.write_all(b"220 localhost ESMTP\r\n")?;
+
+    let mut sender = String::new();
+    let mut recipient_list = Vec::new();
+    let mut line = String::new();
+    loop {
+        line.clear();
+        if reader.read_line(&mut line)? == 0 {
+            return Ok(());
+        }
+        let command = line.trim_end().to_string();
+        let upper_command = command.to_uppercase();
+
+        if upper_command.starts_with("EHLO") || upper_command.starts_with("HELO") {
+            writer.write_all(b"250 localhost\r\n")?;
+        } else if upper_command.starts_with("MAIL FROM:") {
+            sender = strip_address(&command["MAIL FROM:".len()..]);
+            writer.write_all(b"250 OK\r\n")?;
+        } else if upper_command.starts_with("RCPT TO:") {
+            recipient_list.push(strip_address(&command["RCPT TO:".len()..]));
+            writer.write_all(b"250 OK\r\n")?;
+        } else if upper_command == "DATA" {
+            writer.write_all(b"354 End data with <CR><LF>.<CR><LF>\r\n")?;
+            let mut data = String::new();
+            loop {
+                line.clear();
+                if reader.read_line(&mut line)? == 0 {
+                    return Ok(());
+                }
+                if line.trim_end() == "." {
+                    break;
+                }
+                data.push_str(&line);
+            }
+            received_list
+                .lock()
+                .expect("Expected received mail lock")
+                .push(ReceivedMail {
+                    sender: sender.clone(),
+                    recipient_list: std::mem::take(&mut recipient_list),
+                    data,
+                });
+            writer.write_all(b"250 OK\r\n")?;
+        } else if upper_command == "QUIT" {
+            writer.write_all(b"221 Bye\r\n")?;
+            return Ok(());
+        } else if upper_command == "RSET" || upper_command == "NOOP" {
+            recipient_list.clear();
+            writer.write_all(b"250 OK\r\n")?;
+        } else {
+            writer.write_all(b"502 Command not implemented\r\n")?;
+        }
+    }
+}
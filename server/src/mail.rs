@@ -0,0 +1,468 @@
+use chrono::{NaiveDateTime, Utc};
+use lettre::{
+    message::Mailbox, transport::smtp::authentication::Credentials, Message, SmtpTransport,
+    Transport,
+};
+use tracing::{event, Level};
+use uuid::Uuid;
+
+use crate::{
+    cook_and_run::{self, CookAndRun},
+    db::{self, Database},
+    error::RestError,
+    team::Team,
+};
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum MailKind {
+    RegistrationConfirmation,
+    SchedulePublished,
+    ScheduleChanged,
+}
+
+impl MailKind {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            MailKind::RegistrationConfirmation => "registration_confirmation",
+            MailKind::SchedulePublished => "schedule_published",
+            MailKind::ScheduleChanged => "schedule_changed",
+        }
+    }
+
+    pub fn parse(kind: &str) -> Result<Self, RestError> {
+        match kind {
+            "registration_confirmation" => Ok(MailKind::RegistrationConfirmation),
+            "schedule_published" => Ok(MailKind::SchedulePublished),
+            "schedule_changed" => Ok(MailKind::ScheduleChanged),
+            _ => Err(RestError::bad_request_error(&format!(
+                "Unknown mail kind {}!",
+                kind
+            ))),
+        }
+    }
+
+    fn default_template(&self) -> (&'static str, &'static str) {
+        match self {
+            MailKind::RegistrationConfirmation => (
+                "Registration for {{project_name}}",
+                "Hello {{team_name}},\n\n\
+                 thank you for registering for {{project_name}}.\n\
+                 You can change or withdraw your registration with this code: {{management_token}}\n",
+            ),
+            MailKind::SchedulePublished => (
+                "Your schedule for {{project_name}}",
+                "Hello {{team_name}},\n\n\
+                 {{introduction}}\n\n\
+                 Your schedule:\n\
+                 {{schedule}}\n",
+            ),
+            MailKind::ScheduleChanged => (
+                "Your schedule for {{project_name}} has changed",
+                "Hello {{team_name}},\n\n\
+                 your schedule for {{project_name}} has changed.\n\n\
+                 Your new schedule:\n\
+                 {{schedule}}\n",
+            ),
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct MailTemplate {
+    pub kind: MailKind,
+    pub subject: String,
+    pub body: String,
+}
+
+#[derive(Debug, Clone)]
+pub struct MailLogEntry {
+    pub id: Uuid,
+    pub kind: String,
+    pub recipient: String,
+    pub subject: String,
+    pub sent: NaiveDateTime,
+    pub success: bool,
+    pub error: Option<String>,
+}
+
+impl MailLogEntry {
+    fn from(db_log: db::models::MailLog) -> Self {
+        MailLogEntry {
+            id: db_log.id,
+            kind: db_log.kind,
+            recipient: db_log.recipient,
+            subject: db_log.subject,
+            sent: db_log.sent,
+            success: db_log.success,
+            error: db_log.error,
+        }
+    }
+}
+
+pub trait Mailer {
+    fn send(&self, recipient: &str, subject: &str, body: &str) -> Result<(), String>;
+}
+
+pub struct SmtpMailer {
+    transport: SmtpTransport,
+    from: Mailbox,
+}
+
+impl SmtpMailer {
+    // Without credentials the connection is unencrypted, which is only meant for a local relay
+    pub fn new(
+        host: &str,
+        port: u16,
+        credentials: Option<Credentials>,
+        from: &str,
+    ) -> Result<Self, String> {
+        let from = from
+            .parse::<Mailbox>()
+            .map_err(|e| format!("Invalid sender address {}: {}", from, e))?;
+        let builder = match credentials {
+            Some(credentials) => SmtpTransport::starttls_relay(host)
+                .map_err(|e| format!("Could not connect to SMTP server {}: {}", host, e))?
+                .credentials(credentials),
+            None => SmtpTransport::builder_dangerous(host),
+        };
+        Ok(SmtpMailer {
+            transport: builder.port(port).build(),
+            from,
+        })
+    }
+}
+
+impl Mailer for SmtpMailer {
+    fn send(&self, recipient: &str, subject: &str, body: &str) -> Result<(), String> {
+        let to = recipient
+            .parse::<Mailbox>()
+            .map_err(|e| format!("Invalid recipient {}: {}", recipient, e))?;
+        let message = Message::builder()
+            .from(self.from.clone())
+            .to(to)
+            .subject(subject)
+            .body(body.to_string())
+            .map_err(|e| format!("Could not build mail: {}", e))?;
+        self.transport
+            .send(&message)
+            .map(|_| ())
+            .map_err(|e| format!("Could not send mail: {}", e))
+    }
+}
+
+// Used when no SMTP server is configured, every mail ends up as failed in the send log
+struct DisabledMailer;
+
+impl Mailer for DisabledMailer {
+    fn send(&self, recipient: &str, _subject: &str, _body: &str) -> Result<(), String> {
+        event!(
+            Level::WARN,
+            "SMTP is not configured, no mail sent to {}",
+            recipient
+        );
+        Err("SMTP is not configured".to_string())
+    }
+}
+
+// Reads SMTP_HOST, SMTP_PORT, SMTP_USER, SMTP_PASSWORD and SMTP_FROM
+pub fn mailer_from_env() -> Box<dyn Mailer> {
+    let Ok(host) = std::env::var("SMTP_HOST") else {
+        return Box::new(DisabledMailer);
+    };
+    let port = std::env::var("SMTP_PORT")
+        .ok()
+        .and_then(|p| p.parse::<u16>().ok())
+        .unwrap_or(587);
+    let credentials = match (std::env::var("SMTP_USER"), std::env::var("SMTP_PASSWORD")) {
+        (Ok(user), Ok(password)) => Some(Credentials::new(user, password)),
+        _ => None,
+    };
+    let from = std::env::var("SMTP_FROM")
+        .unwrap_or_else(|_| "Cook and Run <noreply@localhost>".to_string());
+
+    match SmtpMailer::new(&host, port, credentials, &from) {
+        Ok(mailer) => Box::new(mailer),
+        Err(e) => {
+            event!(Level::ERROR, "Could not configure SMTP: {}", e);
+            Box::new(DisabledMailer)
+        }
+    }
+}
+
+fn map_db_error(e: diesel::result::Error, text: &str) -> RestError {
+    event!(Level::ERROR, "{}: {}", text, e);
+    RestError::internal_server_error(text)
+}
+
+pub fn get_template(
+    db: &mut Database,
+    cook_and_run_id: &Uuid,
+    kind: MailKind,
+) -> Result<MailTemplate, RestError> {
+    let template = db
+        .select_mail_template(cook_and_run_id, kind.as_str())
+        .map_err(|e| map_db_error(e, "Database error while selecting mail template"))?;
+
+    Ok(match template {
+        Some(template) => MailTemplate {
+            kind,
+            subject: template.subject,
+            body: template.body,
+        },
+        None => {
+            let (subject, body) = kind.default_template();
+            MailTemplate {
+                kind,
+                subject: subject.to_string(),
+                body: body.to_string(),
+            }
+        }
+    })
+}
+
+pub fn update_template(
+    db: &mut Database,
+    cook_and_run_id: &Uuid,
+    kind: MailKind,
+    subject: &str,
+    body: &str,
+) -> Result<MailTemplate, RestError> {
+    if subject.trim().is_empty() {
+        return Err(RestError::bad_request_error("Subject is required!"));
+    }
+    db.upsert_mail_template(&db::models::MailTemplate {
+        id: Uuid::new_v4(),
+        cook_and_run_id: *cook_and_run_id,
+        kind: kind.as_str().to_string(),
+        subject: subject.to_string(),
+        body: body.to_string(),
+    })
+    .map_err(|e| map_db_error(e, "Could not save mail template in database"))?;
+    get_template(db, cook_and_run_id, kind)
+}
+
+// Replaces every {{key}} with its value, unknown placeholders are kept
+pub fn render(text: &str, value_list: &[(&str, String)]) -> String {
+    let mut result = text.to_string();
+    for (key, value) in value_list {
+        result = result.replace(&format!("{{{{{}}}}}", key), value);
+    }
+    result
+}
+
+pub fn schedule_text(cook_and_run: &CookAndRun, team_id: &Uuid) -> String {
+    let Some(step_list) = cook_and_run
+        .plan
+        .as_ref()
+        .and_then(|p| p.walking_paths.get(team_id))
+    else {
+        return String::new();
+    };
+
+    let mut line_list = Vec::new();
+    if let Some(start_point) = &cook_and_run.start_point {
+        line_list.push(format!("Start: {}", start_point.address));
+    }
+    for step in step_list {
+        let course = cook_and_run
+            .course_list
+            .iter()
+            .find(|c| c.id == step.course_id);
+        let host = cook_and_run
+            .team_list
+            .iter()
+            .find(|t| t.id == step.host_team_id);
+        let (Some(course), Some(host)) = (course, host) else {
+            continue;
+        };
+        if &host.id == team_id {
+            line_list.push(format!(
+                "{} {}: You are hosting at {}",
+                course.time, course.name, host.address.address
+            ));
+        } else {
+            line_list.push(format!(
+                "{} {}: {} at {}",
+                course.time, course.name, host.name, host.address.address
+            ));
+        }
+    }
+    if let Some(end_point) = &cook_and_run.end_point {
+        line_list.push(format!("End: {}", end_point.address));
+    }
+    line_list.join("\n")
+}
+
+fn write_log(
+    db: &mut Database,
+    team_id: &Uuid,
+    kind: MailKind,
+    recipient: &str,
+    subject: &str,
+    result: Result<(), String>,
+) -> Result<MailLogEntry, RestError> {
+    let log = db::models::MailLog {
+        id: Uuid::new_v4(),
+        team_id: *team_id,
+        kind: kind.as_str().to_string(),
+        recipient: recipient.to_string(),
+        subject: subject.to_string(),
+        sent: Utc::now().naive_utc(),
+        success: result.is_ok(),
+        error: result.err(),
+    };
+    db.create_mail_log(&log)
+        .map_err(|e| map_db_error(e, "Could not write mail log to database"))?;
+    Ok(MailLogEntry::from(log))
+}
+
+// Sends one mail to a team, a failed delivery is only recorded in the send log
+pub fn notify_team(
+    db: &mut Database,
+    mailer: &dyn Mailer,
+    cook_and_run: &CookAndRun,
+    team: &Team,
+    kind: MailKind,
+    extra_value_list: &[(&str, String)],
+) -> Result<Option<MailLogEntry>, RestError> {
+    let Some(recipient) = team.mail.as_ref().filter(|m| !m.trim().is_empty()) else {
+        return Ok(None);
+    };
+
+    let template = get_template(db, &cook_and_run.id, kind)?;
+    let introduction = cook_and_run
+        .plan
+        .as_ref()
+        .and_then(|p| p.introduction.clone())
+        .unwrap_or_default();
+    let mut value_list = vec![
+        ("team_name", team.name.clone()),
+        ("project_name", cook_and_run.name.clone()),
+        ("introduction", introduction),
+        ("schedule", schedule_text(cook_and_run, &team.id)),
+    ];
+    value_list.extend(extra_value_list.iter().cloned());
+
+    let subject = render(&template.subject, &value_list);
+    let body = render(&template.body, &value_list);
+    let result = mailer.send(recipient, &subject, &body);
+    if let Err(e) = &result {
+        event!(
+            Level::ERROR,
+            "Could not send mail to team {}: {}",
+            team.id,
+            e
+        );
+    }
+    write_log(db, &team.id, kind, recipient, &subject, result).map(Some)
+}
+
+// Sends the schedule to every team that is part of the plan
+pub fn notify_all(
+    db: &mut Database,
+    mailer: &dyn Mailer,
+    cook_and_run_id: &Uuid,
+    kind: MailKind,
+) -> Result<Vec<MailLogEntry>, RestError> {
+    if kind == MailKind::RegistrationConfirmation {
+        return Err(RestError::bad_request_error(
+            "Registration confirmations are only sent on registration!",
+        ));
+    }
+    let cook_and_run = cook_and_run::get_cook_and_run(db, cook_and_run_id)?;
+    let Some(plan) = &cook_and_run.plan else {
+        return Err(RestError::bad_request_error(
+            "No plan has been calculated yet!",
+        ));
+    };
+
+    let mut log_list = Vec::new();
+    for team in cook_and_run
+        .team_list
+        .iter()
+        .filter(|t| plan.walking_paths.contains_key(&t.id))
+    {
+        if let Some(log) = notify_team(db, mailer, &cook_and_run, team, kind, &[])? {
+            log_list.push(log);
+        }
+    }
+    Ok(log_list)
+}
+
+pub fn get_log_list(
+    db: &mut Database,
+    cook_and_run_id: &Uuid,
+    team_id: &Uuid,
+) -> Result<Vec<MailLogEntry>, RestError> {
+    let team = db.select_team(team_id).map_err(|e| match e {
+        diesel::result::Error::NotFound => RestError::not_found_error("Team not found!"),
+        e => map_db_error(e, "Database error while selecting team"),
+    })?;
+    if &team.cook_and_run_id != cook_and_run_id {
+        return Err(RestError::not_found_error(
+            "Team not found in this project!",
+        ));
+    }
+    db.select_all_mail_log(team_id)
+        .map(|list| list.into_iter().map(MailLogEntry::from).collect())
+        .map_err(|e| map_db_error(e, "Database error while selecting mail log"))
+}
+
+pub fn template_to_json(template: &MailTemplate) -> serde_json::Value {
+    serde_json::json!({
+        "kind": template.kind.as_str(),
+        "subject": template.subject,
+        "body": template.body,
+    })
+}
+
+pub fn log_to_json(log: &MailLogEntry) -> serde_json::Value {
+    serde_json::json!({
+        "id": log.id,
+        "kind": log.kind,
+        "recipient": log.recipient,
+        "subject": log.subject,
+        "sent": log.sent,
+        "success": log.success,
+        "error": log.error,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        local_smtp::LocalSmtpServer,
+        mail::{render, Mailer, SmtpMailer},
+    };
+
+    #[test]
+    fn test_render() {
+        let text = render(
+            "Hello {{team_name}}, welcome to {{project_name}}! {{unknown}}",
+            &[
+                ("team_name", "Chili Chasers".to_string()),
+                ("project_name", "Summer Run".to_string()),
+            ],
+        );
+        assert_eq!(
+            text,
+            "Hello Chili Chasers, welcome to Summer Run! {{unknown}}"
+        );
+    }
+
+    #[test]
+    fn test_send_to_local_smtp_server() {
+        let server = LocalSmtpServer::start().unwrap();
+        let mailer = SmtpMailer::new("127.0.0.1", server.port(), None, "tcc@localhost").unwrap();
+
+        mailer
+            .send("team@localhost", "Your schedule", "Starter at 18:00")
+            .unwrap();
+
+        let received_list = server.received();
+        assert_eq!(received_list.len(), 1);
+        assert_eq!(received_list[0].recipient_list, vec!["team@localhost"]);
+        assert!(received_list[0].data.contains("Subject: Your schedule"));
+        assert!(received_list[0].data.contains("Starter at 18:00"));
+    }
+}
@@ -1,9 +1,15 @@
 use std::collections::HashMap;
 
+use chrono::NaiveTime;
 use tracing::event;
 use uuid::Uuid;
 
-use crate::{db, error::RestError};
+use crate::{
+    cook_and_run::{self, CookAndRun},
+    db,
+    error::{ErrorDetail, RestError},
+    team::TeamStatus,
+};
 
 #[derive(Debug, Clone)]
 pub enum Access {
@@ -19,6 +25,31 @@ impl Access {
         }
     }
 
+    pub fn parse(access: &str) -> Result<Self, RestError> {
+        match access {
+            "link" => Ok(Access::Link),
+            "account" => Ok(Access::Account),
+            _ => Err(RestError::bad_request_error(&format!(
+                "Unknown access {}!",
+                access
+            ))),
+        }
+    }
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Access::Link => "link",
+            Access::Account => "account",
+        }
+    }
+
+    fn to(&self) -> db::models::Access {
+        match self {
+            Access::Link => db::models::Access::Link,
+            Access::Account => db::models::Access::Account,
+        }
+    }
+
     fn from_list(db_field_list: Option<Vec<Option<db::models::Access>>>) -> Vec<Self> {
         db_field_list.map_or_else(
             || vec![],
@@ -37,6 +68,8 @@ pub struct Plan {
     pub introduction: Option<String>,
     pub hosting_assignments: Vec<Hosting>,
     pub walking_paths: HashMap<Uuid, Vec<WalkingPathStep>>,
+    // Minutes before a course from which its guests see the address, None shows it right away
+    pub reveal_minutes: Option<u32>,
 }
 
 impl Plan {
@@ -50,6 +83,7 @@ impl Plan {
             introduction: db_plan.introduction,
             hosting_assignments: hosting_list,
             walking_paths,
+            reveal_minutes: db_plan.reveal_minutes.map(|m| m as u32),
         }
     }
 }
@@ -101,6 +135,270 @@ impl WalkingPathStep {
             host_team_id: db_step.host_team_id,
         }
     }
+
+    fn to_map(walking_paths: &HashMap<Uuid, Vec<Self>>) -> serde_json::Value {
+        let walking_path: HashMap<Uuid, Vec<crate::db::models::WalkingPathStep>> = walking_paths
+            .iter()
+            .map(|(key, steps)| {
+                let step_list = steps
+                    .iter()
+                    .map(|s| crate::db::models::WalkingPathStep {
+                        course_id: s.course_id,
+                        host_team_id: s.host_team_id,
+                    })
+                    .collect();
+                (*key, step_list)
+            })
+            .collect();
+        serde_json::to_value(walking_path).expect("Failed to serialize WalkingPathStep")
+    }
+}
+
+fn map_db_error(e: diesel::result::Error, text: &str) -> RestError {
+    match e {
+        diesel::result::Error::NotFound => RestError::not_found_error(text),
+        e => {
+            event!(tracing::Level::ERROR, "{}: {}", text, e);
+            RestError::internal_server_error(text)
+        }
+    }
+}
+
+fn detail(field: &str, message: String) -> ErrorDetail {
+    ErrorDetail {
+        field: field.to_string(),
+        message,
+    }
+}
+
+// Checks the structural rules of a plan against the approved teams and the courses of the
+// project, the same rules the client checks after a calculation or an edit
+pub fn validate(cook_and_run: &CookAndRun, plan: &Plan) -> Vec<ErrorDetail> {
+    let mut details = vec![];
+    let mut course_list = cook_and_run.course_list.clone();
+    course_list.sort_by_key(|c| {
+        NaiveTime::parse_from_str(&c.time, "%H:%M")
+            .or_else(|_| NaiveTime::parse_from_str(&c.time, "%H:%M:%S"))
+            .ok()
+    });
+    // Teams on the waitlist don't take part in the plan
+    let team_list: Vec<_> = cook_and_run
+        .team_list
+        .iter()
+        .filter(|t| t.status == TeamStatus::Approved && t.waitlist_position.is_none())
+        .collect();
+    let team_name = |id: &Uuid| {
+        team_list
+            .iter()
+            .find(|t| &t.id == id)
+            .map(|t| t.name.clone())
+    };
+    let course_name = |id: &Uuid| {
+        course_list
+            .iter()
+            .find(|c| &c.id == id)
+            .map(|c| c.name.clone())
+    };
+
+    for hosting in plan.hosting_assignments.iter() {
+        let course = course_name(&hosting.course_id);
+        if course.is_none() {
+            details.push(detail(
+                "hosting_assignments",
+                format!("Hosting {} belongs to an unknown course", hosting.id),
+            ));
+        }
+        for id in std::iter::once(&hosting.team_id).chain(hosting.guest_team_ids.iter()) {
+            if team_name(id).is_none() {
+                details.push(detail(
+                    "hosting_assignments",
+                    format!(
+                        "Team {} at {} is not an approved team",
+                        id,
+                        course.clone().unwrap_or("an unknown course".to_string())
+                    ),
+                ));
+            }
+        }
+    }
+
+    for team in team_list.iter() {
+        let number_of_hostings = plan
+            .hosting_assignments
+            .iter()
+            .filter(|h| h.team_id == team.id)
+            .count();
+        if number_of_hostings != 1 {
+            details.push(detail(
+                "hosting_assignments",
+                format!("{} hosts {} times", team.name, number_of_hostings),
+            ));
+        }
+
+        let mut expected_path = vec![];
+        for course in course_list.iter() {
+            let hosting_list: Vec<&Hosting> = plan
+                .hosting_assignments
+                .iter()
+                .filter(|h| h.course_id == course.id)
+                .filter(|h| h.team_id == team.id || h.guest_team_ids.contains(&team.id))
+                .collect();
+            if hosting_list.len() != 1 {
+                details.push(detail(
+                    "hosting_assignments",
+                    format!(
+                        "{} is {} times at {}",
+                        team.name,
+                        hosting_list.len(),
+                        course.name
+                    ),
+                ));
+            }
+            expected_path.extend(hosting_list.iter().map(|h| (h.course_id, h.team_id)));
+        }
+
+        let path: Option<Vec<(Uuid, Uuid)>> = plan.walking_paths.get(&team.id).map(|steps| {
+            steps
+                .iter()
+                .map(|s| (s.course_id, s.host_team_id))
+                .collect()
+        });
+        if path != Some(expected_path) {
+            details.push(detail(
+                "walking_paths",
+                format!("Walking path of {} doesn't match its hostings", team.name),
+            ));
+        }
+    }
+
+    for course in course_list.iter() {
+        let hosting_list: Vec<&Hosting> = plan
+            .hosting_assignments
+            .iter()
+            .filter(|h| h.course_id == course.id)
+            .collect();
+        if hosting_list.is_empty() {
+            continue;
+        }
+        let number_of_guests: usize = hosting_list.iter().map(|h| h.guest_team_ids.len()).sum();
+        let min_guests = number_of_guests / hosting_list.len();
+        let max_guests = number_of_guests.div_ceil(hosting_list.len());
+        for hosting in hosting_list {
+            let guest_count = hosting.guest_team_ids.len();
+            if guest_count < min_guests || guest_count > max_guests {
+                details.push(detail(
+                    "hosting_assignments",
+                    format!(
+                        "{} has {} guests at {}, expected {} to {}",
+                        team_name(&hosting.team_id).unwrap_or(hosting.team_id.to_string()),
+                        guest_count,
+                        course.name,
+                        min_guests,
+                        max_guests
+                    ),
+                ));
+            }
+        }
+    }
+
+    details
+}
+
+// Replaces the plan of the project, hostings of a previous plan are dropped
+pub fn update(
+    db: &mut crate::db::Database,
+    cook_and_run_id: &Uuid,
+    plan: Plan,
+) -> Result<Plan, RestError> {
+    let db_cook_and_run = db
+        .select_cook_and_run(cook_and_run_id)
+        .map_err(|e| map_db_error(e, "Cook and run project not found"))?;
+    let cook_and_run = cook_and_run::get_cook_and_run(db, cook_and_run_id)?;
+
+    let details = validate(&cook_and_run, &plan);
+    if !details.is_empty() {
+        return Err(RestError::validation_error(details));
+    }
+
+    let plan_id = db_cook_and_run.plan.unwrap_or_else(Uuid::new_v4);
+    let db_plan = db::models::Plan {
+        id: plan_id,
+        access: Some(plan.access.iter().map(|a| Some(a.to())).collect()),
+        introduction: plan.introduction.clone(),
+        walking_paths: WalkingPathStep::to_map(&plan.walking_paths),
+        reveal_minutes: plan.reveal_minutes.map(|m| m as i32),
+    };
+
+    if db_cook_and_run.plan.is_some() {
+        db.update_plan(&db_plan)
+            .map_err(|e| map_db_error(e, "Database error while updating plan"))?;
+        let old_hosting_list = db
+            .select_all_hosting(&plan_id)
+            .map_err(|e| map_db_error(e, "Database error while selecting hosting assignments"))?;
+        for hosting in old_hosting_list {
+            db.delete_hosting(&hosting.id)
+                .map_err(|e| map_db_error(e, "Database error while deleting hosting"))?;
+        }
+    } else {
+        db.create_plan(&db_plan)
+            .map_err(|e| map_db_error(e, "Database error while creating plan"))?;
+        db.update_cook_and_run_plan(cook_and_run_id, Some(plan_id))
+            .map_err(|e| map_db_error(e, "Database error while linking plan"))?;
+    }
+
+    for hosting in plan.hosting_assignments.iter() {
+        let db_hosting = db::models::Hosting {
+            id: hosting.id,
+            plan_id,
+            course_id: hosting.course_id,
+            team_id: hosting.team_id,
+            guest_team_ids: serde_json::to_value(&hosting.guest_team_ids)
+                .expect("Failed to serialize guest_team_ids"),
+        };
+        db.create_hosting(&db_hosting)
+            .map_err(|e| map_db_error(e, "Database error while creating hosting"))?;
+    }
+
+    get_by_id(db, &plan_id)
+}
+
+pub fn to_json(plan: &Plan) -> serde_json::Value {
+    let hosting_list: Vec<serde_json::Value> = plan
+        .hosting_assignments
+        .iter()
+        .map(|h| {
+            serde_json::json!({
+                "id": h.id,
+                "course_id": h.course_id,
+                "team_id": h.team_id,
+                "guest_team_ids": h.guest_team_ids,
+            })
+        })
+        .collect();
+    let walking_paths: HashMap<Uuid, Vec<serde_json::Value>> = plan
+        .walking_paths
+        .iter()
+        .map(|(team_id, steps)| {
+            let step_list = steps
+                .iter()
+                .map(|s| {
+                    serde_json::json!({
+                        "course_id": s.course_id,
+                        "host_team_id": s.host_team_id,
+                    })
+                })
+                .collect();
+            (*team_id, step_list)
+        })
+        .collect();
+
+    serde_json::json!({
+        "access": plan.access.iter().map(Access::as_str).collect::<Vec<_>>(),
+        "introduction": plan.introduction,
+        "reveal_minutes": plan.reveal_minutes,
+        "hosting_assignments": hosting_list,
+        "walking_paths": walking_paths,
+    })
 }
 
 pub fn get_by_id(db: &mut crate::db::Database, plan_id: &Uuid) -> Result<Plan, RestError> {
@@ -142,5 +440,6 @@ pub fn get_by_id(db: &mut crate::db::Database, plan_id: &Uuid) -> Result<Plan, R
         introduction: db_plan.introduction,
         hosting_assignments,
         walking_paths,
+        reveal_minutes: db_plan.reveal_minutes.map(|m| m as u32),
     })
 }
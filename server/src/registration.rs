@@ -0,0 +1,307 @@
+use chrono::{NaiveDateTime, Utc};
+use serde::Deserialize;
+use tracing::{event, Level};
+use uuid::Uuid;
+
+use crate::{
+    auth::AuthUser,
+    cook_and_run,
+    db::{self, Database},
+    error::RestError,
+    mail::{self, MailKind, Mailer},
+    sharing::{self, RequiredField, ShareTeamConfig},
+    team::{self, Team},
+    waitlist,
+};
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct RegistrationAddress {
+    pub address: String,
+    pub latitude: f64,
+    pub longitude: f64,
+}
+
+// Data a team enters on the public registration page
+#[derive(Debug, Clone, Deserialize)]
+pub struct Registration {
+    pub name: String,
+    pub address: RegistrationAddress,
+    pub mail: Option<String>,
+    pub phone: Option<String>,
+    pub members: Option<u32>,
+    pub diets: Option<String>,
+}
+
+#[derive(Debug, Clone)]
+pub struct RegistrationResult {
+    pub team: Team,
+    pub management_token: String,
+}
+
+fn is_missing(value: &Option<String>) -> bool {
+    value.as_ref().map_or(true, |v| v.trim().is_empty())
+}
+
+impl Registration {
+    fn check_fields(&self, config: &ShareTeamConfig) -> Result<(), RestError> {
+        if self.name.trim().is_empty() {
+            return Err(RestError::bad_request_error("Team name is required!"));
+        }
+        if self.address.address.trim().is_empty()
+            || !(-90.0..=90.0).contains(&self.address.latitude)
+            || !(-180.0..=180.0).contains(&self.address.longitude)
+        {
+            return Err(RestError::bad_request_error("A valid address is required!"));
+        }
+
+        for field in config.required_fields.iter() {
+            let missing = match field {
+                RequiredField::Mail => is_missing(&self.mail),
+                RequiredField::Phone => is_missing(&self.phone),
+                RequiredField::Members => self.members.map_or(true, |m| m == 0),
+                RequiredField::Diets => is_missing(&self.diets),
+            };
+            if missing {
+                return Err(RestError::bad_request_error(&format!(
+                    "Field {:?} is required for this registration!",
+                    field
+                )));
+            }
+        }
+        Ok(())
+    }
+}
+
+// Checks deadline, login and required fields of a share config. The team limit is
+// handled by the waitlist. The user comes from the verified bearer token.
+pub fn check_registration(
+    config: &ShareTeamConfig,
+    user: Option<&AuthUser>,
+    registration: &Registration,
+    now: &NaiveDateTime,
+) -> Result<(), RestError> {
+    if let Some(deadline) = config.registration_deadline {
+        if now > &deadline {
+            return Err(RestError::forbidden_error(
+                "Registration deadline has passed!",
+            ));
+        }
+    }
+
+    if config.needs_login && user.is_none() {
+        return Err(RestError::unauthorized_error(
+            "Registration requires a logged in user!",
+        ));
+    }
+
+    registration.check_fields(config)
+}
+
+fn map_db_error(e: diesel::result::Error, text: &str) -> RestError {
+    match e {
+        diesel::result::Error::NotFound => RestError::not_found_error(text),
+        e => {
+            event!(Level::ERROR, "{}: {}", text, e);
+            RestError::internal_server_error(text)
+        }
+    }
+}
+
+fn create_management_token() -> String {
+    format!("{}{}", Uuid::new_v4().simple(), Uuid::new_v4().simple())
+}
+
+fn create_address(db: &mut Database, address: &RegistrationAddress) -> Result<Uuid, RestError> {
+    let id = Uuid::new_v4();
+    db.create_address(&db::models::Address {
+        id,
+        address_text: address.address.clone(),
+        latitude: address.latitude,
+        longitude: address.longitude,
+    })
+    .map_err(|e| map_db_error(e, "Could not create address in database"))?;
+    Ok(id)
+}
+
+fn update_address(
+    db: &mut Database,
+    id: Uuid,
+    address: &RegistrationAddress,
+) -> Result<(), RestError> {
+    db.update_address(&db::models::Address {
+        id,
+        address_text: address.address.clone(),
+        latitude: address.latitude,
+        longitude: address.longitude,
+    })
+    .map_err(|e| map_db_error(e, "Could not update address in database"))
+}
+
+pub fn register_team(
+    db: &mut Database,
+    mailer: &dyn Mailer,
+    share_id: &Uuid,
+    user: Option<AuthUser>,
+    registration: Registration,
+) -> Result<RegistrationResult, RestError> {
+    let cook_and_run = db
+        .select_cook_and_run_by_share(share_id)
+        .map_err(|e| map_db_error(e, "No cook and run project found for this share link"))?;
+    let config = sharing::get_by_id(db, share_id)?;
+
+    let now = Utc::now().naive_utc();
+    check_registration(&config, user.as_ref(), &registration, &now)?;
+
+    let address_id = create_address(db, &registration.address)?;
+    let management_token = create_management_token();
+    let team_id = Uuid::new_v4();
+    let waitlist_position = waitlist::create_team(
+        db,
+        db::models::Team {
+            id: team_id,
+            cook_and_run_id: cook_and_run.id,
+            created_by_user: user.map(|user| user.user_id),
+            name: registration.name,
+            created: now,
+            edited: now,
+            address: address_id,
+            mail: registration.mail,
+            phone: registration.phone,
+            members: registration.members.map(|m| m as i32),
+            diets: registration.diets,
+            needs_check: config.default_needs_check,
+            management_token: Some(management_token.clone()),
+            rejected: false,
+            waitlist_position: None,
+        },
+        config.max_teams,
+    )?;
+
+    event!(
+        Level::INFO,
+        "Team {} registered for cook and run project {} (waitlist position {:?})",
+        team_id,
+        cook_and_run.id,
+        waitlist_position
+    );
+
+    let team = team::get_by_id(db, &team_id)?;
+
+    // The registration is kept even if the confirmation could not be sent
+    let project = cook_and_run::get_cook_and_run(db, &cook_and_run.id)?;
+    if let Err(e) = mail::notify_team(
+        db,
+        mailer,
+        &project,
+        &team,
+        MailKind::RegistrationConfirmation,
+        &[("management_token", management_token.clone())],
+    ) {
+        event!(
+            Level::ERROR,
+            "Could not send registration confirmation to team {}: {:?}",
+            team_id,
+            e
+        );
+    }
+
+    Ok(RegistrationResult {
+        team,
+        management_token,
+    })
+}
+
+fn select_team_by_token(
+    db: &mut Database,
+    management_token: &str,
+) -> Result<db::models::Team, RestError> {
+    db.select_team_by_management_token(management_token)
+        .map_err(|e| map_db_error(e, "No team found for this management link"))
+}
+
+pub fn get_registered_team(db: &mut Database, management_token: &str) -> Result<Team, RestError> {
+    let db_team = select_team_by_token(db, management_token)?;
+    team::get_by_id(db, &db_team.id)
+}
+
+pub fn update_registered_team(
+    db: &mut Database,
+    management_token: &str,
+    registration: Registration,
+) -> Result<Team, RestError> {
+    let mut db_team = select_team_by_token(db, management_token)?;
+    let cook_and_run = db
+        .select_cook_and_run(&db_team.cook_and_run_id)
+        .map_err(|e| map_db_error(e, "Could not get cook and run project from database"))?;
+
+    // The share config may have been removed since the team registered
+    if let Some(share_id) = cook_and_run.share_team_config {
+        let config = sharing::get_by_id(db, &share_id)?;
+        let now = Utc::now().naive_utc();
+        if config.registration_deadline.is_some_and(|d| now > d) {
+            return Err(RestError::forbidden_error(
+                "Registration deadline has passed!",
+            ));
+        }
+        registration.check_fields(&config)?;
+    }
+
+    update_address(db, db_team.address, &registration.address)?;
+    db_team.name = registration.name;
+    db_team.edited = Utc::now().naive_utc();
+    db_team.mail = registration.mail;
+    db_team.phone = registration.phone;
+    db_team.members = registration.members.map(|m| m as i32);
+    db_team.diets = registration.diets;
+    db.update_team(&db_team)
+        .map_err(|e| map_db_error(e, "Could not update team in database"))?;
+
+    team::get_by_id(db, &db_team.id)
+}
+
+pub fn withdraw_registered_team(
+    db: &mut Database,
+    management_token: &str,
+) -> Result<(), RestError> {
+    let db_team = select_team_by_token(db, management_token)?;
+    // A withdrawn team frees its place for the next team on the waitlist
+    waitlist::delete_team(db, &db_team)?;
+
+    event!(
+        Level::INFO,
+        "Team {} withdrew from cook and run project {}",
+        db_team.id,
+        db_team.cook_and_run_id
+    );
+    Ok(())
+}
+
+pub fn to_json(team: &Team) -> serde_json::Value {
+    // Latest reason the organizer gave while reviewing the team
+    let review_reason = team
+        .note_list
+        .iter()
+        .filter(|n| n.headline == "Approved" || n.headline == "Rejected")
+        .max_by_key(|n| n.created)
+        .map(|n| n.content.clone());
+
+    serde_json::json!({
+        "id": team.id,
+        "name": team.name,
+        "created": team.created,
+        "edited": team.edited,
+        "address": {
+            "address": team.address.address,
+            "latitude": team.address.latitude,
+            "longitude": team.address.longitude,
+        },
+        "mail": team.mail,
+        "phone": team.phone,
+        "members": team.members,
+        "diets": team.diets,
+        "needs_check": team.needs_check,
+        "status": team.status.as_str(),
+        "review_reason": review_reason,
+        "waitlist_position": team.waitlist_position,
+    })
+}
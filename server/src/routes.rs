@@ -1,8 +1,20 @@
 use axum::{routing::{get, post, put, patch, delete}, Router, Json};
-use axum::http::StatusCode;
+use axum::http::{header, StatusCode};
 use crate::state::AppState;
+use crate::auth::AuthUser;
 use crate::error::{AppResult};
+use crate::calendar;
+use crate::cook_and_run;
+use crate::mail::{self, MailKind};
+use crate::plan;
+use crate::registration::{self, Registration};
+use crate::schedule_access;
+use crate::team;
+use crate::team_plan;
+use crate::waitlist;
+use serde::Deserialize;
 use std::sync::Arc;
+use uuid::Uuid;
 
 pub fn build_router(state: Arc<AppState>) -> Router {
     let mut app = Router::new();
@@ -24,15 +36,32 @@ pub fn build_router(state: Arc<AppState>) -> Router {
     app = app.route("/cook_and_run/{cookAndRunId}/team/{teamId}", get(get_cook_and_run_cookandrunid_team_teamid));
     app = app.route("/cook_and_run/{cookAndRunId}/team/{teamId}", patch(patch_cook_and_run_cookandrunid_team_teamid));
     app = app.route("/cook_and_run/{cookAndRunId}/team/{teamId}", delete(delete_cook_and_run_cookandrunid_team_teamid));
+    app = app.route("/cook_and_run/{cookAndRunId}/team/{teamId}/review", patch(patch_cook_and_run_cookandrunid_team_teamid_review));
     app = app.route("/cook_and_run/{cookAndRunId}/team/{teamId}/plan", get(get_cook_and_run_cookandrunid_team_teamid_plan));
+    app = app.route("/cook_and_run/{cookAndRunId}/team/{teamId}/schedule_token", post(post_cook_and_run_cookandrunid_team_teamid_schedule_token));
+    app = app.route("/cook_and_run/{cookAndRunId}/team/{teamId}/schedule_token", delete(delete_cook_and_run_cookandrunid_team_teamid_schedule_token));
+    app = app.route("/cook_and_run/{cookAndRunId}/team/{teamId}/mail_log", get(get_cook_and_run_cookandrunid_team_teamid_mail_log));
     app = app.route("/cook_and_run/{cookAndRunId}/team/{teamId}/notes", get(get_cook_and_run_cookandrunid_team_teamid_notes));
     app = app.route("/cook_and_run/{cookAndRunId}/team/{teamId}/note/{noteId}", post(post_cook_and_run_cookandrunid_team_teamid_note_noteid));
     app = app.route("/cook_and_run/{cookAndRunId}/team/{teamId}/note/{noteId}", delete(delete_cook_and_run_cookandrunid_team_teamid_note_noteid));
+    app = app.route("/cook_and_run/{cookAndRunId}/waitlist", get(get_cook_and_run_cookandrunid_waitlist));
+    app = app.route("/cook_and_run/{cookAndRunId}/waitlist", patch(patch_cook_and_run_cookandrunid_waitlist));
     app = app.route("/cook_and_run/{cookAndRunId}/share_team_config", patch(patch_cook_and_run_cookandrunid_share_team_config));
     app = app.route("/cook_and_run/{cookAndRunId}/share_team_config", get(get_cook_and_run_cookandrunid_share_team_config));
     app = app.route("/cook_and_run/{cookAndRunId}/share_team_config", delete(delete_cook_and_run_cookandrunid_share_team_config));
     app = app.route("/cook_and_run/{cookAndRunId}/plan", get(get_cook_and_run_cookandrunid_plan));
     app = app.route("/cook_and_run/{cookAndRunId}/plan", patch(patch_cook_and_run_cookandrunid_plan));
+    app = app.route("/cook_and_run/{cookAndRunId}/plan/notify", post(post_cook_and_run_cookandrunid_plan_notify));
+    app = app.route("/cook_and_run/{cookAndRunId}/schedule_tokens", post(post_cook_and_run_cookandrunid_schedule_tokens));
+    app = app.route("/cook_and_run/{cookAndRunId}/calendar.ics", get(get_cook_and_run_cookandrunid_calendar));
+    app = app.route("/cook_and_run/{cookAndRunId}/mail_template/{kind}", get(get_cook_and_run_cookandrunid_mail_template_kind));
+    app = app.route("/cook_and_run/{cookAndRunId}/mail_template/{kind}", put(put_cook_and_run_cookandrunid_mail_template_kind));
+    app = app.route("/share/{shareId}/registration", post(post_share_shareid_registration));
+    app = app.route("/registration/{managementToken}", get(get_registration_managementtoken));
+    app = app.route("/registration/{managementToken}", patch(patch_registration_managementtoken));
+    app = app.route("/registration/{managementToken}", delete(delete_registration_managementtoken));
+    app = app.route("/schedule/{scheduleToken}", get(get_schedule_scheduletoken));
+    app = app.route("/schedule/{scheduleToken}/calendar.ics", get(get_schedule_scheduletoken_calendar));
     app = app.route("/health", get(get_health));
     app.with_state(state)
 }
@@ -184,11 +213,36 @@ pub async fn delete_cook_and_run_cookandrunid_team_teamid(_state: axum::extract:
 }
 
 
-pub async fn get_cook_and_run_cookandrunid_team_teamid_plan(_state: axum::extract::State<State>) -> AppResult<(StatusCode, Json<serde_json::Value>)> {
-    Ok((StatusCode::NOT_IMPLEMENTED, Json(serde_json::json!({ 
-        "error": "Not Implemented", 
-        "operation": "get_cook_and_run_cookandrunid_team_teamid_plan" 
-    }))))
+#[derive(Deserialize)]
+pub struct ReviewRequest {
+    pub approved: bool,
+    pub reason: Option<String>,
+}
+
+pub async fn patch_cook_and_run_cookandrunid_team_teamid_review(
+    axum::extract::State(state): axum::extract::State<State>,
+    axum::extract::Path((cook_and_run_id, team_id)): axum::extract::Path<(Uuid, Uuid)>,
+    user: AuthUser,
+    Json(request): Json<ReviewRequest>,
+) -> AppResult<(StatusCode, Json<serde_json::Value>)> {
+    let mut db = state.db.clone();
+    cook_and_run::check_owner(&mut db, &cook_and_run_id, &user)?;
+    let team = team::review(&mut db, &cook_and_run_id, &team_id, request.approved, request.reason)?;
+    Ok((StatusCode::OK, Json(registration::to_json(&team))))
+}
+
+
+pub async fn get_cook_and_run_cookandrunid_team_teamid_plan(
+    axum::extract::State(state): axum::extract::State<State>,
+    axum::extract::Path((cook_and_run_id, team_id)): axum::extract::Path<(Uuid, Uuid)>,
+    user: AuthUser,
+) -> AppResult<(StatusCode, Json<serde_json::Value>)> {
+    let mut db = state.db.clone();
+    cook_and_run::check_owner(&mut db, &cook_and_run_id, &user)?;
+    // Course times are local times of the event
+    let now = chrono::Local::now().naive_local();
+    let team_plan = team_plan::get_team_plan(&mut db, &cook_and_run_id, &team_id, &now)?;
+    Ok((StatusCode::OK, Json(team_plan::to_json(&team_plan))))
 }
 
 
@@ -216,6 +270,33 @@ pub async fn delete_cook_and_run_cookandrunid_team_teamid_note_noteid(_state: ax
 }
 
 
+pub async fn get_cook_and_run_cookandrunid_waitlist(
+    axum::extract::State(state): axum::extract::State<State>,
+    axum::extract::Path(cook_and_run_id): axum::extract::Path<Uuid>,
+    user: AuthUser,
+) -> AppResult<(StatusCode, Json<serde_json::Value>)> {
+    let mut db = state.db.clone();
+    cook_and_run::check_owner(&mut db, &cook_and_run_id, &user)?;
+    let waitlist = waitlist::get_list(&mut db, &cook_and_run_id)?;
+    let waitlist: Vec<serde_json::Value> = waitlist.iter().map(registration::to_json).collect();
+    Ok((StatusCode::OK, Json(serde_json::json!(waitlist))))
+}
+
+
+pub async fn patch_cook_and_run_cookandrunid_waitlist(
+    axum::extract::State(state): axum::extract::State<State>,
+    axum::extract::Path(cook_and_run_id): axum::extract::Path<Uuid>,
+    user: AuthUser,
+    Json(team_id_list): Json<Vec<Uuid>>,
+) -> AppResult<(StatusCode, Json<serde_json::Value>)> {
+    let mut db = state.db.clone();
+    cook_and_run::check_owner(&mut db, &cook_and_run_id, &user)?;
+    let waitlist = waitlist::reorder(&mut db, &cook_and_run_id, &team_id_list)?;
+    let waitlist: Vec<serde_json::Value> = waitlist.iter().map(registration::to_json).collect();
+    Ok((StatusCode::OK, Json(serde_json::json!(waitlist))))
+}
+
+
 pub async fn patch_cook_and_run_cookandrunid_share_team_config(_state: axum::extract::State<State>) -> AppResult<(StatusCode, Json<serde_json::Value>)> {
     Ok((StatusCode::NOT_IMPLEMENTED, Json(serde_json::json!({ 
         "error": "Not Implemented", 
@@ -248,14 +329,254 @@ pub async fn get_cook_and_run_cookandrunid_plan(_state: axum::extract::State<Sta
 }
 
 
-pub async fn patch_cook_and_run_cookandrunid_plan(_state: axum::extract::State<State>) -> AppResult<(StatusCode, Json<serde_json::Value>)> {
-    Ok((StatusCode::NOT_IMPLEMENTED, Json(serde_json::json!({ 
-        "error": "Not Implemented", 
-        "operation": "patch_cook_and_run_cookandrunid_plan" 
+#[derive(Deserialize)]
+pub struct HostingRequest {
+    pub id: Uuid,
+    pub course_id: Uuid,
+    pub team_id: Uuid,
+    pub guest_team_ids: Vec<Uuid>,
+}
+
+#[derive(Deserialize)]
+pub struct WalkingPathStepRequest {
+    pub course_id: Uuid,
+    pub host_team_id: Uuid,
+}
+
+#[derive(Deserialize)]
+pub struct PlanRequest {
+    #[serde(default)]
+    pub access: Vec<String>,
+    pub introduction: Option<String>,
+    pub reveal_minutes: Option<u32>,
+    pub hosting_assignments: Vec<HostingRequest>,
+    pub walking_paths: std::collections::HashMap<Uuid, Vec<WalkingPathStepRequest>>,
+}
+
+pub async fn patch_cook_and_run_cookandrunid_plan(
+    axum::extract::State(state): axum::extract::State<State>,
+    axum::extract::Path(cook_and_run_id): axum::extract::Path<Uuid>,
+    user: AuthUser,
+    Json(request): Json<PlanRequest>,
+) -> AppResult<(StatusCode, Json<serde_json::Value>)> {
+    let mut db = state.db.clone();
+    cook_and_run::check_owner(&mut db, &cook_and_run_id, &user)?;
+    let access = request.access.iter().map(|a| plan::Access::parse(a)).collect::<Result<Vec<_>, _>>()?;
+    let plan = plan::Plan {
+        access,
+        introduction: request.introduction,
+        reveal_minutes: request.reveal_minutes,
+        hosting_assignments: request.hosting_assignments.into_iter().map(|h| plan::Hosting {
+            id: h.id,
+            course_id: h.course_id,
+            team_id: h.team_id,
+            guest_team_ids: h.guest_team_ids,
+        }).collect(),
+        walking_paths: request.walking_paths.into_iter().map(|(team_id, steps)| {
+            (team_id, steps.into_iter().map(|s| plan::WalkingPathStep {
+                course_id: s.course_id,
+                host_team_id: s.host_team_id,
+            }).collect())
+        }).collect(),
+    };
+    let plan = plan::update(&mut db, &cook_and_run_id, plan)?;
+    Ok((StatusCode::OK, Json(plan::to_json(&plan))))
+}
+
+
+pub async fn post_cook_and_run_cookandrunid_team_teamid_schedule_token(
+    axum::extract::State(state): axum::extract::State<State>,
+    axum::extract::Path((cook_and_run_id, team_id)): axum::extract::Path<(Uuid, Uuid)>,
+    user: AuthUser,
+) -> AppResult<(StatusCode, Json<serde_json::Value>)> {
+    let mut db = state.db.clone();
+    cook_and_run::check_owner(&mut db, &cook_and_run_id, &user)?;
+    let token = schedule_access::issue_token(&mut db, &cook_and_run_id, &team_id)?;
+    Ok((StatusCode::CREATED, Json(serde_json::json!({
+        "team_id": team_id,
+        "token": token
     }))))
 }
 
 
+pub async fn delete_cook_and_run_cookandrunid_team_teamid_schedule_token(
+    axum::extract::State(state): axum::extract::State<State>,
+    axum::extract::Path((cook_and_run_id, team_id)): axum::extract::Path<(Uuid, Uuid)>,
+    user: AuthUser,
+) -> AppResult<(StatusCode, Json<serde_json::Value>)> {
+    let mut db = state.db.clone();
+    cook_and_run::check_owner(&mut db, &cook_and_run_id, &user)?;
+    schedule_access::revoke_token(&mut db, &cook_and_run_id, &team_id)?;
+    Ok((StatusCode::NO_CONTENT, Json(serde_json::json!({}))))
+}
+
+
+pub async fn post_cook_and_run_cookandrunid_schedule_tokens(
+    axum::extract::State(state): axum::extract::State<State>,
+    axum::extract::Path(cook_and_run_id): axum::extract::Path<Uuid>,
+    user: AuthUser,
+) -> AppResult<(StatusCode, Json<serde_json::Value>)> {
+    let mut db = state.db.clone();
+    cook_and_run::check_owner(&mut db, &cook_and_run_id, &user)?;
+    let token_list = schedule_access::issue_missing_tokens(&mut db, &cook_and_run_id)?;
+    Ok((StatusCode::OK, Json(serde_json::json!(token_list
+        .iter()
+        .map(|(team_id, token)| serde_json::json!({ "team_id": team_id, "token": token }))
+        .collect::<Vec<_>>()))))
+}
+
+
+pub async fn get_cook_and_run_cookandrunid_calendar(
+    axum::extract::State(state): axum::extract::State<State>,
+    axum::extract::Path(cook_and_run_id): axum::extract::Path<Uuid>,
+    user: AuthUser,
+) -> AppResult<(StatusCode, [(header::HeaderName, &'static str); 1], String)> {
+    let mut db = state.db.clone();
+    cook_and_run::check_owner(&mut db, &cook_and_run_id, &user)?;
+    let cook_and_run = cook_and_run::get_cook_and_run(&mut db, &cook_and_run_id)?;
+    let ics = calendar::project_calendar(&cook_and_run)?;
+    Ok((StatusCode::OK, [(header::CONTENT_TYPE, "text/calendar; charset=utf-8")], ics))
+}
+
+
+pub async fn get_cook_and_run_cookandrunid_team_teamid_mail_log(
+    axum::extract::State(state): axum::extract::State<State>,
+    axum::extract::Path((cook_and_run_id, team_id)): axum::extract::Path<(Uuid, Uuid)>,
+    user: AuthUser,
+) -> AppResult<(StatusCode, Json<serde_json::Value>)> {
+    let mut db = state.db.clone();
+    cook_and_run::check_owner(&mut db, &cook_and_run_id, &user)?;
+    let log_list = mail::get_log_list(&mut db, &cook_and_run_id, &team_id)?;
+    Ok((StatusCode::OK, Json(serde_json::json!(log_list.iter().map(mail::log_to_json).collect::<Vec<_>>()))))
+}
+
+
+#[derive(Deserialize)]
+pub struct NotifyRequest {
+    pub kind: String,
+}
+
+pub async fn post_cook_and_run_cookandrunid_plan_notify(
+    axum::extract::State(state): axum::extract::State<State>,
+    axum::extract::Path(cook_and_run_id): axum::extract::Path<Uuid>,
+    user: AuthUser,
+    Json(request): Json<NotifyRequest>,
+) -> AppResult<(StatusCode, Json<serde_json::Value>)> {
+    let mut db = state.db.clone();
+    cook_and_run::check_owner(&mut db, &cook_and_run_id, &user)?;
+    let kind = MailKind::parse(&request.kind)?;
+    let mailer = mail::mailer_from_env();
+    let log_list = mail::notify_all(&mut db, mailer.as_ref(), &cook_and_run_id, kind)?;
+    Ok((StatusCode::OK, Json(serde_json::json!(log_list.iter().map(mail::log_to_json).collect::<Vec<_>>()))))
+}
+
+
+pub async fn get_cook_and_run_cookandrunid_mail_template_kind(
+    axum::extract::State(state): axum::extract::State<State>,
+    axum::extract::Path((cook_and_run_id, kind)): axum::extract::Path<(Uuid, String)>,
+    user: AuthUser,
+) -> AppResult<(StatusCode, Json<serde_json::Value>)> {
+    let mut db = state.db.clone();
+    cook_and_run::check_owner(&mut db, &cook_and_run_id, &user)?;
+    let template = mail::get_template(&mut db, &cook_and_run_id, MailKind::parse(&kind)?)?;
+    Ok((StatusCode::OK, Json(mail::template_to_json(&template))))
+}
+
+
+#[derive(Deserialize)]
+pub struct MailTemplateRequest {
+    pub subject: String,
+    pub body: String,
+}
+
+pub async fn put_cook_and_run_cookandrunid_mail_template_kind(
+    axum::extract::State(state): axum::extract::State<State>,
+    axum::extract::Path((cook_and_run_id, kind)): axum::extract::Path<(Uuid, String)>,
+    user: AuthUser,
+    Json(request): Json<MailTemplateRequest>,
+) -> AppResult<(StatusCode, Json<serde_json::Value>)> {
+    let mut db = state.db.clone();
+    cook_and_run::check_owner(&mut db, &cook_and_run_id, &user)?;
+    let template = mail::update_template(&mut db, &cook_and_run_id, MailKind::parse(&kind)?, &request.subject, &request.body)?;
+    Ok((StatusCode::OK, Json(mail::template_to_json(&template))))
+}
+
+
+pub async fn post_share_shareid_registration(
+    axum::extract::State(state): axum::extract::State<State>,
+    axum::extract::Path(share_id): axum::extract::Path<Uuid>,
+    user: Option<AuthUser>,
+    Json(request): Json<Registration>,
+) -> AppResult<(StatusCode, Json<serde_json::Value>)> {
+    let mut db = state.db.clone();
+    let mailer = mail::mailer_from_env();
+    let result = registration::register_team(&mut db, mailer.as_ref(), &share_id, user, request)?;
+    Ok((StatusCode::CREATED, Json(serde_json::json!({
+        "team": registration::to_json(&result.team),
+        "management_token": result.management_token
+    }))))
+}
+
+
+pub async fn get_registration_managementtoken(
+    axum::extract::State(state): axum::extract::State<State>,
+    axum::extract::Path(management_token): axum::extract::Path<String>,
+) -> AppResult<(StatusCode, Json<serde_json::Value>)> {
+    let mut db = state.db.clone();
+    let team = registration::get_registered_team(&mut db, &management_token)?;
+    Ok((StatusCode::OK, Json(registration::to_json(&team))))
+}
+
+
+pub async fn patch_registration_managementtoken(
+    axum::extract::State(state): axum::extract::State<State>,
+    axum::extract::Path(management_token): axum::extract::Path<String>,
+    Json(request): Json<Registration>,
+) -> AppResult<(StatusCode, Json<serde_json::Value>)> {
+    let mut db = state.db.clone();
+    let team = registration::update_registered_team(&mut db, &management_token, request)?;
+    Ok((StatusCode::OK, Json(registration::to_json(&team))))
+}
+
+
+pub async fn delete_registration_managementtoken(
+    axum::extract::State(state): axum::extract::State<State>,
+    axum::extract::Path(management_token): axum::extract::Path<String>,
+) -> AppResult<(StatusCode, Json<serde_json::Value>)> {
+    let mut db = state.db.clone();
+    registration::withdraw_registered_team(&mut db, &management_token)?;
+    Ok((StatusCode::NO_CONTENT, Json(serde_json::json!({}))))
+}
+
+
+pub async fn get_schedule_scheduletoken(
+    axum::extract::State(state): axum::extract::State<State>,
+    axum::extract::Path(schedule_token): axum::extract::Path<String>,
+    user: Option<AuthUser>,
+) -> AppResult<(StatusCode, Json<serde_json::Value>)> {
+    let mut db = state.db.clone();
+    // Course times are local times of the event
+    let now = chrono::Local::now().naive_local();
+    let user_id = user.as_ref().map(|user| user.user_id.as_str());
+    let schedule = schedule_access::get_schedule(&mut db, &schedule_token, user_id, &now)?;
+    Ok((StatusCode::OK, Json(schedule_access::to_json(&schedule))))
+}
+
+
+pub async fn get_schedule_scheduletoken_calendar(
+    axum::extract::State(state): axum::extract::State<State>,
+    axum::extract::Path(schedule_token): axum::extract::Path<String>,
+    user: Option<AuthUser>,
+) -> AppResult<(StatusCode, [(header::HeaderName, &'static str); 1], String)> {
+    let mut db = state.db.clone();
+    // Course times are local times of the event
+    let now = chrono::Local::now().naive_local();
+    let user_id = user.as_ref().map(|user| user.user_id.as_str());
+    let schedule = schedule_access::get_schedule(&mut db, &schedule_token, user_id, &now)?;
+    Ok((StatusCode::OK, [(header::CONTENT_TYPE, "text/calendar; charset=utf-8")], calendar::team_calendar(&schedule)))
+}
+
+
 pub async fn get_health(_state: axum::extract::State<State>) -> AppResult<(StatusCode, Json<serde_json::Value>)> {
     Ok((StatusCode::NOT_IMPLEMENTED, Json(serde_json::json!({ 
         "error": "Not Implemented", 
@@ -0,0 +1,195 @@
+use chrono::{NaiveDateTime, Utc};
+use tracing::{event, Level};
+use uuid::Uuid;
+
+use crate::{
+    cook_and_run::{self, CookAndRun},
+    db::{self, Database},
+    error::RestError,
+    plan::Access,
+    team::Team,
+    team_plan::{self, TeamPlan},
+};
+
+#[derive(Debug, Clone)]
+pub struct TeamSchedule {
+    pub cook_and_run_id: Uuid,
+    pub cook_and_run_name: String,
+    pub occur: NaiveDateTime,
+    pub team: Team,
+    pub team_plan: TeamPlan,
+}
+
+fn map_db_error(e: diesel::result::Error, text: &str) -> RestError {
+    match e {
+        diesel::result::Error::NotFound => RestError::not_found_error(text),
+        e => {
+            event!(Level::ERROR, "{}: {}", text, e);
+            RestError::internal_server_error(text)
+        }
+    }
+}
+
+fn create_token() -> String {
+    format!("{}{}", Uuid::new_v4().simple(), Uuid::new_v4().simple())
+}
+
+fn check_team(db: &mut Database, cook_and_run_id: &Uuid, team_id: &Uuid) -> Result<(), RestError> {
+    let team = db
+        .select_team(team_id)
+        .map_err(|e| map_db_error(e, "Team not found!"))?;
+    if &team.cook_and_run_id != cook_and_run_id {
+        return Err(RestError::not_found_error(
+            "Team not found in this project!",
+        ));
+    }
+    Ok(())
+}
+
+fn revoke(db: &mut Database, team_id: &Uuid) -> Result<(), RestError> {
+    db.revoke_schedule_token(team_id, &Utc::now().naive_utc())
+        .map_err(|e| map_db_error(e, "Could not revoke schedule link in database"))
+}
+
+fn create(db: &mut Database, team_id: &Uuid) -> Result<String, RestError> {
+    let token = create_token();
+    db.create_schedule_token(&db::models::ScheduleToken {
+        token: token.clone(),
+        team_id: *team_id,
+        created: Utc::now().naive_utc(),
+        revoked: None,
+    })
+    .map_err(|e| map_db_error(e, "Could not create schedule link in database"))?;
+    Ok(token)
+}
+
+// Issues a new schedule link for a team, a previous link stops working
+pub fn issue_token(
+    db: &mut Database,
+    cook_and_run_id: &Uuid,
+    team_id: &Uuid,
+) -> Result<String, RestError> {
+    check_team(db, cook_and_run_id, team_id)?;
+    revoke(db, team_id)?;
+    let token = create(db, team_id)?;
+    event!(Level::INFO, "Schedule link issued for team {}", team_id);
+    Ok(token)
+}
+
+// Returns the current schedule link of every team of the plan, missing links are issued
+pub fn issue_missing_tokens(
+    db: &mut Database,
+    cook_and_run_id: &Uuid,
+) -> Result<Vec<(Uuid, String)>, RestError> {
+    let cook_and_run = cook_and_run::get_cook_and_run(db, cook_and_run_id)?;
+    let Some(plan) = &cook_and_run.plan else {
+        return Err(RestError::bad_request_error(
+            "No plan has been calculated yet!",
+        ));
+    };
+
+    let mut token_list = Vec::new();
+    for team in cook_and_run
+        .team_list
+        .iter()
+        .filter(|t| plan.walking_paths.contains_key(&t.id))
+    {
+        let active = db
+            .select_active_schedule_token(&team.id)
+            .map_err(|e| map_db_error(e, "Database error while selecting schedule link"))?;
+        let token = match active {
+            Some(active) => active.token,
+            None => create(db, &team.id)?,
+        };
+        token_list.push((team.id, token));
+    }
+    Ok(token_list)
+}
+
+pub fn revoke_token(
+    db: &mut Database,
+    cook_and_run_id: &Uuid,
+    team_id: &Uuid,
+) -> Result<(), RestError> {
+    check_team(db, cook_and_run_id, team_id)?;
+    revoke(db, team_id)?;
+    event!(Level::INFO, "Schedule link revoked for team {}", team_id);
+    Ok(())
+}
+
+// Link access lets everybody with the link see the schedule, account access only the user
+// who registered the team
+fn check_access(
+    cook_and_run: &CookAndRun,
+    team: &Team,
+    user_id: Option<&str>,
+) -> Result<(), RestError> {
+    let access_list = cook_and_run
+        .plan
+        .as_ref()
+        .map(|p| p.access.clone())
+        .unwrap_or_default();
+
+    if access_list.iter().any(|a| matches!(a, Access::Link)) {
+        return Ok(());
+    }
+    if access_list.iter().any(|a| matches!(a, Access::Account)) {
+        return match (user_id, &team.created_by_user) {
+            (Some(user_id), Some(created_by_user)) if user_id == created_by_user => Ok(()),
+            _ => Err(RestError::unauthorized_error(
+                "This schedule is only available to the logged in team!",
+            )),
+        };
+    }
+    Err(RestError::forbidden_error(
+        "Schedules of this project are not shared with the teams!",
+    ))
+}
+
+pub fn get_schedule(
+    db: &mut Database,
+    token: &str,
+    user_id: Option<&str>,
+    now: &NaiveDateTime,
+) -> Result<TeamSchedule, RestError> {
+    let schedule_token = db
+        .select_schedule_token(token)
+        .map_err(|e| map_db_error(e, "Schedule link not found!"))?;
+    if schedule_token.revoked.is_some() {
+        return Err(RestError::forbidden_error(
+            "Schedule link has been revoked!",
+        ));
+    }
+
+    let db_team = db
+        .select_team(&schedule_token.team_id)
+        .map_err(|e| map_db_error(e, "Team not found!"))?;
+    let cook_and_run = cook_and_run::get_cook_and_run(db, &db_team.cook_and_run_id)?;
+    let team = cook_and_run
+        .team_list
+        .iter()
+        .find(|t| t.id == db_team.id)
+        .cloned()
+        .ok_or_else(|| RestError::not_found_error("Team not found!"))?;
+
+    check_access(&cook_and_run, &team, user_id)?;
+
+    let team_plan = team_plan::build_team_plan(&cook_and_run, &team.id, now)?;
+    Ok(TeamSchedule {
+        cook_and_run_id: cook_and_run.id,
+        cook_and_run_name: cook_and_run.name,
+        occur: cook_and_run.occur,
+        team,
+        team_plan,
+    })
+}
+
+pub fn to_json(schedule: &TeamSchedule) -> serde_json::Value {
+    serde_json::json!({
+        "cook_and_run_id": schedule.cook_and_run_id,
+        "cook_and_run_name": schedule.cook_and_run_name,
+        "occur": schedule.occur,
+        "team": crate::registration::to_json(&schedule.team),
+        "plan": team_plan::to_json(&schedule.team_plan),
+    })
+}
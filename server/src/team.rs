@@ -1,4 +1,4 @@
-use chrono::NaiveDateTime;
+use chrono::{NaiveDateTime, Utc};
 use tracing::event;
 use uuid::Uuid;
 
@@ -7,8 +7,36 @@ use crate::{
     db::{self, Database},
     error::RestError,
     note::{self, Note},
+    waitlist,
 };
 
+#[derive(Debug, Clone, PartialEq)]
+pub enum TeamStatus {
+    Pending,
+    Approved,
+    Rejected,
+}
+
+impl TeamStatus {
+    fn from(needs_check: bool, rejected: bool) -> Self {
+        if rejected {
+            TeamStatus::Rejected
+        } else if needs_check {
+            TeamStatus::Pending
+        } else {
+            TeamStatus::Approved
+        }
+    }
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            TeamStatus::Pending => "pending",
+            TeamStatus::Approved => "approved",
+            TeamStatus::Rejected => "rejected",
+        }
+    }
+}
+
 #[derive(Debug, Clone)]
 pub struct Team {
     pub id: Uuid,
@@ -22,11 +50,13 @@ pub struct Team {
     pub members: Option<u32>,
     pub diets: Option<String>,
     pub needs_check: bool,
+    pub status: TeamStatus,
+    pub waitlist_position: Option<u32>,
     pub note_list: Vec<Note>,
 }
 
 impl Team {
-    fn from(db_team: db::models::Team, address: Address, note_list: Vec<Note>) -> Self {
+    pub(crate) fn from(db_team: db::models::Team, address: Address, note_list: Vec<Note>) -> Self {
         Team {
             id: db_team.id,
             created_by_user: db_team.created_by_user,
@@ -39,6 +69,8 @@ impl Team {
             members: db_team.members.map(|m| m as u32),
             diets: db_team.diets,
             needs_check: db_team.needs_check,
+            status: TeamStatus::from(db_team.needs_check, db_team.rejected),
+            waitlist_position: db_team.waitlist_position.map(|p| p as u32),
             note_list,
         }
     }
@@ -65,3 +97,99 @@ pub fn get_list(db: &mut Database, cook_and_run_id: &Uuid) -> Result<Vec<Team>,
     }
     Ok(result)
 }
+
+pub fn get_by_id(db: &mut Database, team_id: &Uuid) -> Result<Team, RestError> {
+    let team = db.select_team(team_id).map_err(|e| {
+        event!(
+            tracing::Level::ERROR,
+            "Database error while selecting team with id {}: {}",
+            team_id,
+            e
+        );
+        RestError::InternalServer {
+            message: "Database error while selecting team!".to_string(),
+        }
+    })?;
+
+    let address = address::get_by_id(db, &team.address)?;
+    let note_list = note::get_list_by_team_id(db, &team.id)?;
+    Ok(Team::from(team, address, note_list))
+}
+
+// Approves or rejects a team, the optional reason is kept as note of the team
+pub fn review(
+    db: &mut Database,
+    cook_and_run_id: &Uuid,
+    team_id: &Uuid,
+    approved: bool,
+    reason: Option<String>,
+) -> Result<Team, RestError> {
+    let team = db.select_team(team_id).map_err(|e| match e {
+        diesel::result::Error::NotFound => RestError::not_found_error("Team not found!"),
+        e => {
+            event!(
+                tracing::Level::ERROR,
+                "Database error while selecting team with id {}: {}",
+                team_id,
+                e
+            );
+            RestError::internal_server_error("Database error while selecting team!")
+        }
+    })?;
+    if &team.cook_and_run_id != cook_and_run_id {
+        return Err(RestError::not_found_error(
+            "Team not found in this project!",
+        ));
+    }
+
+    if approved {
+        waitlist::approve_team(db, cook_and_run_id, team_id)?;
+    } else {
+        db.update_team_review(team_id, false, true).map_err(|e| {
+            event!(
+                tracing::Level::ERROR,
+                "Database error while reviewing team with id {}: {}",
+                team_id,
+                e
+            );
+            RestError::internal_server_error("Database error while reviewing team!")
+        })?;
+
+        // A rejected team leaves the waitlist and frees its place for the next team
+        if team.waitlist_position.is_some() {
+            db.update_team_waitlist_position(team_id, None)
+                .map_err(|e| {
+                    event!(
+                        tracing::Level::ERROR,
+                        "Database error while removing team with id {} from the waitlist: {}",
+                        team_id,
+                        e
+                    );
+                    RestError::internal_server_error("Database error while updating waitlist!")
+                })?;
+        }
+        waitlist::promote(db, cook_and_run_id)?;
+    }
+
+    if let Some(reason) = reason.filter(|r| !r.trim().is_empty()) {
+        let headline = if approved { "Approved" } else { "Rejected" };
+        db.create_note(&db::models::Note {
+            id: Uuid::new_v4(),
+            team_id: *team_id,
+            headline: headline.to_string(),
+            content: reason,
+            created: Utc::now().naive_utc(),
+        })
+        .map_err(|e| {
+            event!(
+                tracing::Level::ERROR,
+                "Database error while creating review note for team with id {}: {}",
+                team_id,
+                e
+            );
+            RestError::internal_server_error("Database error while creating review note!")
+        })?;
+    }
+
+    get_by_id(db, team_id)
+}
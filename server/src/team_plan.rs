@@ -0,0 +1,185 @@
+use chrono::{Duration, NaiveDateTime, NaiveTime};
+use tracing::{event, Level};
+use uuid::Uuid;
+
+use crate::{
+    address::Address,
+    cook_and_run::{self, CookAndRun},
+    course::Course,
+    error::RestError,
+    team::Team,
+};
+
+#[derive(Debug, Clone)]
+pub struct TeamPlanStep {
+    pub course_id: Uuid,
+    pub course_name: String,
+    pub course_time: String,
+    pub you_are_hosting: bool,
+    pub reveal_time: Option<NaiveDateTime>,
+    // Host and guests stay empty until the address is revealed
+    pub host: Option<Team>,
+    pub guest_name_list: Vec<String>,
+}
+
+#[derive(Debug, Clone)]
+pub struct TeamPlan {
+    pub team_id: Uuid,
+    pub introduction: Option<String>,
+    pub start_point: Option<Address>,
+    pub end_point: Option<Address>,
+    pub step_list: Vec<TeamPlanStep>,
+    pub next_reveal: Option<NaiveDateTime>,
+}
+
+pub fn course_start(occur: &NaiveDateTime, course_time: &str) -> Option<NaiveDateTime> {
+    let time = NaiveTime::parse_from_str(course_time, "%H:%M")
+        .or_else(|_| NaiveTime::parse_from_str(course_time, "%H:%M:%S"));
+    match time {
+        Ok(time) => Some(occur.date().and_time(time)),
+        Err(e) => {
+            event!(
+                Level::WARN,
+                "Course time {} could not be parsed: {}",
+                course_time,
+                e
+            );
+            None
+        }
+    }
+}
+
+// Time from which the guests see the address of a course, None if it is shown right away
+pub fn reveal_time(
+    occur: &NaiveDateTime,
+    course_time: &str,
+    reveal_minutes: Option<u32>,
+) -> Option<NaiveDateTime> {
+    let minutes = reveal_minutes?;
+    course_start(occur, course_time).map(|start| start - Duration::minutes(minutes as i64))
+}
+
+fn find_course<'a>(cook_and_run: &'a CookAndRun, course_id: &Uuid) -> Option<&'a Course> {
+    cook_and_run.course_list.iter().find(|c| &c.id == course_id)
+}
+
+fn find_team<'a>(cook_and_run: &'a CookAndRun, team_id: &Uuid) -> Option<&'a Team> {
+    cook_and_run.team_list.iter().find(|t| &t.id == team_id)
+}
+
+pub fn get_team_plan(
+    db: &mut crate::db::Database,
+    cook_and_run_id: &Uuid,
+    team_id: &Uuid,
+    now: &NaiveDateTime,
+) -> Result<TeamPlan, RestError> {
+    let cook_and_run = cook_and_run::get_cook_and_run(db, cook_and_run_id)?;
+    build_team_plan(&cook_and_run, team_id, now)
+}
+
+pub fn build_team_plan(
+    cook_and_run: &CookAndRun,
+    team_id: &Uuid,
+    now: &NaiveDateTime,
+) -> Result<TeamPlan, RestError> {
+    let plan = cook_and_run
+        .plan
+        .as_ref()
+        .ok_or_else(|| RestError::not_found_error("No plan has been calculated yet!"))?;
+    let walking_path = plan
+        .walking_paths
+        .get(team_id)
+        .ok_or_else(|| RestError::not_found_error("Team is not part of the plan!"))?;
+
+    let mut step_list = Vec::new();
+    for step in walking_path {
+        let Some(course) = find_course(cook_and_run, &step.course_id) else {
+            continue;
+        };
+        let you_are_hosting = &step.host_team_id == team_id;
+        let reveal_time = reveal_time(&cook_and_run.occur, &course.time, plan.reveal_minutes)
+            .filter(|r| !you_are_hosting && r > now);
+
+        let (host, guest_name_list) = if reveal_time.is_some() {
+            (None, vec![])
+        } else {
+            let guest_name_list = plan
+                .hosting_assignments
+                .iter()
+                .find(|h| h.course_id == step.course_id && h.team_id == step.host_team_id)
+                .map(|h| {
+                    h.guest_team_ids
+                        .iter()
+                        .filter_map(|id| find_team(cook_and_run, id))
+                        .map(|t| t.name.clone())
+                        .collect()
+                })
+                .unwrap_or_default();
+            (
+                find_team(cook_and_run, &step.host_team_id).cloned(),
+                guest_name_list,
+            )
+        };
+
+        step_list.push(TeamPlanStep {
+            course_id: course.id,
+            course_name: course.name.clone(),
+            course_time: course.time.clone(),
+            you_are_hosting,
+            reveal_time,
+            host,
+            guest_name_list,
+        });
+    }
+
+    let next_reveal = step_list.iter().filter_map(|s| s.reveal_time).min();
+    Ok(TeamPlan {
+        team_id: *team_id,
+        introduction: plan.introduction.clone(),
+        start_point: cook_and_run.start_point.clone(),
+        end_point: cook_and_run.end_point.clone(),
+        step_list,
+        next_reveal,
+    })
+}
+
+fn address_to_json(address: &Address) -> serde_json::Value {
+    serde_json::json!({
+        "address": address.address,
+        "latitude": address.latitude,
+        "longitude": address.longitude,
+    })
+}
+
+pub fn to_json(team_plan: &TeamPlan) -> serde_json::Value {
+    let step_list: Vec<serde_json::Value> = team_plan
+        .step_list
+        .iter()
+        .map(|s| {
+            serde_json::json!({
+                "course_id": s.course_id,
+                "course_name": s.course_name,
+                "course_time": s.course_time,
+                "you_are_hosting": s.you_are_hosting,
+                "revealed": s.reveal_time.is_none(),
+                "reveal_time": s.reveal_time,
+                "host": s.host.as_ref().map(|h| serde_json::json!({
+                    "id": h.id,
+                    "name": h.name,
+                    "phone": h.phone,
+                    "address": address_to_json(&h.address),
+                })),
+                "guests": s.guest_name_list,
+            })
+        })
+        .collect();
+
+    serde_json::json!({
+        "team_id": team_plan.team_id,
+        "introduction": team_plan.introduction,
+        "start_point": team_plan.start_point.as_ref().map(address_to_json),
+        "end_point": team_plan.end_point.as_ref().map(address_to_json),
+        "steps": step_list,
+        "next_reveal": team_plan.next_reveal,
+    })
+}
@@ -0,0 +1,116 @@
+use tracing::{event, Level};
+use uuid::Uuid;
+
+use crate::{
+    address,
+    db::{self, Database},
+    error::RestError,
+    note, sharing,
+    team::Team,
+};
+
+fn map_db_error(e: diesel::result::Error, text: &str) -> RestError {
+    event!(Level::ERROR, "{}: {}", text, e);
+    RestError::internal_server_error(text)
+}
+
+fn select_waitlist(
+    db: &mut Database,
+    cook_and_run_id: &Uuid,
+) -> Result<Vec<db::models::Team>, RestError> {
+    db.select_waitlist(cook_and_run_id)
+        .map_err(|e| map_db_error(e, "Database error while selecting waitlist"))
+}
+
+fn get_max_teams(db: &mut Database, cook_and_run_id: &Uuid) -> Result<Option<u32>, RestError> {
+    let cook_and_run = db
+        .select_cook_and_run(cook_and_run_id)
+        .map_err(|e| map_db_error(e, "Database error while selecting cook and run project"))?;
+    let config = cook_and_run
+        .share_team_config
+        .map(|s| sharing::get_by_id(db, &s))
+        .transpose()?;
+    Ok(config.and_then(|c| c.max_teams))
+}
+
+pub fn get_list(db: &mut Database, cook_and_run_id: &Uuid) -> Result<Vec<Team>, RestError> {
+    let mut result = Vec::new();
+    for team in select_waitlist(db, cook_and_run_id)? {
+        let address = address::get_by_id(db, &team.address)?;
+        let note_list = note::get_list_by_team_id(db, &team.id)?;
+        result.push(Team::from(team, address, note_list));
+    }
+    Ok(result)
+}
+
+// Creates a new team, on the waitlist if the team limit is reached or others are
+// waiting. Returns the waitlist position, None if the team got a regular place.
+pub fn create_team(
+    db: &mut Database,
+    team: db::models::Team,
+    max_teams: Option<u32>,
+) -> Result<Option<u32>, RestError> {
+    db.create_team_with_waitlist(team, max_teams.map(|m| m as i64))
+        .map(|p| p.map(|p| p as u32))
+        .map_err(|e| map_db_error(e, "Could not create team in database"))
+}
+
+fn log_promoted(cook_and_run_id: &Uuid, promoted_list: &[Uuid]) {
+    for team_id in promoted_list {
+        event!(
+            Level::INFO,
+            "Team {} moved from the waitlist to cook and run project {}",
+            team_id,
+            cook_and_run_id
+        );
+    }
+}
+
+// Moves waitlisted teams to a regular place as long as places are free
+pub fn promote(db: &mut Database, cook_and_run_id: &Uuid) -> Result<Vec<Uuid>, RestError> {
+    let max_teams = get_max_teams(db, cook_and_run_id)?;
+    let promoted_list = db
+        .promote_waitlist(cook_and_run_id, max_teams.map(|m| m as i64))
+        .map_err(|e| map_db_error(e, "Database error while promoting waitlisted teams"))?;
+    log_promoted(cook_and_run_id, &promoted_list);
+    Ok(promoted_list)
+}
+
+// Approves a team, a rejected team goes to the waitlist if the team limit is reached or
+// others are waiting
+pub fn approve_team(
+    db: &mut Database,
+    cook_and_run_id: &Uuid,
+    team_id: &Uuid,
+) -> Result<Option<u32>, RestError> {
+    let max_teams = get_max_teams(db, cook_and_run_id)?;
+    db.approve_team_with_waitlist(cook_and_run_id, team_id, max_teams.map(|m| m as i64))
+        .map(|p| p.map(|p| p as u32))
+        .map_err(|e| map_db_error(e, "Database error while approving team"))
+}
+
+// Deletes a team, a freed place goes to the next team on the waitlist
+pub fn delete_team(db: &mut Database, team: &db::models::Team) -> Result<Vec<Uuid>, RestError> {
+    let max_teams = get_max_teams(db, &team.cook_and_run_id)?;
+    let promoted_list = db
+        .delete_team_with_waitlist(team, max_teams.map(|m| m as i64))
+        .map_err(|e| map_db_error(e, "Could not delete team in database"))?;
+    log_promoted(&team.cook_and_run_id, &promoted_list);
+    Ok(promoted_list)
+}
+
+pub fn reorder(
+    db: &mut Database,
+    cook_and_run_id: &Uuid,
+    team_id_list: &[Uuid],
+) -> Result<Vec<Team>, RestError> {
+    let reordered = db
+        .reorder_waitlist(cook_and_run_id, team_id_list)
+        .map_err(|e| map_db_error(e, "Database error while reordering waitlist"))?;
+    if !reordered {
+        return Err(RestError::bad_request_error(
+            "New order has to contain every waitlisted team once!",
+        ));
+    }
+    get_list(db, cook_and_run_id)
+}
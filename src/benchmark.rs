@@ -0,0 +1,221 @@
+use std::{
+    collections::HashMap,
+    fmt,
+    time::{Duration, Instant},
+};
+
+use crate::{
+    calculator::{calc_distance, solve_exact, Calculator, Plan},
+    scenario::Scenario,
+};
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum SolverKind {
+    Genetic,
+    Exact,
+}
+
+impl fmt::Display for SolverKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            SolverKind::Genetic => write!(f, "genetic"),
+            SolverKind::Exact => write!(f, "exact"),
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct BenchmarkResult {
+    pub scenario: String,
+    pub solver: SolverKind,
+    // Longest walk of a team, in course order, None if no plan was found
+    pub score: Option<f64>,
+    pub runtime: Duration,
+    pub violation_list: Vec<String>,
+    pub error: Option<String>,
+}
+
+// Runs every solver on every scenario with the same time budget
+pub fn run_benchmark(
+    scenario_list: &[Scenario],
+    solver_list: &[SolverKind],
+    time_budget: Duration,
+) -> Vec<BenchmarkResult> {
+    let mut result_list = Vec::new();
+    for scenario in scenario_list {
+        for solver in solver_list {
+            log::info!("Benchmark {} with {} solver", scenario.name, solver);
+            let start_time = Instant::now();
+            let plan = run_solver(scenario, *solver, time_budget);
+            let runtime = start_time.elapsed();
+
+            result_list.push(match plan {
+                Ok(plan) => BenchmarkResult {
+                    scenario: scenario.name.clone(),
+                    solver: *solver,
+                    score: Some(get_score(scenario, &plan)),
+                    runtime,
+                    violation_list: get_violation_list(scenario, &plan),
+                    error: None,
+                },
+                Err(err) => BenchmarkResult {
+                    scenario: scenario.name.clone(),
+                    solver: *solver,
+                    score: None,
+                    runtime,
+                    violation_list: Vec::new(),
+                    error: Some(err),
+                },
+            });
+        }
+    }
+    result_list
+}
+
+fn run_solver(
+    scenario: &Scenario,
+    solver: SolverKind,
+    time_budget: Duration,
+) -> Result<Plan, String> {
+    let mut config = scenario.to_calculator_config();
+    config.check()?;
+    match solver {
+        SolverKind::Genetic => {
+            config.set_time_budget(Some(time_budget));
+            let mut calculator = Calculator::new(config);
+            calculator.calculate()?;
+            calculator.wait();
+            let top_plan = calculator
+                .top_plan
+                .lock()
+                .expect("Expect to get lock on top plan");
+            top_plan
+                .clone()
+                .ok_or_else(|| "No plan found within the time budget!".to_string())
+        }
+        SolverKind::Exact => {
+            solve_exact(&config, time_budget, None).map(|exact_plan| exact_plan.plan)
+        }
+    }
+}
+
+// Scores the plan the same way for every solver: the longest walk from the start
+// point over the hosts of all courses, in course order, to the goal point
+pub fn get_score(scenario: &Scenario, plan: &Plan) -> f64 {
+    let distance = |from: (i32, i32), to: (i32, i32)| calc_distance(from.0, from.1, to.0, to.1);
+    plan.walking_path
+        .values()
+        .map(|course_list| {
+            let mut course_list = course_list.clone();
+            course_list.sort_by_key(|course| {
+                scenario
+                    .course_name_list
+                    .iter()
+                    .position(|name| *name == course.name)
+            });
+            let mut stop_list: Vec<(i32, i32)> = course_list
+                .iter()
+                .map(|course| (course.host.latitude, course.host.longitude))
+                .collect();
+            if let Some(start_point) = scenario.start_point {
+                stop_list.insert(0, start_point);
+            }
+            if let Some(goal_point) = scenario.goal_point {
+                stop_list.push(goal_point);
+            }
+            stop_list
+                .windows(2)
+                .map(|window| distance(window[0], window[1]))
+                .sum::<f64>()
+        })
+        .fold(0.0, f64::max)
+}
+
+// Checks the rules every plan has to follow
+pub fn get_violation_list(scenario: &Scenario, plan: &Plan) -> Vec<String> {
+    let mut violation_list = Vec::new();
+    let mut attendance: HashMap<(u8, &String), usize> = HashMap::new();
+    let mut hosting_count: HashMap<u8, usize> = HashMap::new();
+    let mut meeting_count: HashMap<(u8, u8), usize> = HashMap::new();
+
+    for (course_name, course_list) in plan.course_map.iter() {
+        for course in course_list {
+            *hosting_count.entry(course.host.id).or_default() += 1;
+            let member_list: Vec<u8> = std::iter::once(course.host.id)
+                .chain(course.guest_list.iter().map(|guest| guest.id))
+                .collect();
+            for (index, first) in member_list.iter().enumerate() {
+                *attendance.entry((*first, course_name)).or_default() += 1;
+                for second in member_list[index + 1..].iter() {
+                    *meeting_count
+                        .entry((*first.min(second), *first.max(second)))
+                        .or_default() += 1;
+                }
+            }
+        }
+    }
+
+    for contact in scenario.contact_list.iter() {
+        for course_name in scenario.course_name_list.iter() {
+            let count = attendance
+                .get(&(contact.id, course_name))
+                .cloned()
+                .unwrap_or(0);
+            if count != 1 {
+                violation_list.push(format!(
+                    "{} is at {} tables for {}",
+                    contact.team_name, count, course_name
+                ));
+            }
+        }
+        if hosting_count.get(&contact.id).cloned().unwrap_or(0) > 1 {
+            violation_list.push(format!("{} hosts more than once", contact.team_name));
+        }
+    }
+    let mut repeated_list: Vec<&(u8, u8)> = meeting_count
+        .iter()
+        .filter(|(_, count)| **count > 1)
+        .map(|(pair, _)| pair)
+        .collect();
+    repeated_list.sort();
+    let team_name = |id: &u8| {
+        scenario
+            .contact_list
+            .iter()
+            .find(|contact| contact.id == *id)
+            .map(|contact| contact.team_name.clone())
+            .unwrap_or_default()
+    };
+    for (first, second) in repeated_list {
+        violation_list.push(format!(
+            "{} and {} meet more than once",
+            team_name(first),
+            team_name(second)
+        ));
+    }
+    violation_list
+}
+
+// One line per result, to compare solvers at a glance
+pub fn format_result_list(result_list: &[BenchmarkResult]) -> String {
+    let mut table = format!(
+        "{:<24} {:<8} {:>10} {:>10} {:>10}\n",
+        "Scenario", "Solver", "Score", "Runtime", "Violations"
+    );
+    for result in result_list {
+        let score = match (&result.score, &result.error) {
+            (Some(score), _) => format!("{:.1}", score),
+            (None, Some(_)) => "error".to_string(),
+            (None, None) => "-".to_string(),
+        };
+        table.push_str(&format!(
+            "{:<24} {:<8} {:>10} {:>9.1}s {:>10}\n",
+            result.scenario,
+            result.solver.to_string(),
+            score,
+            result.runtime.as_secs_f64(),
+            result.violation_list.len()
+        ));
+    }
+    table
+}
@@ -0,0 +1,178 @@
+use std::{
+    any::Any,
+    sync::{
+        atomic::{AtomicBool, Ordering},
+        Arc,
+    },
+    thread::{self, JoinHandle},
+    time::Instant,
+};
+
+use log::{error, info};
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum JobState {
+    Running,
+    // Cancelled, but some workers are still finishing their iteration
+    Stopping,
+    Finished,
+    // At least one worker panicked, holds the panic messages
+    Failed(String),
+}
+
+// Shared by all workers of a job, they stop as soon as it is cancelled
+#[derive(Debug, Clone, Default)]
+pub struct CancellationToken {
+    cancelled: Arc<AtomicBool>,
+}
+
+impl CancellationToken {
+    pub fn new() -> Self {
+        CancellationToken::default()
+    }
+
+    pub fn cancel(&self) {
+        self.cancelled.store(true, Ordering::SeqCst);
+    }
+
+    pub fn is_cancelled(&self) -> bool {
+        self.cancelled.load(Ordering::SeqCst)
+    }
+}
+
+// Cancels the job if its worker panics, so the other workers don't wait forever
+struct CancelOnPanic(CancellationToken);
+
+impl Drop for CancelOnPanic {
+    fn drop(&mut self) {
+        if thread::panicking() {
+            self.0.cancel();
+        }
+    }
+}
+
+#[derive(Debug)]
+pub struct CalculationJob {
+    token: CancellationToken,
+    handle_list: Vec<JoinHandle<()>>,
+    panic_list: Vec<String>,
+    start_time: Instant,
+    stop_time: Option<Instant>,
+}
+
+impl CalculationJob {
+    pub fn spawn<F>(number_of_threads: usize, worker: F) -> Self
+    where
+        F: Fn(usize, CancellationToken) + Send + Sync + 'static,
+    {
+        let token = CancellationToken::new();
+        let worker = Arc::new(worker);
+        let handle_list = (0..number_of_threads)
+            .map(|index| {
+                let token = token.clone();
+                let worker = Arc::clone(&worker);
+                thread::spawn(move || {
+                    let _guard = CancelOnPanic(token.clone());
+                    info!(
+                        "Start calculation of thread {}/{}",
+                        index + 1,
+                        number_of_threads
+                    );
+                    worker(index, token);
+                    info!(
+                        "Finished calculation of thread {}/{}",
+                        index + 1,
+                        number_of_threads
+                    );
+                })
+            })
+            .collect();
+
+        CalculationJob {
+            token,
+            handle_list,
+            panic_list: Vec::new(),
+            start_time: Instant::now(),
+            stop_time: None,
+        }
+    }
+
+    pub fn get_token(&self) -> CancellationToken {
+        self.token.clone()
+    }
+
+    pub fn cancel(&self) {
+        self.token.cancel();
+    }
+
+    // Blocks until every worker has returned
+    pub fn join(&mut self) -> JobState {
+        for handle in self.handle_list.drain(..) {
+            if let Err(panic) = handle.join() {
+                let message = get_panic_message(panic);
+                error!("Calculation thread panicked: {}", message);
+                self.panic_list.push(message);
+            }
+        }
+        if self.stop_time.is_none() {
+            self.stop_time = Some(Instant::now());
+        }
+        self.get_state()
+    }
+
+    pub fn cancel_and_join(&mut self) -> JobState {
+        self.cancel();
+        self.join()
+    }
+
+    // Joins the workers without blocking once all of them have returned
+    pub fn poll(&mut self) -> JobState {
+        if !self.handle_list.is_empty() && self.handle_list.iter().all(JoinHandle::is_finished) {
+            self.join();
+        }
+        self.get_state()
+    }
+
+    pub fn get_state(&self) -> JobState {
+        if self.handle_list.iter().any(|handle| !handle.is_finished()) {
+            if self.token.is_cancelled() {
+                JobState::Stopping
+            } else {
+                JobState::Running
+            }
+        } else if !self.panic_list.is_empty() {
+            JobState::Failed(self.panic_list.join("\n"))
+        } else {
+            JobState::Finished
+        }
+    }
+
+    pub fn is_running(&self) -> bool {
+        matches!(self.get_state(), JobState::Running | JobState::Stopping)
+    }
+
+    pub fn get_start_time(&self) -> Instant {
+        self.start_time
+    }
+
+    pub fn get_stop_time(&self) -> Option<Instant> {
+        self.stop_time
+    }
+}
+
+// Dropping a job never blocks, the workers stop on their own once cancelled
+impl Drop for CalculationJob {
+    fn drop(&mut self) {
+        self.cancel();
+    }
+}
+
+fn get_panic_message(panic: Box<dyn Any + Send>) -> String {
+    if let Some(message) = panic.downcast_ref::<&str>() {
+        message.to_string()
+    } else if let Some(message) = panic.downcast_ref::<String>() {
+        message.clone()
+    } else {
+        "Unknown panic".to_string()
+    }
+}
@@ -6,7 +6,7 @@ use std::{
         Arc, Mutex,
     },
     thread::{self},
-    time::Instant,
+    time::{Duration, Instant},
 };
 
 use log::{debug, info};
@@ -15,16 +15,79 @@ use rand::Rng;
 
 use colored::Colorize;
 
-use crate::contact::Contact;
+mod exact_solver;
+
+pub use exact_solver::{solve_exact, ExactPlan, MAX_EXACT_TEAMS};
+
+use crate::{
+    calculation_job::{CalculationJob, CancellationToken, JobState},
+    contact::Contact,
+};
 
 #[derive(Debug)]
 pub struct Calculator {
     pub top_plan: Arc<Mutex<Option<Plan>>>,
-    pub start_time: Option<Instant>,
-    pub stop_time: Option<Instant>,
     pub iterations: Arc<AtomicUsize>,
+    pub history: Arc<Mutex<History>>,
+    // Optimality gap of the plan of the exact solver, once it is done
+    pub gap: Arc<Mutex<Option<f64>>>,
     config: CalculatorConfig,
-    calculating: Arc<Mutex<bool>>,
+    job: Option<CalculationJob>,
+    last_improvement: Arc<Mutex<Instant>>,
+}
+
+// Keeps the history of long calculations small
+const MAX_HISTORY_LENGTH: usize = 1000;
+
+// Iteration stats of a calculation. Once full, every second stat is dropped and only
+// every second iteration is recorded from then on.
+#[derive(Debug)]
+pub struct History {
+    stat_list: Vec<IterationStat>,
+    step: usize,
+}
+
+impl Default for History {
+    fn default() -> Self {
+        History {
+            stat_list: Vec::new(),
+            step: 1,
+        }
+    }
+}
+
+impl History {
+    pub fn push(&mut self, stat: IterationStat) {
+        if !stat.iteration.is_multiple_of(self.step) {
+            return;
+        }
+        self.stat_list.push(stat);
+        if self.stat_list.len() >= MAX_HISTORY_LENGTH {
+            self.step *= 2;
+            let step = self.step;
+            self.stat_list
+                .retain(|stat| stat.iteration.is_multiple_of(step));
+        }
+    }
+
+    pub fn clear(&mut self) {
+        *self = History::default();
+    }
+
+    pub fn get_stat_list(&self) -> &[IterationStat] {
+        &self.stat_list
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct IterationStat {
+    pub iteration: usize,
+    pub elapsed: Duration,
+    pub best_score: f64,
+    // Scores of the feasible plans of this generation
+    pub population_best: f64,
+    pub population_mean: f64,
+    pub feasible_count: usize,
 }
 
 #[derive(Debug)]
@@ -32,17 +95,63 @@ pub struct CalculatorConfig {
     start_point: Option<(i32, i32)>,
     goal_point: Option<(i32, i32)>,
     course_name_list: Vec<String>,
-    course_with_more_hosts: Option<String>,
+    course_with_more_hosts_list: Vec<String>,
+    guests_per_hosting: Option<usize>,
     contact_list: Vec<Contact>,
+    // The calculation stops itself if the best plan didn't change for this long
+    stop_after_no_improvement: Option<Duration>,
+    number_of_threads: usize,
+    // Plans per generation and thread
+    population_size: usize,
+    // Share of the next generation bred from the best plans, the rest is random
+    crossover_rate: f64,
+    // Chance of each byte of a bred seed to be replaced by a random one
+    mutation_rate: f64,
+    // Best plans copied unchanged into the next generation
+    elite_count: usize,
+    selection: Selection,
+    time_budget: Option<Duration>,
+    iteration_budget: Option<usize>,
+    // Search a provably best plan instead of running the genetic algorithm
+    exact_solver: bool,
+}
+
+// How parents for the next generation are picked
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Selection {
+    // Neighbours in the sorted list of the best plans are paired
+    Truncation,
+    // Each parent is the best of this many random plans
+    Tournament(usize),
 }
 
+const DEFAULT_POPULATION_SIZE: usize = 1_000;
+const DEFAULT_CROSSOVER_RATE: f64 = 0.8;
+const DEFAULT_MUTATION_RATE: f64 = 0.02;
+const DEFAULT_ELITE_COUNT: usize = 2;
+const DEFAULT_SELECTION: Selection = Selection::Tournament(3);
+// Infeasible seeds are mutated this often before they are given up
+const REPAIR_ATTEMPTS: usize = 10;
+const REPAIR_MUTATION_RATE: f64 = 0.1;
+// Used by the exact solver if no time budget is set
+const DEFAULT_EXACT_TIME_LIMIT: Duration = Duration::from_secs(60);
+
 #[derive(Debug)]
 struct CalculatorConfigInternal {
     start_point: Option<(i32, i32)>,
     goal_point: Option<(i32, i32)>,
     course_name_list: Vec<Rc<String>>,
-    course_with_more_hosts: Option<String>,
+    course_with_more_hosts_list: Vec<String>,
+    guests_per_hosting: Option<usize>,
     contact_list: Vec<Rc<Contact>>,
+    stop_after_no_improvement: Option<Duration>,
+    population_size: usize,
+    crossover_rate: f64,
+    mutation_rate: f64,
+    elite_count: usize,
+    selection: Selection,
+    time_budget: Option<Duration>,
+    iteration_budget: Option<usize>,
 }
 
 struct PlanInternal {
@@ -136,9 +245,20 @@ impl CalculatorConfig {
         CalculatorConfig {
             start_point,
             goal_point,
-            course_with_more_hosts,
+            course_with_more_hosts_list: course_with_more_hosts.into_iter().collect(),
+            guests_per_hosting: None,
             course_name_list,
             contact_list: contact_list,
+            stop_after_no_improvement: None,
+            number_of_threads: get_default_number_of_threads(),
+            population_size: DEFAULT_POPULATION_SIZE,
+            crossover_rate: DEFAULT_CROSSOVER_RATE,
+            mutation_rate: DEFAULT_MUTATION_RATE,
+            elite_count: DEFAULT_ELITE_COUNT,
+            selection: DEFAULT_SELECTION,
+            time_budget: None,
+            iteration_budget: None,
+            exact_solver: false,
         }
     }
     pub fn new(
@@ -149,10 +269,143 @@ impl CalculatorConfig {
         CalculatorConfig {
             start_point: None,
             goal_point: None,
-            course_with_more_hosts,
+            course_with_more_hosts_list: course_with_more_hosts.into_iter().collect(),
+            guests_per_hosting: None,
             course_name_list,
             contact_list,
+            stop_after_no_improvement: None,
+            number_of_threads: get_default_number_of_threads(),
+            population_size: DEFAULT_POPULATION_SIZE,
+            crossover_rate: DEFAULT_CROSSOVER_RATE,
+            mutation_rate: DEFAULT_MUTATION_RATE,
+            elite_count: DEFAULT_ELITE_COUNT,
+            selection: DEFAULT_SELECTION,
+            time_budget: None,
+            iteration_budget: None,
+            exact_solver: false,
+        }
+    }
+
+    pub fn set_guests_per_hosting(&mut self, guests_per_hosting: Option<usize>) {
+        self.guests_per_hosting = guests_per_hosting;
+    }
+
+    pub fn set_stop_after_no_improvement(&mut self, stop_after_no_improvement: Option<Duration>) {
+        self.stop_after_no_improvement = stop_after_no_improvement;
+    }
+
+    pub fn set_number_of_threads(&mut self, number_of_threads: usize) {
+        self.number_of_threads = number_of_threads;
+    }
+
+    pub fn set_population_size(&mut self, population_size: usize) {
+        self.population_size = population_size;
+    }
+
+    pub fn set_crossover_rate(&mut self, crossover_rate: f64) {
+        self.crossover_rate = crossover_rate;
+    }
+
+    pub fn set_mutation_rate(&mut self, mutation_rate: f64) {
+        self.mutation_rate = mutation_rate;
+    }
+
+    pub fn set_elite_count(&mut self, elite_count: usize) {
+        self.elite_count = elite_count;
+    }
+
+    pub fn set_selection(&mut self, selection: Selection) {
+        self.selection = selection;
+    }
+
+    pub fn set_time_budget(&mut self, time_budget: Option<Duration>) {
+        self.time_budget = time_budget;
+    }
+
+    pub fn set_iteration_budget(&mut self, iteration_budget: Option<usize>) {
+        self.iteration_budget = iteration_budget;
+    }
+
+    pub fn set_exact_solver(&mut self, exact_solver: bool) {
+        self.exact_solver = exact_solver;
+    }
+
+    pub fn set_course_with_more_hosts_list(&mut self, course_with_more_hosts_list: Vec<String>) {
+        self.course_with_more_hosts_list = course_with_more_hosts_list;
+    }
+
+    pub fn get_number_of_hostings(&self, course_name: &String) -> usize {
+        get_number_of_hostings(
+            self.contact_list.len(),
+            get_number_of_seats(self.guests_per_hosting, self.course_name_list.len()),
+            self.course_with_more_hosts_list.contains(course_name),
+        )
+    }
+
+    pub fn check(&self) -> Result<(), String> {
+        if self.course_name_list.len() < 2 {
+            return Err("At least two courses are needed!".to_string());
+        }
+        if self.exact_solver && self.contact_list.len() > MAX_EXACT_TEAMS {
+            return Err(format!(
+                "The exact solver supports up to {} teams!",
+                MAX_EXACT_TEAMS
+            ));
+        }
+        if self.number_of_threads == 0 {
+            return Err("At least one thread is needed!".to_string());
+        }
+        if self.population_size < 2 {
+            return Err("A population needs at least two plans!".to_string());
+        }
+        if !(0.0..=1.0).contains(&self.crossover_rate) {
+            return Err("The crossover rate has to be between 0 and 1!".to_string());
+        }
+        if !(0.0..=1.0).contains(&self.mutation_rate) {
+            return Err("The mutation rate has to be between 0 and 1!".to_string());
+        }
+        if self.elite_count >= self.population_size {
+            return Err("The elite has to be smaller than the population!".to_string());
+        }
+        if self.selection == Selection::Tournament(0) {
+            return Err("A tournament needs at least one plan!".to_string());
+        }
+        let number_of_contacts = self.contact_list.len();
+        let number_of_seats =
+            get_number_of_seats(self.guests_per_hosting, self.course_name_list.len());
+        if number_of_seats < 2 {
+            return Err("Every hosting needs at least one guest!".to_string());
         }
+        if number_of_contacts < number_of_seats {
+            return Err(format!(
+                "A hosting seats {} teams, but there are only {} contacts!",
+                number_of_seats, number_of_contacts
+            ));
+        }
+        if !number_of_contacts.is_multiple_of(number_of_seats)
+            && self.course_with_more_hosts_list.is_empty()
+        {
+            return Err("A course with more hosts has to be set!".to_string());
+        }
+
+        let number_of_hostings: usize = self
+            .course_name_list
+            .iter()
+            .map(|course_name| {
+                get_number_of_hostings(
+                    number_of_contacts,
+                    number_of_seats,
+                    self.course_with_more_hosts_list.contains(course_name),
+                )
+            })
+            .sum();
+        if number_of_hostings > number_of_contacts {
+            return Err(format!(
+                "{} hostings are needed, but there are only {} contacts!",
+                number_of_hostings, number_of_contacts
+            ));
+        }
+        Ok(())
     }
 
     fn get_internal(&self) -> CalculatorConfigInternal {
@@ -164,12 +417,21 @@ impl CalculatorConfig {
                 .iter()
                 .map(|name| Rc::new(name.clone()))
                 .collect(),
-            course_with_more_hosts: self.course_with_more_hosts.clone(),
+            course_with_more_hosts_list: self.course_with_more_hosts_list.clone(),
+            guests_per_hosting: self.guests_per_hosting,
             contact_list: self
                 .contact_list
                 .iter()
                 .map(|contact| Rc::new(contact.clone()))
                 .collect(),
+            stop_after_no_improvement: self.stop_after_no_improvement,
+            population_size: self.population_size,
+            crossover_rate: self.crossover_rate,
+            mutation_rate: self.mutation_rate,
+            elite_count: self.elite_count,
+            selection: self.selection,
+            time_budget: self.time_budget,
+            iteration_budget: self.iteration_budget,
         }
     }
 
@@ -178,90 +440,242 @@ impl CalculatorConfig {
             start_point: self.start_point,
             goal_point: self.goal_point,
             course_name_list: self.course_name_list.clone(),
-            course_with_more_hosts: self.course_with_more_hosts.clone(),
+            course_with_more_hosts_list: self.course_with_more_hosts_list.clone(),
+            guests_per_hosting: self.guests_per_hosting,
             contact_list: self.contact_list.clone(),
+            stop_after_no_improvement: self.stop_after_no_improvement,
+            number_of_threads: self.number_of_threads,
+            population_size: self.population_size,
+            crossover_rate: self.crossover_rate,
+            mutation_rate: self.mutation_rate,
+            elite_count: self.elite_count,
+            selection: self.selection,
+            time_budget: self.time_budget,
+            iteration_budget: self.iteration_budget,
+            exact_solver: self.exact_solver,
         }
     }
 }
 
+fn get_default_number_of_threads() -> usize {
+    thread::available_parallelism()
+        .map(|number| number.get())
+        .unwrap_or(1)
+}
+
+impl CalculatorConfigInternal {
+    fn get_number_of_hostings(&self, course_name: &String) -> usize {
+        get_number_of_hostings(
+            self.contact_list.len(),
+            get_number_of_seats(self.guests_per_hosting, self.course_name_list.len()),
+            self.course_with_more_hosts_list.contains(course_name),
+        )
+    }
+}
+
+// Each hosting seats the host and its guests. Without a configured number of
+// guests there are as many seats as courses, so every contact hosts once.
+fn get_number_of_seats(guests_per_hosting: Option<usize>, number_of_courses: usize) -> usize {
+    guests_per_hosting
+        .map(|guests_per_hosting| guests_per_hosting + 1)
+        .unwrap_or(number_of_courses)
+}
+
+// If the contacts don't fill every seat, courses with more hosts get one extra hosting
+fn get_number_of_hostings(
+    number_of_contacts: usize,
+    number_of_seats: usize,
+    with_more_hosts: bool,
+) -> usize {
+    number_of_contacts / number_of_seats
+        + if !number_of_contacts.is_multiple_of(number_of_seats) && with_more_hosts {
+            1
+        } else {
+            0
+        }
+}
+
 impl Calculator {
     pub fn new(config: CalculatorConfig) -> Self {
         Calculator {
             config,
             top_plan: Arc::new(Mutex::new(None)),
-            calculating: Arc::new(Mutex::new(true)),
-            start_time: None,
-            stop_time: None,
+            job: None,
             iterations: Arc::new(AtomicUsize::new(0)),
+            history: Arc::new(Mutex::new(History::default())),
+            gap: Arc::new(Mutex::new(None)),
+            last_improvement: Arc::new(Mutex::new(Instant::now())),
         }
     }
 
-    pub fn calculate(&mut self) {
+    // Only one calculation runs at a time, a finished one is replaced
+    pub fn calculate(&mut self) -> Result<(), String> {
+        self.config.check()?;
+        if self.is_running() {
+            return Err("A calculation is already running!".to_string());
+        }
         log::info!("Start calculation");
         self.iterations.store(0, Ordering::SeqCst);
-        self.start_time = Some(Instant::now());
-        self.stop_time = None;
-        let number_of_threads = 5;
+        self.history.lock().expect("Expect history").clear();
+        let start_time = Instant::now();
         *self
-            .calculating
+            .last_improvement
             .lock()
-            .expect("Expect calculating to be set!") = true;
-        for index in 0..number_of_threads {
-            let config = self.config.clone();
-            let top_plan = Arc::clone(&self.top_plan);
-            let calculating = Arc::clone(&self.calculating);
-            let iteration = Arc::clone(&self.iterations);
-            thread::spawn(move || {
-                info!(
-                    "Start calculation of thread {}/{}",
-                    index + 1,
-                    number_of_threads
-                );
-                calcutate_job(config.get_internal(), calculating, top_plan, iteration);
-                info!(
-                    "Finished calculation of thread {}/{}",
-                    index + 1,
-                    number_of_threads
-                );
-            });
+            .expect("Expect last improvement") = start_time;
+
+        let config = self.config.clone();
+        let top_plan = Arc::clone(&self.top_plan);
+        let iteration = Arc::clone(&self.iterations);
+        let history = Arc::clone(&self.history);
+        let last_improvement = Arc::clone(&self.last_improvement);
+        *self.gap.lock().expect("Expect gap") = None;
+        if self.config.exact_solver {
+            let gap = Arc::clone(&self.gap);
+            self.job = Some(CalculationJob::spawn(1, move |_, token| {
+                let progress = Progress {
+                    iteration: Arc::clone(&iteration),
+                    history: Arc::clone(&history),
+                    last_improvement: Arc::clone(&last_improvement),
+                    start_time,
+                };
+                let time_limit = config.time_budget.unwrap_or(DEFAULT_EXACT_TIME_LIMIT);
+                let result =
+                    exact_solver::solve(&config.get_internal(), time_limit, Some(&token), |plan| {
+                        *top_plan.lock().expect("Expect to get lock on top plan") =
+                            Some(plan.clone());
+                        progress.improved();
+                        progress.record(&[plan.score], Some(plan.score));
+                    });
+                match result {
+                    Ok(exact_plan) => *gap.lock().expect("Expect gap") = Some(exact_plan.gap),
+                    Err(err) => log::error!("Exact solver failed: {}", err),
+                }
+            }));
+            return Ok(());
         }
+        self.job = Some(CalculationJob::spawn(
+            self.config.number_of_threads,
+            move |_, token| {
+                let progress = Progress {
+                    iteration: Arc::clone(&iteration),
+                    history: Arc::clone(&history),
+                    last_improvement: Arc::clone(&last_improvement),
+                    start_time,
+                };
+                calcutate_job(
+                    config.get_internal(),
+                    token,
+                    Arc::clone(&top_plan),
+                    progress,
+                );
+            },
+        ));
+        Ok(())
     }
 
-    pub fn stop(&mut self) {
+    // Cancels the calculation and waits for all threads to finish
+    pub fn stop(&mut self) -> Option<JobState> {
         info!("Stop calculation");
-        *self
-            .calculating
-            .lock()
-            .expect("Expect calculating to be set!") = false;
-        self.stop_time = Some(Instant::now());
+        self.job.as_mut().map(CalculationJob::cancel_and_join)
+    }
+
+    // Asks the calculation to stop without waiting for the threads, poll get_state()
+    // to see when they have returned
+    pub fn cancel(&self) -> Option<JobState> {
+        info!("Cancel calculation");
+        self.job.as_ref().map(|job| {
+            job.cancel();
+            job.get_state()
+        })
+    }
+
+    // Waits for the calculation to stop on its own, e.g. after its budget
+    pub fn wait(&mut self) -> Option<JobState> {
+        self.job.as_mut().map(CalculationJob::join)
+    }
+
+    pub fn get_state(&mut self) -> Option<JobState> {
+        self.job.as_mut().map(CalculationJob::poll)
     }
 
     pub fn is_running(&self) -> bool {
+        self.job.as_ref().is_some_and(CalculationJob::is_running)
+    }
+
+    pub fn get_start_time(&self) -> Option<Instant> {
+        self.job.as_ref().map(CalculationJob::get_start_time)
+    }
+
+    pub fn get_stop_time(&self) -> Option<Instant> {
+        self.job.as_ref().and_then(CalculationJob::get_stop_time)
+    }
+}
+
+// Shared progress of all threads of a calculation
+struct Progress {
+    iteration: Arc<AtomicUsize>,
+    history: Arc<Mutex<History>>,
+    last_improvement: Arc<Mutex<Instant>>,
+    start_time: Instant,
+}
+
+impl Progress {
+    fn record(&self, score_list: &[f64], best_score: Option<f64>) {
+        let iteration = self.iteration.fetch_add(1, Ordering::SeqCst) + 1;
+        let score_list: Vec<f64> = score_list
+            .iter()
+            .cloned()
+            .filter(|score| *score != f64::MAX)
+            .collect();
+        let population_best = score_list.iter().cloned().fold(f64::MAX, f64::min);
+        let population_mean = if score_list.is_empty() {
+            f64::MAX
+        } else {
+            score_list.iter().sum::<f64>() / score_list.len() as f64
+        };
+        self.history
+            .lock()
+            .expect("Expect history")
+            .push(IterationStat {
+                iteration,
+                elapsed: self.start_time.elapsed(),
+                best_score: best_score.unwrap_or(f64::MAX),
+                population_best,
+                population_mean,
+                feasible_count: score_list.len(),
+            });
+    }
+
+    fn improved(&self) {
         *self
-            .calculating
+            .last_improvement
             .lock()
-            .expect("Expect calculating to be set!")
+            .expect("Expect last improvement") = Instant::now();
+    }
+
+    fn time_since_improvement(&self) -> Duration {
+        self.last_improvement
+            .lock()
+            .expect("Expect last improvement")
+            .elapsed()
     }
 }
 
 fn calcutate_job(
     config: CalculatorConfigInternal,
-    calculating: Arc<Mutex<bool>>,
+    token: CancellationToken,
     top_plan: Arc<Mutex<Option<Plan>>>,
-    iteration: Arc<AtomicUsize>,
+    progress: Progress,
 ) {
-    let number_of_seeds = 1_000;
-
     let mut list_of_seeds = Vec::new();
-    for _ in 0..number_of_seeds {
+    for _ in 0..config.population_size {
         list_of_seeds.push(generate_seed());
     }
 
     loop {
-        iteration.fetch_add(1, Ordering::SeqCst);
         let mut list_of_plans: Vec<PlanInternal> = list_of_seeds
             .iter()
-            .map(|seed| seed_to_plan(&config, seed.clone()))
+            .map(|seed| repair_plan(&config, seed_to_plan(&config, seed.clone())))
             .collect();
         list_of_plans.sort_by(|a, b| a.score.partial_cmp(&b.score).unwrap());
 
@@ -276,21 +690,47 @@ fn calcutate_job(
                         list_of_plans[0].score
                     );
                     *current_top_score = Some(list_of_plans[0].to_plan());
+                    progress.improved();
                 }
                 Some(existing_score) if list_of_plans[0].score < existing_score.score => {
                     // New score is better → overwrite
                     log::info!("Found new best plan with score: {}", list_of_plans[0].score);
                     *current_top_score = Some(list_of_plans[0].to_plan());
+                    progress.improved();
                 }
                 _ => {
                     // Score is not better → do nothing
                 }
             }
         }
-        if !*calculating.lock().expect("Expact to find calculating flag") {
+        let best_score = top_plan
+            .lock()
+            .expect("Expect to get lock on top plan")
+            .as_ref()
+            .map(|plan| plan.score);
+        let score_list: Vec<f64> = list_of_plans.iter().map(|plan| plan.score).collect();
+        progress.record(&score_list, best_score);
+
+        if let Some(stop_after) = config.stop_after_no_improvement {
+            if progress.time_since_improvement() >= stop_after {
+                info!("No better plan for {:?}, stopping calculation", stop_after);
+                token.cancel();
+            }
+        }
+        if config
+            .time_budget
+            .is_some_and(|time_budget| progress.start_time.elapsed() >= time_budget)
+            || config.iteration_budget.is_some_and(|iteration_budget| {
+                progress.iteration.load(Ordering::SeqCst) >= iteration_budget
+            })
+        {
+            info!("Budget used up, stopping calculation");
+            token.cancel();
+        }
+        if token.is_cancelled() {
             break;
         }
-        list_of_seeds = generate_seed_from_plan_list(list_of_plans);
+        list_of_seeds = generate_seed_from_plan_list(&config, list_of_plans);
     }
 }
 
@@ -319,6 +759,20 @@ fn seed_to_plan(config: &CalculatorConfigInternal, seed: Vec<u8>) -> PlanInterna
     }
 }
 
+// Infeasible seeds get some random genes changed until they give a plan
+fn repair_plan(config: &CalculatorConfigInternal, plan: PlanInternal) -> PlanInternal {
+    let mut plan = plan;
+    for _ in 0..REPAIR_ATTEMPTS {
+        if plan.score != f64::MAX {
+            break;
+        }
+        let mut seed = plan.seed.clone();
+        mutate_seed(&mut seed, REPAIR_MUTATION_RATE);
+        plan = seed_to_plan(config, seed);
+    }
+    plan
+}
+
 fn create_course_map(
     config: &CalculatorConfigInternal,
     seed: &Vec<u8>,
@@ -375,24 +829,18 @@ fn create_course_map(
                 .collect::<Vec<&str>>()
         );
 
-        //Create list of possible hosts and guests, that will be used to create courses
-        let mut possible_host_in_course_list = possible_host_list.clone();
+        //Create list of possible guests, that will be used to create courses
         let mut possible_guest_list = config
             .contact_list
             .iter()
             .cloned()
             .collect::<Vec<Rc<Contact>>>();
-        let mut contact_in_course: HashSet<Rc<Contact>> = HashSet::new();
 
-        let number_of_courses = config.contact_list.len() / config.course_name_list.len()
-            + if config.course_with_more_hosts.as_deref() == Some(course_name)
-                && config.contact_list.len() % config.course_name_list.len() != 0
-            {
-                1
-            } else {
-                0
-            };
+        let number_of_courses = config.get_number_of_hostings(course_name);
         debug!("Number of courses for course: {}", number_of_courses);
+        if number_of_courses == 0 {
+            return None;
+        }
 
         let number_of_guests_per_course = config.contact_list.len() / number_of_courses - 1;
         debug!(
@@ -402,46 +850,15 @@ fn create_course_map(
 
         let mut number_of_guests_overhang = config.contact_list.len() % number_of_courses;
         debug!("Number of extra guests: {}", number_of_guests_overhang);
-        for _ in 0..number_of_courses {
-            debug!(
-                "Possible hosts for course \"{}\":\t{:?}",
-                course_name,
-                possible_host_in_course_list
-                    .iter()
-                    .map(|x| x.team_name.as_str())
-                    .collect::<Vec<&str>>()
-            );
-            debug!(
-                "Possible guests in course \"{}\":\t{:?}",
-                course_name,
-                possible_guest_list
-                    .iter()
-                    .map(|x| x.team_name.as_str())
-                    .collect::<Vec<&str>>()
-            );
-
-            let mut guest_list = Vec::new();
 
-            //Choose host
-            if possible_host_in_course_list.is_empty() {
+        //Choose all hosts of the course first, so no needed host is taken as guest
+        let mut host_list = Vec::new();
+        for _ in 0..number_of_courses {
+            if possible_host_list.is_empty() {
                 return None;
             }
-            let host_index =
-                seed[seed_index % seed.len()] as usize % possible_host_in_course_list.len();
-            let host = Rc::clone(
-                possible_host_in_course_list
-                    .get(host_index)
-                    .expect("Expected host to find in possible host list for this course!"),
-            );
-            //Remove host from possible host list in course
-            possible_host_in_course_list.remove(host_index);
-
-            //Remove host from possible host list
-            let remove_host_index = possible_host_list
-                .iter()
-                .position(|x| x.eq(&host))
-                .expect("Expected host in list of possible hosts!");
-            possible_host_list.remove(remove_host_index);
+            let host_index = seed[seed_index % seed.len()] as usize % possible_host_list.len();
+            let host = possible_host_list.remove(host_index);
 
             //Remove host from possible guest list
             let remove_guest_index = possible_guest_list
@@ -451,13 +868,20 @@ fn create_course_map(
             possible_guest_list.remove(remove_guest_index);
 
             seed_index += 1;
+            host_list.push(host);
+        }
 
-            set_seen_people(
-                &mut seen_contact_map,
-                &mut contact_in_course,
-                &guest_list,
-                Rc::clone(&host),
+        for host in host_list {
+            debug!(
+                "Possible guests in course \"{}\":\t{:?}",
+                course_name,
+                possible_guest_list
+                    .iter()
+                    .map(|x| x.team_name.as_str())
+                    .collect::<Vec<&str>>()
             );
+
+            let mut guest_list = Vec::new();
             for _ in 0..(number_of_guests_per_course
                 + if number_of_guests_overhang != 0 { 1 } else { 0 })
             {
@@ -465,22 +889,24 @@ fn create_course_map(
                     return None;
                 }
 
-                //Choose guest
+                //Choose guest, who has seen nobody of the course before or at least only once.
+                //If nobody fits, the seed is infeasible and gets repaired
+                let mut member_list = guest_list.clone();
+                member_list.push(Rc::clone(&host));
                 let guest = get_contact(
                     &possible_guest_list,
                     seed[seed_index % seed.len()],
-                    &contact_in_course,
+                    &member_list,
                     &seen_contact_map,
                 )
                 .or_else(|| {
                     get_contact(
                         &possible_guest_list,
                         seed[seed_index % seed.len()],
-                        &contact_in_course,
+                        &member_list,
                         &seen_contact_map_second_time,
                     )
-                })
-                .expect("Expected to find host for course!");
+                })?;
 
                 //Remove guest from possible guest list
                 let remove_guest_index = possible_guest_list
@@ -489,24 +915,13 @@ fn create_course_map(
                     .expect("Expected guest in list of possible guests!");
                 possible_guest_list.remove(remove_guest_index);
 
-                //Remove guest from possible host list if exists
-                let remove_host_index = possible_host_in_course_list
-                    .iter()
-                    .position(|x| *x == guest);
-                if remove_host_index.is_some() {
-                    possible_host_in_course_list.remove(
-                        remove_host_index
-                            .expect("Expected guest in list of possible hosts for this course!"),
-                    );
-                }
-
                 seed_index += 1;
 
                 set_seen_people(
                     &mut seen_contact_map,
-                    &mut contact_in_course,
-                    &guest_list,
-                    Rc::clone(&host),
+                    &mut seen_contact_map_second_time,
+                    &member_list,
+                    Rc::clone(&guest),
                 );
                 guest_list.push(guest);
             }
@@ -545,7 +960,15 @@ fn calc_score(
     let mut longest_distance = 0_f64;
 
     for (_, path) in contact_walking_path_set.iter() {
-        let mut path_iter = path.iter();
+        //Walk the courses in the order they are served, not in the order of the set
+        let mut path = path.iter().collect::<Vec<&Rc<CourseInternal>>>();
+        path.sort_by_key(|course| {
+            config
+                .course_name_list
+                .iter()
+                .position(|course_name| *course_name == course.name)
+        });
+        let mut path_iter = path.into_iter();
         let mut distance = 0_f64;
         let mut contact_from;
         let mut contact_to;
@@ -619,7 +1042,7 @@ fn calc_walking_path(
 fn get_contact(
     possible_guest_list: &Vec<Rc<Contact>>,
     seed_id: u8,
-    contact_in_course: &HashSet<Rc<Contact>>,
+    member_list: &Vec<Rc<Contact>>,
     seen_contact_map: &HashMap<Rc<Contact>, HashSet<Rc<Contact>>>,
 ) -> Option<Rc<Contact>> {
     let mut seed = seed_id;
@@ -629,20 +1052,12 @@ fn get_contact(
             .get(contact_index)
             .expect("Expected contact to find in contact list!");
 
-        if contact_in_course.contains(contact) {
-            seed = seed.wrapping_add(1);
-            debug!(
-                "Checking if \"{}\" could be guest... \t{}",
-                contact.team_name,
-                "Contact is already in a course!".red()
-            );
-            continue;
-        }
-
-        if !seen_contact_map
+        let seen_contact_set = seen_contact_map
             .get(contact)
-            .expect("Expected contact to find in seen contact map!")
-            .is_disjoint(&contact_in_course)
+            .expect("Expected contact to find in seen contact map!");
+        if member_list
+            .iter()
+            .any(|member| seen_contact_set.contains(member))
         {
             seed = seed.wrapping_add(1);
             debug!(
@@ -663,7 +1078,7 @@ fn get_contact(
     None
 }
 
-fn calc_distance(
+pub fn calc_distance(
     start_point_latitude: i32,
     start_point_longitude: i32,
     goal_point_latitude: i32,
@@ -699,21 +1114,31 @@ fn course_map_to_contact_map(
 
 fn set_seen_people(
     seen_contact_map: &mut HashMap<Rc<Contact>, HashSet<Rc<Contact>>>,
-    contact_in_course: &mut HashSet<Rc<Contact>>,
-    guest_list: &Vec<Rc<Contact>>,
+    seen_contact_map_second_time: &mut HashMap<Rc<Contact>, HashSet<Rc<Contact>>>,
+    member_list: &Vec<Rc<Contact>>,
     new_contact: Rc<Contact>,
 ) {
-    contact_in_course.insert(Rc::clone(&new_contact));
-    guest_list.iter().for_each(|guest| {
-        seen_contact_map
+    for member in member_list.iter() {
+        let first_time = seen_contact_map
             .get_mut(&new_contact)
             .expect("Expected to find seen contact of new contact!")
-            .insert(Rc::clone(guest));
+            .insert(Rc::clone(member));
         seen_contact_map
-            .get_mut(guest)
-            .expect("Expected to find seen contact of guest!")
+            .get_mut(member)
+            .expect("Expected to find seen contact of member!")
             .insert(Rc::clone(&new_contact));
-    });
+
+        if !first_time {
+            seen_contact_map_second_time
+                .get_mut(&new_contact)
+                .expect("Expected to find second time seen contact of new contact!")
+                .insert(Rc::clone(member));
+            seen_contact_map_second_time
+                .get_mut(member)
+                .expect("Expected to find second time seen contact of member!")
+                .insert(Rc::clone(&new_contact));
+        }
+    }
 }
 
 fn generate_seed() -> Vec<u8> {
@@ -738,19 +1163,62 @@ fn combine_seed(seed_one: &Vec<u8>, seed_two: &Vec<u8>) -> (Vec<u8>, Vec<u8>) {
     (new_seed_one, new_seed_two)
 }
 
-fn generate_seed_from_plan_list(list_of_plans: Vec<PlanInternal>) -> Vec<Vec<u8>> {
-    let top_80_percent = (list_of_plans.len() as f64 * 0.8).ceil() as usize;
-    let mut new_seeds = Vec::new();
+fn select_by_tournament(list_of_plans: &[PlanInternal], tournament_size: usize) -> &PlanInternal {
+    let mut rng = rand::thread_rng();
+    (0..tournament_size)
+        .map(|_| &list_of_plans[rng.gen_range(0..list_of_plans.len())])
+        .min_by(|a, b| a.score.total_cmp(&b.score))
+        .expect("Expect tournament with at least one plan")
+}
+
+fn mutate_seed(seed: &mut [u8], mutation_rate: f64) {
+    let mut rng = rand::thread_rng();
+    for gene in seed.iter_mut() {
+        if rng.gen_bool(mutation_rate) {
+            *gene = rng.gen();
+        }
+    }
+}
 
-    for i in (0..top_80_percent).step_by(2) {
-        if i + 1 < top_80_percent {
-            let (new_seed_one, new_seed_two) =
-                combine_seed(&list_of_plans[i].seed, &list_of_plans[i + 1].seed);
-            new_seeds.push(new_seed_one);
-            new_seeds.push(new_seed_two);
+fn generate_seed_from_plan_list(
+    config: &CalculatorConfigInternal,
+    list_of_plans: Vec<PlanInternal>,
+) -> Vec<Vec<u8>> {
+    let top_percent = (list_of_plans.len() as f64 * config.crossover_rate).ceil() as usize;
+    let mut new_seeds: Vec<Vec<u8>> = list_of_plans
+        .iter()
+        .take(config.elite_count)
+        .map(|plan| plan.seed.clone())
+        .collect();
+
+    let mut parent_list = Vec::new();
+    match config.selection {
+        Selection::Truncation => {
+            for i in (0..top_percent).step_by(2) {
+                if i + 1 < top_percent {
+                    parent_list.push((&list_of_plans[i], &list_of_plans[i + 1]));
+                }
+            }
+        }
+        Selection::Tournament(tournament_size) => {
+            for _ in (0..top_percent).step_by(2) {
+                parent_list.push((
+                    select_by_tournament(&list_of_plans, tournament_size),
+                    select_by_tournament(&list_of_plans, tournament_size),
+                ));
+            }
         }
     }
 
+    for (parent_one, parent_two) in parent_list {
+        let (mut new_seed_one, mut new_seed_two) = combine_seed(&parent_one.seed, &parent_two.seed);
+        mutate_seed(&mut new_seed_one, config.mutation_rate);
+        mutate_seed(&mut new_seed_two, config.mutation_rate);
+        new_seeds.push(new_seed_one);
+        new_seeds.push(new_seed_two);
+    }
+    new_seeds.truncate(list_of_plans.len());
+
     for _ in new_seeds.len()..list_of_plans.len() {
         new_seeds.push(generate_seed());
     }
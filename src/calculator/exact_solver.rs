@@ -0,0 +1,489 @@
+use std::{
+    collections::HashMap,
+    time::{Duration, Instant},
+};
+
+use log::info;
+
+use crate::calculation_job::CancellationToken;
+
+use super::{calc_distance, CalculatorConfig, CalculatorConfigInternal, Course, Plan};
+
+// Teams are kept as bits of an u32, the search gets too slow for more anyway
+pub const MAX_EXACT_TEAMS: usize = 30;
+
+#[derive(Debug, Clone)]
+pub struct ExactPlan {
+    pub plan: Plan,
+    // No plan has a smaller score than this
+    pub lower_bound: f64,
+    // (score - lower bound) / score, 0 once the plan is proven optimal
+    pub gap: f64,
+    pub optimal: bool,
+}
+
+// Branch and bound over host and guest assignment. Every team hosts at most once
+// and meets every other team at most once, the longest walk is minimized.
+pub fn solve_exact(
+    config: &CalculatorConfig,
+    time_limit: Duration,
+    token: Option<&CancellationToken>,
+) -> Result<ExactPlan, String> {
+    config.check()?;
+    solve(&config.get_internal(), time_limit, token, |_| {})
+}
+
+pub(super) fn solve(
+    config: &CalculatorConfigInternal,
+    time_limit: Duration,
+    token: Option<&CancellationToken>,
+    on_plan: impl FnMut(&Plan),
+) -> Result<ExactPlan, String> {
+    let number_of_teams = config.contact_list.len();
+    if number_of_teams > MAX_EXACT_TEAMS {
+        return Err(format!(
+            "The exact solver supports up to {} teams, but there are {}!",
+            MAX_EXACT_TEAMS, number_of_teams
+        ));
+    }
+
+    let mut search = Search::new(config, Instant::now() + time_limit, token, on_plan);
+    let open_bound = search.choose_host(0, 0, 0);
+    let aborted = search.aborted;
+
+    let assignment = match search.best_assignment.take() {
+        Some(assignment) => assignment,
+        None if aborted => return Err("No plan found within the time limit!".to_string()),
+        None => return Err("There is no plan in which teams meet at most once!".to_string()),
+    };
+    let plan = search.to_exact_plan(&assignment, open_bound);
+    info!(
+        "Exact solver finished with score {} and gap {:.2}%",
+        plan.plan.score,
+        plan.gap * 100.0
+    );
+    Ok(plan)
+}
+
+// Hosts and guests of every hosting, per course
+type Assignment = Vec<Vec<(usize, Vec<usize>)>>;
+
+struct Table {
+    host: usize,
+    member_mask: u32,
+    guest_list: Vec<usize>,
+}
+
+struct Search<'a, F: FnMut(&Plan)> {
+    config: &'a CalculatorConfigInternal,
+    distance: Vec<Vec<f64>>,
+    start_distance: Vec<f64>,
+    goal_distance: Vec<f64>,
+    hostings_per_course: Vec<usize>,
+    hosted_mask: u32,
+    met_mask: Vec<u32>,
+    // Index of the host the team ate at in the previous course
+    position: Vec<Option<usize>>,
+    length: Vec<f64>,
+    courses_done: Vec<usize>,
+    // Hostings of this and the later courses without a host yet
+    open_host_slots: usize,
+    // Smallest walk between two homes, from the start and to the goal
+    min_distance: f64,
+    min_start_distance: f64,
+    min_goal_distance: f64,
+    table_list: Vec<Table>,
+    extended_tables: usize,
+    assignment: Assignment,
+    best_score: f64,
+    best_assignment: Option<Assignment>,
+    deadline: Instant,
+    token: Option<&'a CancellationToken>,
+    aborted: bool,
+    on_plan: F,
+}
+
+impl<'a, F: FnMut(&Plan)> Search<'a, F> {
+    fn new(
+        config: &'a CalculatorConfigInternal,
+        deadline: Instant,
+        token: Option<&'a CancellationToken>,
+        on_plan: F,
+    ) -> Self {
+        let contact_list = &config.contact_list;
+        let distance_to = |point: Option<(i32, i32)>| -> Vec<f64> {
+            contact_list
+                .iter()
+                .map(|contact| {
+                    point.map_or(0.0, |(latitude, longitude)| {
+                        calc_distance(latitude, longitude, contact.latitude, contact.longitude)
+                    })
+                })
+                .collect()
+        };
+
+        let distance: Vec<Vec<f64>> = contact_list
+            .iter()
+            .map(|from| {
+                contact_list
+                    .iter()
+                    .map(|to| {
+                        calc_distance(from.latitude, from.longitude, to.latitude, to.longitude)
+                    })
+                    .collect()
+            })
+            .collect();
+        let min_distance = distance
+            .iter()
+            .enumerate()
+            .flat_map(|(from, row)| row.iter().enumerate().filter(move |(to, _)| *to != from))
+            .map(|(_, distance)| *distance)
+            .fold(f64::INFINITY, f64::min);
+        let start_distance = distance_to(config.start_point);
+        let goal_distance = distance_to(config.goal_point);
+        let hostings_per_course: Vec<usize> = config
+            .course_name_list
+            .iter()
+            .map(|course_name| config.get_number_of_hostings(course_name))
+            .collect();
+
+        Search {
+            config,
+            min_distance: if min_distance.is_finite() {
+                min_distance
+            } else {
+                0.0
+            },
+            min_start_distance: start_distance.iter().cloned().fold(f64::INFINITY, f64::min),
+            min_goal_distance: goal_distance.iter().cloned().fold(f64::INFINITY, f64::min),
+            distance,
+            start_distance,
+            goal_distance,
+            open_host_slots: hostings_per_course.iter().sum(),
+            hostings_per_course,
+            hosted_mask: 0,
+            met_mask: vec![0; contact_list.len()],
+            position: vec![None; contact_list.len()],
+            length: vec![0.0; contact_list.len()],
+            courses_done: vec![0; contact_list.len()],
+            table_list: Vec::new(),
+            extended_tables: 0,
+            assignment: Vec::new(),
+            best_score: f64::MAX,
+            best_assignment: None,
+            deadline,
+            token,
+            aborted: false,
+            on_plan,
+        }
+    }
+
+    fn number_of_teams(&self) -> usize {
+        self.config.contact_list.len()
+    }
+
+    // Walk of a team to the given host, including the goal after the last course
+    fn leg(&self, team: usize, host: usize, course: usize) -> f64 {
+        let to_host = match self.position[team] {
+            Some(from) => self.distance[from][host],
+            None => self.start_distance[host],
+        };
+        if course + 1 == self.hostings_per_course.len() {
+            to_host + self.goal_distance[host]
+        } else {
+            to_host
+        }
+    }
+
+    fn longest_walk(&self) -> f64 {
+        self.length.iter().cloned().fold(0.0, f64::max)
+    }
+
+    // Longest walk any team has to make at least, whatever the rest of the plan is
+    fn lower_bound(&self) -> f64 {
+        let number_of_courses = self.hostings_per_course.len();
+        let unhosted = self.number_of_teams() - self.hosted_mask.count_ones() as usize;
+        // If every team without a hosting has to host, it walks home and from there to the goal
+        let all_host = self.open_host_slots == unhosted;
+
+        (0..self.number_of_teams())
+            .map(|team| {
+                let moves_left = number_of_courses - self.courses_done[team];
+                if moves_left == 0 {
+                    return self.length[team];
+                }
+                let first_move = match self.position[team] {
+                    Some(_) => self.min_distance,
+                    None => self.min_start_distance,
+                };
+                let by_moves = first_move
+                    + (moves_left - 1) as f64 * self.min_distance
+                    + self.min_goal_distance;
+                let by_hosting = if all_host && self.hosted_mask & (1 << team) == 0 {
+                    let to_home = match self.position[team] {
+                        Some(from) => self.distance[from][team],
+                        None => self.start_distance[team],
+                    };
+                    to_home + self.goal_distance[team]
+                } else {
+                    0.0
+                };
+                self.length[team] + by_moves.max(by_hosting)
+            })
+            .fold(0.0, f64::max)
+    }
+
+    fn should_abort(&mut self) -> bool {
+        if !self.aborted
+            && (Instant::now() >= self.deadline
+                || self.token.is_some_and(CancellationToken::is_cancelled))
+        {
+            self.aborted = true;
+        }
+        self.aborted
+    }
+
+    // Picks the hosts of a course in increasing order, so every set is tried once.
+    // Returns the lowest bound of the parts of the tree left out by an abort.
+    fn choose_host(&mut self, course: usize, next_team: usize, chosen: usize) -> f64 {
+        if course == self.hostings_per_course.len() {
+            self.save_plan();
+            return f64::INFINITY;
+        }
+        if chosen == self.hostings_per_course[course] {
+            self.extended_tables = 0;
+            return self.choose_table(course, 0);
+        }
+
+        // This course picks its remaining hosts from the teams after the last one, the
+        // later courses from every team which hasn't hosted yet
+        let needed_hosts = self.hostings_per_course[course] - chosen;
+        let later_hosts: usize = self.hostings_per_course[course + 1..].iter().sum();
+        let unhosted = self.number_of_teams() - self.hosted_mask.count_ones() as usize;
+        if unhosted < needed_hosts + later_hosts {
+            return f64::INFINITY;
+        }
+        let current_walk = self.longest_walk();
+        let candidate_list: Vec<(usize, f64)> = (next_team..self.number_of_teams())
+            .filter(|team| self.hosted_mask & (1 << team) == 0)
+            .map(|team| {
+                (
+                    team,
+                    current_walk.max(self.length[team] + self.leg(team, team, course)),
+                )
+            })
+            .collect();
+        if candidate_list.len() < needed_hosts {
+            return f64::INFINITY;
+        }
+
+        let mut open_bound = f64::INFINITY;
+        for (index, &(team, bound)) in candidate_list.iter().enumerate() {
+            if self.should_abort() {
+                return open_bound.min(self.lower_bound().max(min_bound(&candidate_list[index..])));
+            }
+            if bound >= self.best_score {
+                continue;
+            }
+
+            let leg = self.leg(team, team, course);
+            let position = self.position[team];
+            self.hosted_mask |= 1 << team;
+            self.open_host_slots -= 1;
+            self.length[team] += leg;
+            self.position[team] = Some(team);
+            self.courses_done[team] += 1;
+            self.table_list.push(Table {
+                host: team,
+                member_mask: 1 << team,
+                guest_list: Vec::new(),
+            });
+
+            if self.lower_bound() < self.best_score {
+                open_bound = open_bound.min(self.choose_host(course, team + 1, chosen + 1));
+            }
+
+            self.table_list.pop();
+            self.courses_done[team] -= 1;
+            self.position[team] = position;
+            self.length[team] -= leg;
+            self.open_host_slots += 1;
+            self.hosted_mask &= !(1 << team);
+        }
+        open_bound
+    }
+
+    // Seats the next team without a table in this course
+    fn choose_table(&mut self, course: usize, next_team: usize) -> f64 {
+        let seated_mask = self
+            .table_list
+            .iter()
+            .fold(0, |mask, table| mask | table.member_mask);
+        let team =
+            match (next_team..self.number_of_teams()).find(|team| seated_mask & (1 << team) == 0) {
+                Some(team) => team,
+                None => return self.finish_course(course),
+            };
+
+        let number_of_tables = self.table_list.len();
+        let base_size = self.number_of_teams() / number_of_tables;
+        let overhang = self.number_of_teams() % number_of_tables;
+        let current_walk = self.longest_walk();
+        let mut candidate_list: Vec<(usize, f64)> = self
+            .table_list
+            .iter()
+            .enumerate()
+            .filter(|(_, table)| {
+                let size = table.guest_list.len() + 1;
+                size < base_size || (size == base_size && self.extended_tables < overhang)
+            })
+            .filter(|(_, table)| self.met_mask[team] & table.member_mask == 0)
+            .map(|(index, table)| {
+                let walk = self.length[team] + self.leg(team, table.host, course);
+                (index, current_walk.max(walk))
+            })
+            .collect();
+        // Shortest walks first, so good plans are found early
+        candidate_list.sort_by(|a, b| a.1.total_cmp(&b.1));
+
+        let mut open_bound = f64::INFINITY;
+        for (index, &(table_index, bound)) in candidate_list.iter().enumerate() {
+            if self.should_abort() {
+                return open_bound.min(self.lower_bound().max(min_bound(&candidate_list[index..])));
+            }
+            if bound >= self.best_score {
+                break;
+            }
+
+            let host = self.table_list[table_index].host;
+            let leg = self.leg(team, host, course);
+            let position = self.position[team];
+            let member_mask = self.table_list[table_index].member_mask;
+            let extended = self.table_list[table_index].guest_list.len() + 1 == base_size;
+
+            self.length[team] += leg;
+            self.position[team] = Some(host);
+            self.courses_done[team] += 1;
+            self.met_mask[team] |= member_mask;
+            for member in iter_mask(member_mask) {
+                self.met_mask[member] |= 1 << team;
+            }
+            if extended {
+                self.extended_tables += 1;
+            }
+            let table = &mut self.table_list[table_index];
+            table.member_mask |= 1 << team;
+            table.guest_list.push(team);
+
+            if self.lower_bound() < self.best_score {
+                open_bound = open_bound.min(self.choose_table(course, team + 1));
+            }
+
+            let table = &mut self.table_list[table_index];
+            table.guest_list.pop();
+            table.member_mask &= !(1 << team);
+            if extended {
+                self.extended_tables -= 1;
+            }
+            for member in iter_mask(member_mask) {
+                self.met_mask[member] &= !(1 << team);
+            }
+            self.met_mask[team] &= !member_mask;
+            self.courses_done[team] -= 1;
+            self.position[team] = position;
+            self.length[team] -= leg;
+        }
+        open_bound
+    }
+
+    fn finish_course(&mut self, course: usize) -> f64 {
+        let table_list: Vec<Table> = self.table_list.drain(..).collect();
+        self.assignment.push(
+            table_list
+                .iter()
+                .map(|table| (table.host, table.guest_list.clone()))
+                .collect(),
+        );
+
+        let open_bound = self.choose_host(course + 1, 0, 0);
+
+        self.assignment.pop();
+        self.table_list = table_list;
+        open_bound
+    }
+
+    fn save_plan(&mut self) {
+        let score = self.longest_walk();
+        if score >= self.best_score {
+            return;
+        }
+        self.best_score = score;
+        let assignment = self.assignment.clone();
+        let plan = self.to_plan(&assignment);
+        self.best_assignment = Some(assignment);
+        info!("Exact solver found plan with score {}", score);
+        (self.on_plan)(&plan);
+    }
+
+    fn to_exact_plan(&self, assignment: &Assignment, open_bound: f64) -> ExactPlan {
+        let score = self.best_score;
+        let lower_bound = open_bound.min(score);
+        let gap = if score > 0.0 {
+            (score - lower_bound) / score
+        } else {
+            0.0
+        };
+        ExactPlan {
+            plan: self.to_plan(assignment),
+            lower_bound,
+            gap,
+            optimal: gap == 0.0,
+        }
+    }
+
+    fn to_plan(&self, assignment: &Assignment) -> Plan {
+        let contact_list = &self.config.contact_list;
+        let mut course_map: HashMap<String, Vec<Course>> = HashMap::new();
+        let mut walking_path: HashMap<_, Vec<Course>> = HashMap::new();
+        for (course_name, table_list) in self.config.course_name_list.iter().zip(assignment) {
+            for (host, guest_list) in table_list {
+                let course = Course {
+                    name: course_name.to_string(),
+                    host: contact_list[*host].as_ref().clone(),
+                    guest_list: guest_list
+                        .iter()
+                        .map(|guest| contact_list[*guest].as_ref().clone())
+                        .collect(),
+                };
+                for team in std::iter::once(host).chain(guest_list.iter()) {
+                    walking_path
+                        .entry(contact_list[*team].as_ref().clone())
+                        .or_default()
+                        .push(course.clone());
+                }
+                course_map
+                    .entry(course_name.to_string())
+                    .or_default()
+                    .push(course);
+            }
+        }
+
+        Plan {
+            seed: Vec::new(),
+            course_map,
+            walking_path,
+            score: self.best_score,
+        }
+    }
+}
+
+fn min_bound(candidate_list: &[(usize, f64)]) -> f64 {
+    candidate_list
+        .iter()
+        .map(|(_, bound)| *bound)
+        .fold(f64::INFINITY, f64::min)
+}
+
+fn iter_mask(mask: u32) -> impl Iterator<Item = usize> {
+    (0..32).filter(move |bit| mask & (1 << bit) != 0)
+}
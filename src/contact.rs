@@ -34,9 +34,17 @@ impl Contact {
             longitude: f64_to_i32(longitude),
         }
     }
+
+    pub fn get_latitude(&self) -> f64 {
+        i32_to_f64(self.latitude)
+    }
+
+    pub fn get_longitude(&self) -> f64 {
+        i32_to_f64(self.longitude)
+    }
 }
 
-fn f64_to_i32(value: f64) -> i32 {
+pub(crate) fn f64_to_i32(value: f64) -> i32 {
     (value * 10f64.powi(5)).round() as i32
 }
 
@@ -101,3 +109,100 @@ impl ContactLoader {
         return Ok(contact_list);
     }
 }
+
+pub(crate) const KM_PER_DEGREE: f64 = 111.32;
+// Teams further away from the others than this are likely geocoding errors
+const OUTLIER_FACTOR: f64 = 3.0;
+const OUTLIER_MIN_DISTANCE_KM: f64 = 2.0;
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct ContactIssue {
+    // Index of the contact in the list
+    pub index: usize,
+    pub message: String,
+    // Errors have to be fixed before calculating, warnings are hints only
+    pub is_error: bool,
+}
+
+fn i32_to_f64(value: i32) -> f64 {
+    value as f64 / 10f64.powi(5)
+}
+
+pub fn check_coordinates(latitude: f64, longitude: f64) -> Result<(), String> {
+    if !(-90.0..=90.0).contains(&latitude) {
+        return Err(format!("Latitude {} is not between -90 and 90!", latitude));
+    }
+    if !(-180.0..=180.0).contains(&longitude) {
+        return Err(format!(
+            "Longitude {} is not between -180 and 180!",
+            longitude
+        ));
+    }
+    Ok(())
+}
+
+pub fn validate_contact_list(contact_list: &[Contact]) -> Vec<ContactIssue> {
+    let mut issue_list = Vec::new();
+    for (index, contact) in contact_list.iter().enumerate() {
+        let error = |message: String| ContactIssue {
+            index,
+            message,
+            is_error: true,
+        };
+        if contact.team_name.trim().is_empty() {
+            issue_list.push(error("Team name is missing!".to_string()));
+        } else if contact_list[..index]
+            .iter()
+            .any(|other| other.team_name.trim() == contact.team_name.trim())
+        {
+            issue_list.push(error(format!(
+                "Team name \"{}\" is used more than once!",
+                contact.team_name
+            )));
+        }
+        if let Err(message) = check_coordinates(contact.get_latitude(), contact.get_longitude()) {
+            issue_list.push(error(message));
+        }
+    }
+    issue_list.extend(get_outlier_list(contact_list));
+    issue_list
+}
+
+// Compares the distance of every team to the median position with the typical distance
+fn get_outlier_list(contact_list: &[Contact]) -> Vec<ContactIssue> {
+    if contact_list.len() < 3 {
+        return Vec::new();
+    }
+    let median = |mut value_list: Vec<f64>| {
+        value_list.sort_by(|a, b| a.total_cmp(b));
+        value_list[value_list.len() / 2]
+    };
+    let center_latitude = median(contact_list.iter().map(Contact::get_latitude).collect());
+    let center_longitude = median(contact_list.iter().map(Contact::get_longitude).collect());
+
+    let distance_list: Vec<f64> = contact_list
+        .iter()
+        .map(|contact| {
+            let latitude_km = (contact.get_latitude() - center_latitude) * KM_PER_DEGREE;
+            let longitude_km = (contact.get_longitude() - center_longitude)
+                * KM_PER_DEGREE
+                * center_latitude.to_radians().cos();
+            (latitude_km.powi(2) + longitude_km.powi(2)).sqrt()
+        })
+        .collect();
+    let limit = (median(distance_list.clone()) * OUTLIER_FACTOR).max(OUTLIER_MIN_DISTANCE_KM);
+
+    distance_list
+        .iter()
+        .enumerate()
+        .filter(|(_, distance)| **distance > limit)
+        .map(|(index, distance)| ContactIssue {
+            index,
+            message: format!(
+                "{:.1} km away from the other teams, please check the coordinates",
+                distance
+            ),
+            is_error: false,
+        })
+        .collect()
+}
@@ -1,6 +1,9 @@
+pub mod benchmark;
+pub mod calculation_job;
 pub mod calculator;
 pub mod contact;
 mod image_collection;
+pub mod scenario;
 pub mod screen;
 
 use iced::widget::button;
@@ -73,6 +76,15 @@ pub enum Message {
 
     //Load screen actions
     LoadData,
+    AddContact,
+    EditContact(usize),
+    DeleteContact(usize),
+    UpdateContactTeamName(String),
+    UpdateContactAddress(String),
+    UpdateContactLatitude(String),
+    UpdateContactLongitude(String),
+    SaveContact,
+    CancelContactEdit,
 
     //Rule screen actions
     ShowStartPositionInputField(bool),
@@ -89,6 +101,19 @@ pub enum Message {
     UpdateCourseNameList(usize, String),
     AddCourseName(String),
     DeleteCourseName(usize),
+    ToggleCourseWithMoreHosts(usize, bool),
+    UpdateGuestsPerHosting(String),
+    UpdateStopAfterNoImprovement(String),
+    ShowAdvancedSettings(bool),
+    UpdateNumberOfThreads(String),
+    UpdatePopulationSize(String),
+    UpdateCrossoverRate(String),
+    UpdateMutationRate(String),
+    UpdateEliteCount(String),
+    UpdateTournamentSize(String),
+    UpdateTimeBudget(String),
+    UpdateIterationBudget(String),
+    ToggleExactSolver(bool),
 
     //Calculate screen actions
     Tick,
@@ -0,0 +1,177 @@
+use std::f64::consts::PI;
+
+use rand::{rngs::StdRng, Rng, SeedableRng};
+
+use crate::{
+    calculator::CalculatorConfig,
+    contact::{f64_to_i32, Contact, KM_PER_DEGREE},
+};
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Distribution {
+    Uniform,
+    // Teams live around a few neighbourhoods
+    Clustered {
+        number_of_clusters: usize,
+        spread_km: f64,
+    },
+}
+
+#[derive(Debug, Clone)]
+pub struct ScenarioConfig {
+    name: String,
+    number_of_teams: usize,
+    number_of_courses: usize,
+    center: (f64, f64),
+    radius_km: f64,
+    distribution: Distribution,
+    with_start_point: bool,
+    with_goal_point: bool,
+    // Same seed, same scenario
+    seed: u64,
+}
+
+#[derive(Debug, Clone)]
+pub struct Scenario {
+    pub name: String,
+    pub contact_list: Vec<Contact>,
+    pub course_name_list: Vec<String>,
+    pub start_point: Option<(i32, i32)>,
+    pub goal_point: Option<(i32, i32)>,
+}
+
+impl ScenarioConfig {
+    pub fn new(name: &str, number_of_teams: usize, number_of_courses: usize) -> Self {
+        ScenarioConfig {
+            name: name.to_string(),
+            number_of_teams,
+            number_of_courses,
+            // Frankfurt am Main, like the test data
+            center: (50.11, 8.68),
+            radius_km: 5.0,
+            distribution: Distribution::Uniform,
+            with_start_point: false,
+            with_goal_point: false,
+            seed: 0,
+        }
+    }
+
+    pub fn set_center(&mut self, latitude: f64, longitude: f64) {
+        self.center = (latitude, longitude);
+    }
+
+    pub fn set_radius_km(&mut self, radius_km: f64) {
+        self.radius_km = radius_km;
+    }
+
+    pub fn set_distribution(&mut self, distribution: Distribution) {
+        self.distribution = distribution;
+    }
+
+    pub fn set_start_and_goal_point(&mut self, with_start_point: bool, with_goal_point: bool) {
+        self.with_start_point = with_start_point;
+        self.with_goal_point = with_goal_point;
+    }
+
+    pub fn set_seed(&mut self, seed: u64) {
+        self.seed = seed;
+    }
+
+    pub fn generate(&self) -> Result<Scenario, String> {
+        if self.number_of_teams > u8::MAX as usize + 1 {
+            return Err(format!(
+                "At most {} teams are supported!",
+                u8::MAX as usize + 1
+            ));
+        }
+        let mut rng = StdRng::seed_from_u64(self.seed);
+
+        let cluster_list: Vec<(f64, f64)> = match self.distribution {
+            Distribution::Uniform => Vec::new(),
+            Distribution::Clustered {
+                number_of_clusters, ..
+            } => {
+                if number_of_clusters == 0 {
+                    return Err("At least one cluster is needed!".to_string());
+                }
+                (0..number_of_clusters)
+                    .map(|_| self.random_point(&mut rng, self.center, self.radius_km))
+                    .collect()
+            }
+        };
+
+        let contact_list = (0..self.number_of_teams)
+            .map(|index| {
+                let (latitude, longitude) = match self.distribution {
+                    Distribution::Uniform => {
+                        self.random_point(&mut rng, self.center, self.radius_km)
+                    }
+                    Distribution::Clustered { spread_km, .. } => {
+                        let cluster = cluster_list[rng.gen_range(0..cluster_list.len())];
+                        self.random_point(&mut rng, cluster, spread_km)
+                    }
+                };
+                Contact::new(
+                    index as u8,
+                    &format!("Team {}", index + 1),
+                    &format!("Generated address {}", index + 1),
+                    latitude,
+                    longitude,
+                )
+            })
+            .collect();
+
+        let mut get_point = |with_point: bool| {
+            with_point.then(|| {
+                let (latitude, longitude) =
+                    self.random_point(&mut rng, self.center, self.radius_km);
+                (f64_to_i32(latitude), f64_to_i32(longitude))
+            })
+        };
+        let start_point = get_point(self.with_start_point);
+        let goal_point = get_point(self.with_goal_point);
+
+        Ok(Scenario {
+            name: self.name.clone(),
+            contact_list,
+            course_name_list: (1..=self.number_of_courses)
+                .map(|number| format!("Course {}", number))
+                .collect(),
+            start_point,
+            goal_point,
+        })
+    }
+
+    // Uniform in a circle around the center
+    fn random_point(&self, rng: &mut StdRng, center: (f64, f64), radius_km: f64) -> (f64, f64) {
+        let distance_km = radius_km * rng.gen::<f64>().sqrt();
+        let angle = rng.gen::<f64>() * 2.0 * PI;
+        let latitude = center.0 + distance_km * angle.sin() / KM_PER_DEGREE;
+        let longitude =
+            center.1 + distance_km * angle.cos() / (KM_PER_DEGREE * center.0.to_radians().cos());
+        (latitude, longitude)
+    }
+}
+
+impl Scenario {
+    // Teams that don't fill a whole hosting go to the extra hostings of the last course
+    pub fn to_calculator_config(&self) -> CalculatorConfig {
+        let mut config = CalculatorConfig::new_with_start_and_goal(
+            self.start_point,
+            self.goal_point,
+            None,
+            self.course_name_list.clone(),
+            self.contact_list.clone(),
+        );
+        if !self
+            .contact_list
+            .len()
+            .is_multiple_of(self.course_name_list.len())
+        {
+            config.set_course_with_more_hosts_list(
+                self.course_name_list.last().into_iter().cloned().collect(),
+            );
+        }
+        config
+    }
+}
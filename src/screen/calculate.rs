@@ -6,14 +6,17 @@ use iced::{
         Vertical,
     },
     border::Radius,
-    widget::{button, center, column, container, row, scrollable, text, Button, Column, Row, Text},
+    widget::{
+        button, center, column, container, row, scrollable, svg, text, Button, Column, Row, Text,
+    },
     Alignment::Center,
     Border, Color, Element,
     Length::{self, Fill},
 };
 
 use crate::{
-    calculator::{self, Calculator, CalculatorConfig, Plan},
+    calculation_job::JobState,
+    calculator::{self, Calculator, CalculatorConfig, IterationStat, Plan},
     contact::{self, Contact},
     image_collection::IMAGE_COLLECTION,
     Message,
@@ -23,6 +26,7 @@ use super::Screen;
 
 pub(crate) struct CalculateScreen {
     calculator: Option<Calculator>,
+    state: Option<JobState>,
     err_message: Option<String>,
 }
 
@@ -37,9 +41,9 @@ impl Screen for CalculateScreen {
         let calculator = calculator_opt.expect("Expected calculator");
 
         let start_time = calculator
-            .start_time
+            .get_start_time()
             .map(|start_time| {
-                if let Some(stop_time) = calculator.stop_time {
+                if let Some(stop_time) = calculator.get_stop_time() {
                     let duration = stop_time.duration_since(start_time);
                     format!(
                         "{:02}:{:02}",
@@ -67,16 +71,46 @@ impl Screen for CalculateScreen {
             .map(|plan| format!("{:.0}", plan.score))
             .unwrap_or_else(|| "-".to_string());
 
+        // Only the exact solver knows how far its plan is from the best one
+        let gap = calculator
+            .gap
+            .lock()
+            .expect("Failed to lock gap")
+            .map(|gap| format!("{:.1}%", gap * 100.0))
+            .unwrap_or_else(|| "-".to_string());
+
         let progress_info = container(row![
-            column!["Time:", "Iteration:", "Score:"]
+            column!["Time:", "Iteration:", "Score:", "Gap:"]
                 .align_x(Horizontal::Left)
                 .padding(10),
-            column![text(start_time), text(iteration), text(score)]
+            column![text(start_time), text(iteration), text(score), text(gap)]
                 .align_x(Horizontal::Right)
                 .padding(10),
         ])
         .align_x(Horizontal::Center);
 
+        let headline = match self.state {
+            Some(JobState::Stopping) => "Stopping Calculation",
+            Some(JobState::Finished) => "Calculation finished",
+            Some(JobState::Failed(_)) => "Calculation failed",
+            _ => "Calculation in Progress",
+        };
+        let err_message = text(self.err_message.clone().unwrap_or_default())
+            .color(Color::from_rgb(0.8, 0.2, 0.2));
+
+        let chart = svg(svg::Handle::from_memory(
+            get_convergence_chart(
+                calculator
+                    .history
+                    .lock()
+                    .expect("Expect history")
+                    .get_stat_list(),
+            )
+            .into_bytes(),
+        ))
+        .width(CHART_WIDTH)
+        .height(CHART_HEIGHT);
+
         let next_button = container(
             if calculator
                 .top_plan
@@ -94,8 +128,10 @@ impl Screen for CalculateScreen {
         container(
             container(
                 column![
-                    text("Calculation in Progress").size(24),
+                    text(headline).size(24),
+                    err_message,
                     progress_info,
+                    chart,
                     next_button,
                 ]
                 .padding(10)
@@ -123,6 +159,14 @@ impl Screen for CalculateScreen {
     }
     fn update(&mut self, event: Message) {
         match event {
+            Message::Tick => {
+                if let Some(calculator) = &mut self.calculator {
+                    self.state = calculator.get_state();
+                }
+                if let Some(JobState::Failed(message)) = &self.state {
+                    self.err_message = Some(message.clone());
+                }
+            }
             _ => {}
         }
     }
@@ -132,19 +176,24 @@ impl CalculateScreen {
     pub fn new() -> Self {
         CalculateScreen {
             calculator: None,
+            state: None,
             err_message: None,
         }
     }
 
     pub fn start_calculation(&mut self, calculator_config: CalculatorConfig) {
+        // Dropping the previous calculator cancels its threads without waiting for them
+        self.calculator = None;
         let mut calculator = calculator::Calculator::new(calculator_config);
-        calculator.calculate();
+        self.err_message = calculator.calculate().err();
+        self.state = calculator.get_state();
         self.calculator = Some(calculator);
     }
 
     pub fn stop_calculation(&mut self) {
-        if let Some(calculator) = &mut self.calculator {
-            calculator.stop();
+        // The threads are joined by polling the state on every tick
+        if let Some(calculator) = &self.calculator {
+            self.state = calculator.cancel();
         }
     }
 
@@ -158,3 +207,53 @@ impl CalculateScreen {
         })
     }
 }
+
+const CHART_WIDTH: f32 = 400.0;
+const CHART_HEIGHT: f32 = 200.0;
+// Keeps the svg small for long calculations
+const MAX_CHART_POINTS: usize = 200;
+
+// Draws best score and population mean over the iterations as svg
+fn get_convergence_chart(history: &[IterationStat]) -> String {
+    let step = (history.len() / MAX_CHART_POINTS).max(1);
+    let point_list: Vec<&IterationStat> = history
+        .iter()
+        .filter(|stat| stat.best_score != f64::MAX)
+        .step_by(step)
+        .collect();
+
+    let score_list = point_list.iter().flat_map(|stat| {
+        [stat.best_score, stat.population_mean]
+            .into_iter()
+            .filter(|score| *score != f64::MAX)
+    });
+    let min_score = score_list.clone().fold(f64::MAX, f64::min);
+    let max_score = score_list.fold(f64::MIN, f64::max);
+    let max_iteration = point_list.last().map(|stat| stat.iteration).unwrap_or(1);
+
+    let x = |iteration: usize| iteration as f64 / max_iteration.max(1) as f64 * CHART_WIDTH as f64;
+    let y = |score: f64| {
+        let range = (max_score - min_score).max(1.0);
+        CHART_HEIGHT as f64 - (score - min_score) / range * (CHART_HEIGHT as f64 - 10.0) - 5.0
+    };
+    let polyline = |score: fn(&IterationStat) -> f64| {
+        point_list
+            .iter()
+            .filter(|stat| score(stat) != f64::MAX)
+            .map(|stat| format!("{:.1},{:.1}", x(stat.iteration), y(score(stat))))
+            .collect::<Vec<String>>()
+            .join(" ")
+    };
+
+    format!(
+        r##"<svg xmlns="http://www.w3.org/2000/svg" width="{w}" height="{h}" viewBox="0 0 {w} {h}">
+<rect width="{w}" height="{h}" fill="none" stroke="#E6E6E6"/>
+<polyline points="{mean}" fill="none" stroke="#999999" stroke-dasharray="4 3"/>
+<polyline points="{best}" fill="none" stroke="#C66741" stroke-width="2"/>
+</svg>"##,
+        w = CHART_WIDTH,
+        h = CHART_HEIGHT,
+        mean = polyline(|stat| stat.population_mean),
+        best = polyline(|stat| stat.best_score),
+    )
+}
@@ -4,14 +4,16 @@ use iced::{
         Vertical,
     },
     border::Radius,
-    widget::{button, column, container, row, scrollable, text, Button, Column, Row, Text},
+    widget::{
+        button, column, container, row, scrollable, text, text_input, Button, Column, Row, Text,
+    },
     Alignment::Center,
     Border, Color, Element,
     Length::{self, Fill},
 };
 
 use crate::{
-    contact::{self, Contact},
+    contact::{self, check_coordinates, validate_contact_list, Contact, ContactIssue},
     image_collection::IMAGE_COLLECTION,
     Message,
 };
@@ -20,9 +22,35 @@ use super::Screen;
 
 pub(crate) struct LoadScreen {
     contact_list: Option<Vec<Contact>>,
+    contact_edit: Option<ContactEdit>,
+    err_message: Option<String>,
+}
+
+// Input of the contact form, kept as typed until it is saved
+#[derive(Debug, Clone, Default)]
+struct ContactEdit {
+    // None for a new contact
+    index: Option<usize>,
+    team_name: String,
+    address: String,
+    latitude: String,
+    longitude: String,
     err_message: Option<String>,
 }
 
+impl ContactEdit {
+    fn from_contact(index: usize, contact: &Contact) -> Self {
+        ContactEdit {
+            index: Some(index),
+            team_name: contact.team_name.clone(),
+            address: contact.address.clone(),
+            latitude: contact.get_latitude().to_string(),
+            longitude: contact.get_longitude().to_string(),
+            err_message: None,
+        }
+    }
+}
+
 impl Screen for LoadScreen {
     fn get(&self) -> Element<Message> {
         if self.contact_list.is_none() {
@@ -59,6 +87,65 @@ impl Screen for LoadScreen {
                 }
 
                 self.contact_list = contact_list.clone();
+                self.contact_edit = None;
+            }
+            Message::AddContact => {
+                self.contact_list.get_or_insert_with(Vec::new);
+                self.contact_edit = Some(ContactEdit::default());
+            }
+            Message::EditContact(index) => {
+                if let Some(contact) = self
+                    .contact_list
+                    .as_ref()
+                    .and_then(|contact_list| contact_list.get(index))
+                {
+                    self.contact_edit = Some(ContactEdit::from_contact(index, contact));
+                }
+            }
+            Message::DeleteContact(index) => {
+                if let Some(contact_list) = self.contact_list.as_mut() {
+                    contact_list.remove(index);
+                }
+                // Keep editing the same contact, its index moved
+                match self.contact_edit.as_mut().and_then(|edit| edit.index) {
+                    Some(edit_index) if edit_index == index => self.contact_edit = None,
+                    Some(edit_index) if edit_index > index => {
+                        if let Some(edit) = self.contact_edit.as_mut() {
+                            edit.index = Some(edit_index - 1);
+                        }
+                    }
+                    _ => {}
+                }
+            }
+            Message::UpdateContactTeamName(content) => {
+                if let Some(edit) = self.contact_edit.as_mut() {
+                    edit.team_name = content;
+                }
+            }
+            Message::UpdateContactAddress(content) => {
+                if let Some(edit) = self.contact_edit.as_mut() {
+                    edit.address = content;
+                }
+            }
+            Message::UpdateContactLatitude(content) => {
+                if let Some(edit) = self.contact_edit.as_mut() {
+                    edit.latitude = content;
+                }
+            }
+            Message::UpdateContactLongitude(content) => {
+                if let Some(edit) = self.contact_edit.as_mut() {
+                    edit.longitude = content;
+                }
+            }
+            Message::SaveContact => {
+                if let Err(err) = self.save_contact() {
+                    if let Some(edit) = self.contact_edit.as_mut() {
+                        edit.err_message = Some(err);
+                    }
+                }
+            }
+            Message::CancelContactEdit => {
+                self.contact_edit = None;
             }
             _ => {}
         }
@@ -69,26 +156,120 @@ impl LoadScreen {
     pub fn new() -> Self {
         LoadScreen {
             contact_list: None,
+            contact_edit: None,
             err_message: None,
         }
     }
 
+    fn save_contact(&mut self) -> Result<(), String> {
+        let edit = self.contact_edit.as_ref().ok_or("No contact is edited!")?;
+        let contact_list = self.contact_list.get_or_insert_with(Vec::new);
+
+        let team_name = edit.team_name.trim();
+        if team_name.is_empty() {
+            return Err("Please type a team name!".to_string());
+        }
+        if contact_list.iter().enumerate().any(|(index, contact)| {
+            Some(index) != edit.index && contact.team_name.trim() == team_name
+        }) {
+            return Err(format!("Team name \"{}\" is already used!", team_name));
+        }
+        let latitude = parse_coordinate(&edit.latitude)?;
+        let longitude = parse_coordinate(&edit.longitude)?;
+        check_coordinates(latitude, longitude)?;
+
+        let id = match edit.index {
+            Some(index) => contact_list[index].id,
+            None => {
+                let next_id = contact_list
+                    .iter()
+                    .map(|contact| contact.id as usize + 1)
+                    .max()
+                    .unwrap_or(0);
+                u8::try_from(next_id).map_err(|_| "No more teams can be added!".to_string())?
+            }
+        };
+        let contact = Contact::new(id, team_name, edit.address.trim(), latitude, longitude);
+        match edit.index {
+            Some(index) => contact_list[index] = contact,
+            None => contact_list.push(contact),
+        }
+        self.contact_edit = None;
+        Ok(())
+    }
+
     fn get_check_data(&self) -> Element<Message> {
         let headline = Text::new("Cook And Run Teams").size(25).align_x(Left);
+        let issue_list = validate_contact_list(
+            self.contact_list
+                .as_ref()
+                .expect("Expect contacts to be loaded!"),
+        );
+        let has_error = issue_list.iter().any(|issue| issue.is_error);
+
+        let button_add_contact =
+            container(Button::new("Add Team").on_press(Message::AddContact)).align_right(Fill);
         let butto_load_data =
             container(Button::new("Load Data").on_press(Message::LoadData)).align_right(Fill);
-        let button_next =
-            container(button("Next Step").on_press(Message::GoToAddRulesScreen)).align_right(Fill);
-
-        container(column![
-            row![headline, butto_load_data, button_next],
-            self.get_contact_list_element()
-        ])
-        .height(Length::FillPortion(4))
-        .into()
+        // Teams with errors can't be planned
+        let button_next = container(if has_error || self.get_contact_list().is_none() {
+            button("Next Step")
+        } else {
+            button("Next Step").on_press(Message::GoToAddRulesScreen)
+        })
+        .align_right(Fill);
+
+        let mut content = column![row![
+            headline,
+            button_add_contact,
+            butto_load_data,
+            button_next
+        ]];
+        if let Some(contact_edit) = &self.contact_edit {
+            content = content.push(Self::get_contact_edit_element(contact_edit));
+        }
+        if has_error {
+            content = content.push(
+                text("Please fix the marked teams before the next step")
+                    .color(Color::from_rgb(0.8, 0.2, 0.2)),
+            );
+        }
+
+        container(content.push(self.get_contact_list_element(&issue_list)))
+            .height(Length::FillPortion(4))
+            .into()
     }
 
-    fn get_contact_list_element(&self) -> Element<Message> {
+    fn get_contact_edit_element(contact_edit: &ContactEdit) -> Element<'_, Message> {
+        let headline = text(if contact_edit.index.is_some() {
+            "Edit Team"
+        } else {
+            "New Team"
+        })
+        .size(20);
+        let input_row = row![
+            text_input("Team name", &contact_edit.team_name)
+                .on_input(Message::UpdateContactTeamName),
+            text_input("Address", &contact_edit.address).on_input(Message::UpdateContactAddress),
+            text_input("Latitude", &contact_edit.latitude)
+                .on_input(Message::UpdateContactLatitude)
+                .width(120),
+            text_input("Longitude", &contact_edit.longitude)
+                .on_input(Message::UpdateContactLongitude)
+                .width(120),
+            button("Save").on_press(Message::SaveContact),
+            button("Cancel").on_press(Message::CancelContactEdit),
+        ]
+        .spacing(5);
+
+        let mut content = column![headline, input_row].spacing(5);
+        if let Some(err_message) = &contact_edit.err_message {
+            content = content.push(text(err_message).color(Color::from_rgb(0.8, 0.2, 0.2)));
+        }
+        container(content).padding(10).into()
+    }
+
+    fn get_contact_list_element(&self, issue_list: &[ContactIssue]) -> Element<Message> {
         let mut contact_data_row = Row::new().spacing(10).align_y(Vertical::Top);
         let mut contact_dara_column = Column::new()
             .spacing(5)
@@ -113,6 +294,11 @@ impl LoadScreen {
                 &contact.address,
                 (contact.latitude, contact.longitude),
                 index,
+                issue_list
+                    .iter()
+                    .filter(|issue| issue.index == index)
+                    .cloned()
+                    .collect(),
             );
             contact_data_row = contact_data_row.push(contact_box);
             index += 1;
@@ -146,6 +332,7 @@ impl LoadScreen {
         street: &'a str,
         coords: (i32, i32),
         index: usize,
+        issue_list: Vec<ContactIssue>,
     ) -> Element<'a, Message> {
         let icons = column![
             IMAGE_COLLECTION.team.get(20),
@@ -154,21 +341,37 @@ impl LoadScreen {
         ]
         .spacing(8);
 
-        let text = column![
+        let mut description = column![
             text(format!("Team {}", (index + 1))).size(20),
             text(name).size(20).color(Color::from_rgb(0.0, 0.0, 0.5)),
             text(street).size(18),
-            text(format!("({:?} | {:?})", coords.0, coords.1)).size(16)
+            text(format!("({:?} | {:?})", coords.0, coords.1)).size(16),
+            row![
+                button("Edit").on_press(Message::EditContact(index)),
+                button("Delete").on_press(Message::DeleteContact(index))
+            ]
+            .spacing(5)
         ];
+        let has_error = issue_list.iter().any(|issue| issue.is_error);
+        for issue in issue_list {
+            description = description.push(text(issue.message).size(14).color(if issue.is_error {
+                Color::from_rgb(0.8, 0.2, 0.2)
+            } else {
+                Color::from_rgb(0.8, 0.5, 0.0)
+            }));
+        }
 
-        let content = row![icons, text].padding(10);
+        let content = row![icons, description].padding(10);
 
         container(content)
             .width(250)
-            .height(120)
             .style(move |_| container::Style {
                 border: Border {
-                    color: Color::from_rgb(0.9, 0.9, 0.9),
+                    color: if has_error {
+                        Color::from_rgb(0.8, 0.2, 0.2)
+                    } else {
+                        Color::from_rgb(0.9, 0.9, 0.9)
+                    },
                     radius: Radius {
                         top_left: 12.0,
                         top_right: 12.0,
@@ -183,7 +386,9 @@ impl LoadScreen {
     }
 
     pub fn get_contact_list(&self) -> Option<Vec<Contact>> {
-        self.contact_list.clone()
+        self.contact_list
+            .clone()
+            .filter(|contact_list| !contact_list.is_empty())
     }
 
     fn get_choose_file() -> Element<'static, Message> {
@@ -196,7 +401,14 @@ impl LoadScreen {
             container(Text::new("Please load the contact data from a CSV-File.").size(15))
                 .width(Fill)
                 .center_x(Fill);
-        let button = container(Button::new("Load Data").on_press(Message::LoadData)).center_x(Fill);
+        let button = container(
+            row![
+                Button::new("Load Data").on_press(Message::LoadData),
+                Button::new("Enter Teams").on_press(Message::AddContact)
+            ]
+            .spacing(10),
+        )
+        .center_x(Fill);
         let upload_area = container(column![sub_headline, short_description, button])
             .style(move |_| container::Style {
                 border: Border {
@@ -223,3 +435,11 @@ impl LoadScreen {
             .into()
     }
 }
+
+fn parse_coordinate(content: &str) -> Result<f64, String> {
+    content
+        .trim()
+        .replace(',', ".")
+        .parse::<f64>()
+        .map_err(|_| format!("\"{}\" is not a coordinate!", content))
+}
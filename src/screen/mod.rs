@@ -8,7 +8,10 @@ use iced::Element;
 use result::ResultScreen;
 use rule::RuleScreen;
 
-use crate::{calculator::CalculatorConfig, LoadScreen, Message};
+use crate::{
+    calculator::{CalculatorConfig, Selection},
+    LoadScreen, Message,
+};
 
 trait Screen {
     fn get(&self) -> Element<Message>;
@@ -92,16 +95,51 @@ impl AvailableScreens {
         let course_name_list = self.rule_screen.get_course_name_list();
         if course_name_list.is_none() {
             log::error!("Course name list is None");
+            self.rule_screen
+                .set_err_message(Some("Please type at least one course name!".to_string()));
             return ScreenName::AddRules;
         }
 
-        let calculator_config = CalculatorConfig::new_with_start_and_goal(
+        let mut calculator_config = CalculatorConfig::new_with_start_and_goal(
             self.rule_screen.get_start_point(),
             self.rule_screen.get_goal_point(),
-            Some("Haupt".to_string()),
+            None,
             course_name_list.expect("Expect course name list"),
             contact_list.expect("Expect contact list"),
         );
+        calculator_config
+            .set_course_with_more_hosts_list(self.rule_screen.get_course_with_more_hosts_list());
+        calculator_config.set_guests_per_hosting(self.rule_screen.get_guests_per_hosting());
+        calculator_config
+            .set_stop_after_no_improvement(self.rule_screen.get_stop_after_no_improvement());
+        if let Some(number_of_threads) = self.rule_screen.get_number_of_threads() {
+            calculator_config.set_number_of_threads(number_of_threads);
+        }
+        if let Some(population_size) = self.rule_screen.get_population_size() {
+            calculator_config.set_population_size(population_size);
+        }
+        if let Some(crossover_rate) = self.rule_screen.get_crossover_rate() {
+            calculator_config.set_crossover_rate(crossover_rate);
+        }
+        if let Some(mutation_rate) = self.rule_screen.get_mutation_rate() {
+            calculator_config.set_mutation_rate(mutation_rate);
+        }
+        if let Some(elite_count) = self.rule_screen.get_elite_count() {
+            calculator_config.set_elite_count(elite_count);
+        }
+        if let Some(tournament_size) = self.rule_screen.get_tournament_size() {
+            calculator_config.set_selection(Selection::Tournament(tournament_size));
+        }
+        calculator_config.set_exact_solver(self.rule_screen.get_exact_solver());
+        calculator_config.set_time_budget(self.rule_screen.get_time_budget());
+        calculator_config.set_iteration_budget(self.rule_screen.get_iteration_budget());
+
+        if let Err(err) = calculator_config.check() {
+            log::error!("Rules are not valid: {}", err);
+            self.rule_screen.set_err_message(Some(err));
+            return ScreenName::AddRules;
+        }
+        self.rule_screen.set_err_message(None);
 
         self.calculate_screen.start_calculation(calculator_config);
 
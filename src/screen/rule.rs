@@ -1,3 +1,5 @@
+use std::time::Duration;
+
 use iced::{
     alignment::{
         Horizontal::{Left, Right},
@@ -9,7 +11,7 @@ use iced::{
     Length::Fill,
 };
 
-use crate::Message;
+use crate::{calculator::MAX_EXACT_TEAMS, Message};
 use iced::widget::{button, column, row, text_input, Column};
 
 use super::Screen;
@@ -39,12 +41,73 @@ impl Position {
     }
 }
 
+// Text of an optional number field, kept as typed so errors can be shown
+#[derive(Debug, Clone, Default)]
+struct NumberInput {
+    value: Option<String>,
+    err: bool,
+}
+
+impl NumberInput {
+    fn update<T: std::str::FromStr>(&mut self, content: String) {
+        if content.is_empty() {
+            self.value = None;
+            self.err = false;
+        } else {
+            let content = normalize_number(content.as_str());
+            // "0." is no number yet, but a valid start of one
+            let is_prefix = format!("{}0", content).parse::<T>().is_ok();
+            self.err = content.parse::<T>().is_err() && !is_prefix;
+            self.value = Some(content);
+        }
+    }
+
+    fn get<T: std::str::FromStr>(&self) -> Option<T> {
+        if self.err {
+            return None;
+        }
+        self.value.as_ref()?.parse::<T>().ok()
+    }
+
+    fn view<'a>(
+        &'a self,
+        label: &'a str,
+        placeholder: &'a str,
+        on_input: fn(String) -> Message,
+    ) -> Element<'a, Message> {
+        let mut content = column![
+            text(label),
+            text_input(placeholder, self.value.as_deref().unwrap_or("")).on_input(on_input)
+        ];
+        if self.err {
+            content =
+                content.push(text("Please type a number").color(Color::from_rgb(0.8, 0.2, 0.2)));
+        }
+        content.into()
+    }
+}
+
 pub(crate) struct RuleScreen {
     course_name_list: Vec<String>,
+    more_hosts_state_list: Vec<bool>,
+    guests_per_hosting: NumberInput,
+    stop_after_no_improvement: NumberInput,
+    advanced_settings_checkbox_state: bool,
+    number_of_threads: NumberInput,
+    population_size: NumberInput,
+    crossover_rate: NumberInput,
+    mutation_rate: NumberInput,
+    elite_count: NumberInput,
+    tournament_size: NumberInput,
+    time_budget: NumberInput,
+    iteration_budget: NumberInput,
+    exact_solver_checkbox_state: bool,
     start_point: Option<Position>,
     goal_point: Option<Position>,
     start_point_checkbox_state: bool,
     goal_point_checkbox_state: bool,
+    // Why the rules were rejected when starting the calculation
+    err_message: Option<String>,
 }
 
 impl Screen for RuleScreen {
@@ -55,11 +118,19 @@ impl Screen for RuleScreen {
 
         let button_next =
             container(button("Next Step").on_press(Message::GoToCalculateScreen)).align_right(Fill);
+        let err_message = text(self.err_message.clone().unwrap_or_default())
+            .color(Color::from_rgb(0.8, 0.2, 0.2));
 
         column![
             row![headline, button_next],
+            err_message,
             row![
-                self.get_course_name(),
+                column![
+                    self.get_course_name(),
+                    self.get_guests_per_hosting_element(),
+                    self.get_stop_after_no_improvement_element(),
+                    self.get_advanced_settings_element()
+                ],
                 container(
                     container(
                         column![
@@ -119,13 +190,55 @@ impl Screen for RuleScreen {
                 let len = self.course_name_list.len();
                 if len > 1 && self.course_name_list[len - 1].is_empty() {
                     self.course_name_list.remove(len - 1);
+                    self.more_hosts_state_list.remove(len - 1);
                 }
             }
             Message::DeleteCourseName(index) => {
                 self.course_name_list.remove(index);
+                self.more_hosts_state_list.remove(index);
             }
             Message::AddCourseName(value) => {
                 self.course_name_list.push(value);
+                self.more_hosts_state_list.push(false);
+            }
+            Message::ToggleCourseWithMoreHosts(index, state) => {
+                self.more_hosts_state_list[index] = state;
+            }
+            Message::UpdateGuestsPerHosting(content) => {
+                self.guests_per_hosting.update::<usize>(content);
+            }
+            Message::UpdateStopAfterNoImprovement(content) => {
+                self.stop_after_no_improvement.update::<u64>(content);
+            }
+            Message::ShowAdvancedSettings(state) => {
+                self.advanced_settings_checkbox_state = state;
+            }
+            Message::UpdateNumberOfThreads(content) => {
+                self.number_of_threads.update::<usize>(content);
+            }
+            Message::UpdatePopulationSize(content) => {
+                self.population_size.update::<usize>(content);
+            }
+            Message::UpdateCrossoverRate(content) => {
+                self.crossover_rate.update::<f64>(content);
+            }
+            Message::UpdateMutationRate(content) => {
+                self.mutation_rate.update::<f64>(content);
+            }
+            Message::UpdateEliteCount(content) => {
+                self.elite_count.update::<usize>(content);
+            }
+            Message::UpdateTournamentSize(content) => {
+                self.tournament_size.update::<usize>(content);
+            }
+            Message::UpdateTimeBudget(content) => {
+                self.time_budget.update::<u64>(content);
+            }
+            Message::UpdateIterationBudget(content) => {
+                self.iteration_budget.update::<usize>(content);
+            }
+            Message::ToggleExactSolver(state) => {
+                self.exact_solver_checkbox_state = state;
             }
             _ => {}
         }
@@ -136,27 +249,50 @@ impl RuleScreen {
     pub fn new() -> Self {
         RuleScreen {
             course_name_list: vec![],
+            more_hosts_state_list: vec![],
+            guests_per_hosting: NumberInput::default(),
+            stop_after_no_improvement: NumberInput::default(),
+            advanced_settings_checkbox_state: false,
+            number_of_threads: NumberInput::default(),
+            population_size: NumberInput::default(),
+            crossover_rate: NumberInput::default(),
+            mutation_rate: NumberInput::default(),
+            elite_count: NumberInput::default(),
+            tournament_size: NumberInput::default(),
+            time_budget: NumberInput::default(),
+            iteration_budget: NumberInput::default(),
+            exact_solver_checkbox_state: false,
             start_point: None,
             goal_point: None,
             start_point_checkbox_state: false,
             goal_point_checkbox_state: false,
+            err_message: None,
         }
     }
 
+    pub fn set_err_message(&mut self, err_message: Option<String>) {
+        self.err_message = err_message;
+    }
+
     fn get_course_name(&self) -> Element<Message> {
         let course_name_headline = text("Course names:").size(20);
 
         let mut course_name_rows: Column<Message> = column![];
         for (i, el) in self.course_name_list.iter().enumerate() {
-            course_name_rows = course_name_rows.push(row![
-                text(format!("{}: ", i + 1))
-                    .align_y(Vertical::Center)
-                    .width(15),
-                //.height(Fill),
-                text_input("Type course name here...", el)
-                    .on_input(move |content| Message::UpdateCourseNameList(i, content)),
-                button("-").on_press(Message::DeleteCourseName(i))
-            ]);
+            course_name_rows = course_name_rows.push(
+                row![
+                    text(format!("{}: ", i + 1))
+                        .align_y(Vertical::Center)
+                        .width(15),
+                    //.height(Fill),
+                    text_input("Type course name here...", el)
+                        .on_input(move |content| Message::UpdateCourseNameList(i, content)),
+                    checkbox("More hosts", self.more_hosts_state_list[i])
+                        .on_toggle(move |state| Message::ToggleCourseWithMoreHosts(i, state)),
+                    button("-").on_press(Message::DeleteCourseName(i))
+                ]
+                .spacing(5),
+            );
         }
         let len = self.course_name_list.len();
 
@@ -175,6 +311,80 @@ impl RuleScreen {
             .into()
     }
 
+    fn get_guests_per_hosting_element(&self) -> Element<'_, Message> {
+        let content = self.guests_per_hosting.view(
+            "Guests per hosting:",
+            "Number of courses - 1",
+            Message::UpdateGuestsPerHosting,
+        );
+        container(content).padding(20).into()
+    }
+
+    fn get_stop_after_no_improvement_element(&self) -> Element<'_, Message> {
+        let content = self.stop_after_no_improvement.view(
+            "Stop after seconds without a better plan:",
+            "Calculate until stopped",
+            Message::UpdateStopAfterNoImprovement,
+        );
+        container(content).padding(20).into()
+    }
+
+    fn get_advanced_settings_element(&self) -> Element<'_, Message> {
+        let headline = checkbox("Advanced settings", self.advanced_settings_checkbox_state)
+            .on_toggle(Message::ShowAdvancedSettings);
+        if !self.advanced_settings_checkbox_state {
+            return container(headline).padding(20).into();
+        }
+
+        let content = column![
+            headline,
+            checkbox(
+                format!("Exact solver (up to {} teams)", MAX_EXACT_TEAMS),
+                self.exact_solver_checkbox_state
+            )
+            .on_toggle(Message::ToggleExactSolver),
+            self.number_of_threads.view(
+                "Threads:",
+                "Number of cores",
+                Message::UpdateNumberOfThreads
+            ),
+            self.population_size.view(
+                "Plans per generation:",
+                "1000",
+                Message::UpdatePopulationSize
+            ),
+            self.crossover_rate.view(
+                "Share of plans bred from the best (0 - 1):",
+                "0.8",
+                Message::UpdateCrossoverRate
+            ),
+            self.mutation_rate.view(
+                "Mutation rate per gene (0 - 1):",
+                "0.02",
+                Message::UpdateMutationRate
+            ),
+            self.elite_count
+                .view("Best plans kept unchanged:", "2", Message::UpdateEliteCount),
+            self.tournament_size.view(
+                "Plans per selection tournament:",
+                "3",
+                Message::UpdateTournamentSize
+            ),
+            self.time_budget.view(
+                "Time budget in seconds:",
+                "Calculate until stopped",
+                Message::UpdateTimeBudget
+            ),
+            self.iteration_budget.view(
+                "Iteration budget:",
+                "Calculate until stopped",
+                Message::UpdateIterationBudget
+            ),
+        ]
+        .spacing(5);
+        container(content).padding(20).into()
+    }
+
     fn get_start_point_element(&self) -> Element<Message> {
         let headline = checkbox("Start point:", self.start_point_checkbox_state)
             .on_toggle(Message::ShowStartPositionInputField);
@@ -222,6 +432,61 @@ impl RuleScreen {
         }
     }
 
+    pub fn get_course_with_more_hosts_list(&self) -> Vec<String> {
+        self.course_name_list
+            .iter()
+            .zip(self.more_hosts_state_list.iter())
+            .filter(|(_, &state)| state)
+            .map(|(course_name, _)| course_name.clone())
+            .collect()
+    }
+
+    pub fn get_guests_per_hosting(&self) -> Option<usize> {
+        self.guests_per_hosting.get()
+    }
+
+    pub fn get_stop_after_no_improvement(&self) -> Option<Duration> {
+        self.stop_after_no_improvement
+            .get::<u64>()
+            .map(Duration::from_secs)
+    }
+
+    pub fn get_number_of_threads(&self) -> Option<usize> {
+        self.number_of_threads.get()
+    }
+
+    pub fn get_population_size(&self) -> Option<usize> {
+        self.population_size.get()
+    }
+
+    pub fn get_crossover_rate(&self) -> Option<f64> {
+        self.crossover_rate.get()
+    }
+
+    pub fn get_mutation_rate(&self) -> Option<f64> {
+        self.mutation_rate.get()
+    }
+
+    pub fn get_elite_count(&self) -> Option<usize> {
+        self.elite_count.get()
+    }
+
+    pub fn get_tournament_size(&self) -> Option<usize> {
+        self.tournament_size.get()
+    }
+
+    pub fn get_exact_solver(&self) -> bool {
+        self.exact_solver_checkbox_state
+    }
+
+    pub fn get_time_budget(&self) -> Option<Duration> {
+        self.time_budget.get::<u64>().map(Duration::from_secs)
+    }
+
+    pub fn get_iteration_budget(&self) -> Option<usize> {
+        self.iteration_budget.get()
+    }
+
     pub fn get_start_point(&self) -> Option<(i32, i32)> {
         self.start_point.as_ref().and_then(|start_point| {
             if self.start_point_checkbox_state
@@ -288,7 +553,8 @@ fn set_position_data(
         });
 }
 
-fn string_to_number<T: std::str::FromStr>(content: &str) -> Result<T, String> {
+// Keeps digits and the first decimal separator, a comma counts as separator
+fn normalize_number(content: &str) -> String {
     let normalized_content = content.replace(',', ".");
     let mut new_content = String::new();
     let mut has_decimal = false;
@@ -301,7 +567,11 @@ fn string_to_number<T: std::str::FromStr>(content: &str) -> Result<T, String> {
             new_content.push(c);
         }
     }
+    new_content
+}
 
+fn string_to_number<T: std::str::FromStr>(content: &str) -> Result<T, String> {
+    let new_content = normalize_number(content);
     match new_content.parse::<T>() {
         Ok(number) => Ok(number),
         Err(_) => Err(new_content),
@@ -0,0 +1,135 @@
+use std::time::Duration;
+
+use tcc::{
+    benchmark::{format_result_list, get_violation_list, run_benchmark, SolverKind},
+    calculator::solve_exact,
+    scenario::{Distribution, ScenarioConfig},
+};
+
+#[test]
+fn test_generate_scenario() {
+    let mut config = ScenarioConfig::new("Uniform", 12, 3);
+    config.set_seed(7);
+    config.set_start_and_goal_point(true, true);
+    let scenario = config.generate().expect("Expected scenario");
+    assert_eq!(scenario.contact_list.len(), 12);
+    assert_eq!(scenario.course_name_list.len(), 3);
+    assert!(scenario.start_point.is_some());
+    assert!(scenario.goal_point.is_some());
+    assert!(scenario.to_calculator_config().check().is_ok());
+
+    // Same seed, same teams
+    let again = config.generate().expect("Expected scenario");
+    assert_eq!(scenario.contact_list, again.contact_list);
+
+    // 5 km around Frankfurt stays within about 0.05 degrees latitude
+    for contact in scenario.contact_list.iter() {
+        assert!((contact.latitude - 5_011_000).abs() <= 4_500);
+    }
+}
+
+#[test]
+fn test_generate_clustered_scenario() {
+    let mut config = ScenarioConfig::new("Clustered", 10, 3);
+    config.set_distribution(Distribution::Clustered {
+        number_of_clusters: 2,
+        spread_km: 0.5,
+    });
+    let scenario = config.generate().expect("Expected scenario");
+    assert_eq!(scenario.contact_list.len(), 10);
+    assert!(
+        scenario.to_calculator_config().check().is_ok(),
+        "Overhang should go to a course with more hosts"
+    );
+
+    config.set_distribution(Distribution::Clustered {
+        number_of_clusters: 0,
+        spread_km: 0.5,
+    });
+    assert!(config.generate().is_err());
+}
+
+#[test]
+fn test_run_benchmark() {
+    let scenario = ScenarioConfig::new("Nine teams", 9, 3)
+        .generate()
+        .expect("Expected scenario");
+    let result_list = run_benchmark(
+        std::slice::from_ref(&scenario),
+        &[SolverKind::Genetic, SolverKind::Exact],
+        Duration::from_secs(1),
+    );
+    assert_eq!(result_list.len(), 2);
+    // Header and one line per result
+    assert_eq!(format_result_list(&result_list).lines().count(), 3);
+    let exact = &result_list[1];
+    assert_eq!(exact.solver, SolverKind::Exact);
+    assert!(exact.error.is_none());
+    assert!(exact.violation_list.is_empty());
+    // The exact solver proves nine teams optimal, no feasible plan beats it
+    let genetic = &result_list[0];
+    if genetic.violation_list.is_empty() {
+        assert!(
+            genetic.score.expect("Expected score") >= exact.score.expect("Expected score") - 1e-9
+        );
+    }
+}
+
+#[test]
+fn test_violation_list() {
+    let scenario = ScenarioConfig::new("Nine teams", 9, 3)
+        .generate()
+        .expect("Expected scenario");
+    let mut plan = solve_exact(
+        &scenario.to_calculator_config(),
+        Duration::from_secs(10),
+        None,
+    )
+    .expect("Expected plan")
+    .plan;
+    assert!(get_violation_list(&scenario, &plan).is_empty());
+
+    // Two tables of the same course are merged
+    let course_list = plan
+        .course_map
+        .get_mut(&scenario.course_name_list[0])
+        .expect("Expected course");
+    let moved = course_list.remove(1);
+    course_list[0].guest_list.push(moved.host);
+    assert!(!get_violation_list(&scenario, &plan).is_empty());
+}
+
+// Compares the solvers on generated scenarios: cargo test --release -- --ignored
+#[test]
+#[ignore]
+fn test_benchmark_suite() {
+    let mut scenario_list = Vec::new();
+    for (number_of_teams, number_of_courses) in [(9, 3), (12, 3), (16, 4), (24, 3), (40, 4)] {
+        for distribution in [
+            Distribution::Uniform,
+            Distribution::Clustered {
+                number_of_clusters: 3,
+                spread_km: 0.8,
+            },
+        ] {
+            let name = match distribution {
+                Distribution::Uniform => "uniform",
+                Distribution::Clustered { .. } => "clustered",
+            };
+            let mut config = ScenarioConfig::new(
+                &format!("{} teams {}", number_of_teams, name),
+                number_of_teams,
+                number_of_courses,
+            );
+            config.set_distribution(distribution);
+            config.set_start_and_goal_point(true, true);
+            scenario_list.push(config.generate().expect("Expected scenario"));
+        }
+    }
+    let result_list = run_benchmark(
+        &scenario_list,
+        &[SolverKind::Genetic, SolverKind::Exact],
+        Duration::from_secs(10),
+    );
+    println!("{}", format_result_list(&result_list));
+}
@@ -0,0 +1,72 @@
+use std::{thread, time::Duration};
+
+use tcc::calculation_job::{CalculationJob, JobState};
+
+#[test]
+fn test_cancel_and_join() {
+    let mut job = CalculationJob::spawn(3, |_, token| {
+        while !token.is_cancelled() {
+            thread::sleep(Duration::from_millis(10));
+        }
+        // Workers may take a while to finish their iteration
+        thread::sleep(Duration::from_millis(100));
+    });
+    assert_eq!(job.poll(), JobState::Running);
+
+    job.cancel();
+    assert_eq!(job.get_state(), JobState::Stopping);
+    assert_eq!(job.join(), JobState::Finished);
+    assert!(!job.is_running());
+    assert!(job.get_stop_time().is_some());
+}
+
+#[test]
+fn test_worker_panic() {
+    let mut job = CalculationJob::spawn(2, |index, _| {
+        if index == 1 {
+            panic!("Worker {} failed", index);
+        }
+    });
+    assert_eq!(job.join(), JobState::Failed("Worker 1 failed".to_string()));
+}
+
+#[test]
+fn test_worker_panic_cancels_job() {
+    // The healthy workers only stop once the job is cancelled
+    let mut job = CalculationJob::spawn(3, |index, token| {
+        if index == 0 {
+            panic!("Worker {} failed", index);
+        }
+        while !token.is_cancelled() {
+            thread::sleep(Duration::from_millis(10));
+        }
+    });
+    assert_eq!(job.join(), JobState::Failed("Worker 0 failed".to_string()));
+}
+
+#[test]
+fn test_drop_does_not_block() {
+    let job = CalculationJob::spawn(2, |_, token| {
+        while !token.is_cancelled() {
+            thread::sleep(Duration::from_millis(10));
+        }
+        thread::sleep(Duration::from_secs(2));
+    });
+    let start = std::time::Instant::now();
+    drop(job);
+    assert!(start.elapsed() < Duration::from_secs(1));
+}
+
+#[test]
+fn test_worker_cancels_job() {
+    // A worker that is done, e.g. after its budget, stops all others
+    let mut job = CalculationJob::spawn(4, |index, token| {
+        if index == 0 {
+            token.cancel();
+        }
+        while !token.is_cancelled() {
+            thread::sleep(Duration::from_millis(10));
+        }
+    });
+    assert_eq!(job.join(), JobState::Finished);
+}
@@ -1,7 +1,8 @@
 use data::{get_contact_list, get_course_name_list};
 use env_logger::Env;
 use tcc::{
-    calculator::{Calculator, CalculatorConfig, Course, Plan},
+    calculation_job::JobState,
+    calculator::{Calculator, CalculatorConfig, Course, History, IterationStat, Plan, Selection},
     contact::Contact,
 };
 
@@ -212,7 +213,9 @@ fn check_course(
 // START TEST AREA
 
 fn run_calculation(calculator: &mut Calculator) {
-    calculator.calculate();
+    calculator
+        .calculate()
+        .expect("Expected calculation to start");
 
     let start_time = time::Instant::now();
     while calculator
@@ -601,4 +604,410 @@ fn test_team_of_seventeen() {
     assert_number_of_guests_in_course(&plan.course_map, number_of_guests);
     check_course(&plan.course_map, course_with_more_hosts);
 }
+
+#[test]
+fn test_team_of_twelve_with_three_guests() {
+    INIT.call_once(|| {
+        env_logger::Builder::from_env(Env::default().default_filter_or("warn")).init();
+    });
+    let number_of_guests = 12;
+    let number_course = 3;
+    let contact_list = get_contact_list(number_of_guests);
+    let course_name_list = get_course_name_list(number_course);
+
+    print_test_params(&contact_list, &course_name_list);
+
+    let mut config = CalculatorConfig::new(course_name_list, contact_list, None);
+    config.set_guests_per_hosting(Some(3));
+    assert!(config.check().is_ok());
+    let mut calculator = Calculator::new(config);
+    run_calculation(&mut calculator);
+    let plan = calculator
+        .top_plan
+        .lock()
+        .expect("Failed to lock top_plan")
+        .as_ref()
+        .expect("Expected plan")
+        .clone();
+    print_plan(&plan);
+    for (course_name, course_list) in plan.course_map.iter() {
+        assert_eq!(
+            course_list.len(),
+            3,
+            "Course \"{}\" should have 3 hosts",
+            course_name
+        );
+        for course in course_list {
+            assert_eq!(
+                course.guest_list.len(),
+                3,
+                "Host \"{}\" should have 3 guests",
+                course.host.team_name
+            );
+        }
+    }
+    assert_number_of_guests_in_course(&plan.course_map, number_of_guests);
+    check_course(&plan.course_map, None);
+}
+
+#[test]
+fn test_check_guests_per_hosting() {
+    let course_name_list = get_course_name_list(3);
+
+    let mut config = CalculatorConfig::new(course_name_list.clone(), get_contact_list(10), None);
+    config.set_guests_per_hosting(Some(2));
+    assert!(
+        config.check().is_err(),
+        "Overhang without course with more hosts should fail"
+    );
+
+    config.set_course_with_more_hosts_list(course_name_list.clone());
+    assert!(
+        config.check().is_err(),
+        "More hostings than contacts should fail"
+    );
+
+    config.set_course_with_more_hosts_list(vec![course_name_list[1].clone()]);
+    assert!(config.check().is_ok());
+
+    config.set_guests_per_hosting(Some(0));
+    assert!(
+        config.check().is_err(),
+        "Hosting without guests should fail"
+    );
+}
+
+#[test]
+fn test_two_courses() {
+    INIT.call_once(|| {
+        env_logger::Builder::from_env(Env::default().default_filter_or("warn")).init();
+    });
+    let number_of_guests = 10;
+    let number_course = 2;
+    let contact_list = get_contact_list(number_of_guests);
+    let course_name_list = get_course_name_list(number_course);
+
+    print_test_params(&contact_list, &course_name_list);
+
+    let config = CalculatorConfig::new(course_name_list, contact_list, None);
+    assert!(config.check().is_ok());
+    let mut calculator = Calculator::new(config);
+    run_calculation(&mut calculator);
+    let plan = calculator
+        .top_plan
+        .lock()
+        .expect("Failed to lock top_plan")
+        .as_ref()
+        .expect("Expected plan")
+        .clone();
+    print_plan(&plan);
+    assert_eq!(
+        plan.course_map.len(),
+        number_course,
+        "Number of courses should be {}",
+        number_course,
+    );
+    assert_number_of_guests_in_course(&plan.course_map, number_of_guests);
+    check_course(&plan.course_map, None);
+}
+
+#[test]
+fn test_four_courses() {
+    INIT.call_once(|| {
+        env_logger::Builder::from_env(Env::default().default_filter_or("warn")).init();
+    });
+    let number_of_guests = 16;
+    let number_course = 4;
+    let contact_list = get_contact_list(number_of_guests);
+    let course_name_list = get_course_name_list(number_course);
+
+    print_test_params(&contact_list, &course_name_list);
+
+    let config = CalculatorConfig::new(course_name_list, contact_list, None);
+    assert!(config.check().is_ok());
+    let mut calculator = Calculator::new(config);
+    run_calculation(&mut calculator);
+    let plan = calculator
+        .top_plan
+        .lock()
+        .expect("Failed to lock top_plan")
+        .as_ref()
+        .expect("Expected plan")
+        .clone();
+    print_plan(&plan);
+    assert_eq!(
+        plan.course_map.len(),
+        number_course,
+        "Number of courses should be {}",
+        number_course,
+    );
+    assert_number_of_guests_in_course(&plan.course_map, number_of_guests);
+    check_course(&plan.course_map, None);
+}
+
+#[test]
+fn test_check_number_of_courses() {
+    let config = CalculatorConfig::new(get_course_name_list(1), get_contact_list(9), None);
+    assert!(config.check().is_err(), "One course should fail");
+
+    let config = CalculatorConfig::new(get_course_name_list(4), get_contact_list(3), None);
+    assert!(
+        config.check().is_err(),
+        "Fewer contacts than seats should fail"
+    );
+
+    let config = CalculatorConfig::new(get_course_name_list(4), get_contact_list(10), None);
+    assert!(
+        config.check().is_err(),
+        "Overhang without course with more hosts should fail"
+    );
+}
+
+#[test]
+fn test_check_genetic_settings() {
+    let mut config = CalculatorConfig::new(get_course_name_list(3), get_contact_list(9), None);
+    assert!(config.check().is_ok());
+
+    config.set_number_of_threads(0);
+    assert!(config.check().is_err(), "No thread should fail");
+    config.set_number_of_threads(2);
+
+    config.set_population_size(1);
+    assert!(config.check().is_err(), "Population of one should fail");
+    config.set_population_size(100);
+
+    config.set_crossover_rate(1.5);
+    assert!(
+        config.check().is_err(),
+        "Crossover rate above one should fail"
+    );
+    config.set_crossover_rate(0.5);
+
+    config.set_mutation_rate(-0.1);
+    assert!(
+        config.check().is_err(),
+        "Negative mutation rate should fail"
+    );
+    config.set_mutation_rate(0.05);
+    assert!(config.check().is_ok());
+
+    config.set_elite_count(100);
+    assert!(
+        config.check().is_err(),
+        "Elite as big as the population should fail"
+    );
+    config.set_elite_count(5);
+
+    config.set_selection(Selection::Tournament(0));
+    assert!(config.check().is_err(), "Empty tournament should fail");
+    config.set_selection(Selection::Truncation);
+    assert!(config.check().is_ok());
+}
+
+#[test]
+fn test_calculate_checks_config() {
+    let mut config = CalculatorConfig::new(get_course_name_list(3), get_contact_list(9), None);
+    config.set_number_of_threads(0);
+    let mut calculator = Calculator::new(config);
+    assert!(
+        calculator.calculate().is_err(),
+        "Invalid config should not be calculated"
+    );
+    assert!(!calculator.is_running());
+}
+
+#[test]
+fn test_iteration_budget() {
+    let mut config = CalculatorConfig::new(get_course_name_list(3), get_contact_list(9), None);
+    config.set_number_of_threads(2);
+    config.set_population_size(50);
+    config.set_mutation_rate(0.05);
+    config.set_iteration_budget(Some(20));
+    config.set_time_budget(Some(Duration::from_secs(60)));
+    let mut calculator = Calculator::new(config);
+    calculator
+        .calculate()
+        .expect("Expected calculation to start");
+
+    let start_time = time::Instant::now();
+    assert_eq!(calculator.wait(), Some(JobState::Finished));
+    assert!(
+        start_time.elapsed() < Duration::from_secs(60),
+        "Calculation should stop after the iteration budget"
+    );
+    // Every thread finishes its current generation
+    let iterations = calculator
+        .iterations
+        .load(std::sync::atomic::Ordering::SeqCst);
+    assert!((20..=21).contains(&iterations));
+    assert!(calculator
+        .top_plan
+        .lock()
+        .expect("Failed to lock top_plan")
+        .is_some());
+}
+
+// Runs the calculation until the iteration budget is used up and returns the best score
+#[test]
+fn test_elite_keeps_best_plan() {
+    for (number_of_guests, number_course) in [(9, 3), (16, 4)] {
+        let mut config = CalculatorConfig::new(
+            get_course_name_list(number_course),
+            get_contact_list(number_of_guests),
+            None,
+        );
+        config.set_number_of_threads(1);
+        config.set_population_size(50);
+        config.set_iteration_budget(Some(30));
+        let mut calculator = Calculator::new(config);
+        calculator
+            .calculate()
+            .expect("Expected calculation to start");
+        calculator.wait();
+
+        let history = calculator.history.lock().expect("Failed to lock history");
+        let stat_list = history.get_stat_list();
+        assert_eq!(stat_list.len(), 30);
+        for window in stat_list.windows(2) {
+            assert!(
+                window[1].population_best <= window[0].population_best,
+                "Elite should keep the best plan of generation {}",
+                window[0].iteration
+            );
+        }
+        let top_plan = calculator.top_plan.lock().expect("Failed to lock top_plan");
+        assert_eq!(
+            top_plan.as_ref().expect("Expected plan").score,
+            stat_list.last().expect("Expected history").population_best
+        );
+    }
+}
+
+#[test]
+fn test_single_calculation() {
+    let mut config = CalculatorConfig::new(get_course_name_list(3), get_contact_list(9), None);
+    config.set_number_of_threads(2);
+    let mut calculator = Calculator::new(config);
+    assert_eq!(calculator.get_state(), None);
+
+    calculator
+        .calculate()
+        .expect("Expected calculation to start");
+    assert!(
+        calculator.calculate().is_err(),
+        "Second calculation should not start while the first is running"
+    );
+    assert_eq!(calculator.get_state(), Some(JobState::Running));
+
+    // Stop returns once every thread is finished
+    assert_eq!(calculator.stop(), Some(JobState::Finished));
+    assert!(!calculator.is_running());
+    assert!(calculator.get_stop_time().is_some());
+    let iterations = calculator
+        .iterations
+        .load(std::sync::atomic::Ordering::SeqCst);
+    thread::sleep(Duration::from_millis(200));
+    assert_eq!(
+        calculator
+            .iterations
+            .load(std::sync::atomic::Ordering::SeqCst),
+        iterations,
+        "No thread should run after stop"
+    );
+
+    calculator
+        .calculate()
+        .expect("Expected finished calculation to be restartable");
+    assert!(calculator.is_running());
+    calculator.stop();
+}
+
+#[test]
+fn test_cancel_calculation_without_blocking() {
+    let mut config = CalculatorConfig::new(get_course_name_list(3), get_contact_list(9), None);
+    config.set_number_of_threads(2);
+    let mut calculator = Calculator::new(config);
+    assert_eq!(calculator.cancel(), None);
+
+    calculator
+        .calculate()
+        .expect("Expected calculation to start");
+    let state = calculator.cancel();
+    assert!(matches!(
+        state,
+        Some(JobState::Stopping) | Some(JobState::Finished)
+    ));
+
+    // Polling joins the threads once they have returned
+    let start = std::time::Instant::now();
+    while calculator.get_state() != Some(JobState::Finished) {
+        assert!(
+            start.elapsed() < Duration::from_secs(10),
+            "Calculation should stop after cancel"
+        );
+        thread::sleep(Duration::from_millis(10));
+    }
+    assert!(calculator.get_stop_time().is_some());
+}
+#[test]
+fn test_history_is_thinned_out() {
+    let mut history = History::default();
+    for iteration in 1..=10_000 {
+        history.push(IterationStat {
+            iteration,
+            elapsed: Duration::from_millis(iteration as u64),
+            best_score: 1.0,
+            population_best: 1.0,
+            population_mean: 1.0,
+            feasible_count: 1,
+        });
+    }
+    let stat_list = history.get_stat_list();
+    assert!(stat_list.len() < 1000);
+    // The whole run stays covered
+    assert!(stat_list[0].iteration <= 16);
+    assert!(stat_list.last().expect("Expected stat").iteration > 9_900);
+
+    history.clear();
+    assert!(history.get_stat_list().is_empty());
+}
+#[test]
+fn test_number_of_hostings_per_course() {
+    // Same cases as the web client, both have to plan the same number of hostings
+    let parse_list = |field: &str| -> Vec<usize> {
+        match field {
+            "-" => Vec::new(),
+            field => field
+                .split(',')
+                .map(|number| number.parse().expect("Expected number"))
+                .collect(),
+        }
+    };
+    for line in include_str!("data/hostings_per_course.txt")
+        .lines()
+        .filter(|line| !line.starts_with('#'))
+    {
+        let field_list: Vec<&str> = line.split_whitespace().collect();
+        let number_of_teams: usize = field_list[0].parse().expect("Expected teams");
+        let course_name_list =
+            get_course_name_list(field_list[1].parse().expect("Expected courses"));
+        let mut config = CalculatorConfig::new(
+            course_name_list.clone(),
+            get_contact_list(number_of_teams),
+            None,
+        );
+        config.set_guests_per_hosting(field_list[2].parse().ok());
+        config.set_course_with_more_hosts_list(
+            parse_list(field_list[3])
+                .into_iter()
+                .map(|index| course_name_list[index].clone())
+                .collect(),
+        );
+        let hostings: Vec<usize> = course_name_list
+            .iter()
+            .map(|course_name| config.get_number_of_hostings(course_name))
+            .collect();
+        assert_eq!(hostings, parse_list(field_list[4]), "{}", line);
+    }
+}
 //END TEST AREA
@@ -0,0 +1,57 @@
+use tcc::contact::{check_coordinates, validate_contact_list, Contact};
+
+fn get_contact(id: u8, team_name: &str, latitude: f64, longitude: f64) -> Contact {
+    Contact::new(id, team_name, "Address", latitude, longitude)
+}
+
+#[test]
+fn test_valid_contact_list() {
+    let contact_list = vec![
+        get_contact(0, "Team A", 50.110, 8.680),
+        get_contact(1, "Team B", 50.112, 8.684),
+        get_contact(2, "Team C", 50.108, 8.676),
+    ];
+    assert!(validate_contact_list(&contact_list).is_empty());
+}
+
+#[test]
+fn test_duplicate_and_missing_team_name() {
+    let contact_list = vec![
+        get_contact(0, "Team A", 50.110, 8.680),
+        get_contact(1, "Team A", 50.112, 8.684),
+        get_contact(2, " ", 50.108, 8.676),
+    ];
+    let issue_list = validate_contact_list(&contact_list);
+    assert_eq!(issue_list.len(), 2);
+    assert!(issue_list.iter().all(|issue| issue.is_error));
+    assert_eq!(issue_list[0].index, 1);
+    assert_eq!(issue_list[1].index, 2);
+}
+
+#[test]
+fn test_coordinates_out_of_range() {
+    assert!(check_coordinates(50.11, 8.68).is_ok());
+    assert!(check_coordinates(91.0, 8.68).is_err());
+    assert!(check_coordinates(50.11, -181.0).is_err());
+
+    let contact_list = vec![get_contact(0, "Team A", 95.0, 8.680)];
+    let issue_list = validate_contact_list(&contact_list);
+    assert_eq!(issue_list.len(), 1);
+    assert!(issue_list[0].is_error);
+}
+
+#[test]
+fn test_outlier_is_warning() {
+    let contact_list = vec![
+        get_contact(0, "Team A", 50.110, 8.680),
+        get_contact(1, "Team B", 50.112, 8.684),
+        get_contact(2, "Team C", 50.108, 8.676),
+        get_contact(3, "Team D", 50.111, 8.682),
+        // Swapped coordinates
+        get_contact(4, "Team E", 8.680, 50.110),
+    ];
+    let issue_list = validate_contact_list(&contact_list);
+    assert_eq!(issue_list.len(), 1);
+    assert_eq!(issue_list[0].index, 4);
+    assert!(!issue_list[0].is_error);
+}
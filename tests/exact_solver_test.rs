@@ -0,0 +1,378 @@
+use std::{collections::HashSet, time::Duration};
+
+use data::{get_contact_list, get_course_name_list};
+use tcc::{
+    calculation_job::{CancellationToken, JobState},
+    calculator::{calc_distance, solve_exact, Calculator, CalculatorConfig, Plan},
+    contact::Contact,
+};
+
+mod data;
+
+// Every team is at one table per course and meets every other team at most once
+fn check_plan(plan: &Plan, number_of_teams: usize, number_of_courses: usize) {
+    assert_eq!(plan.walking_path.len(), number_of_teams);
+    let mut met_set = HashSet::new();
+    for course_list in plan.course_map.values() {
+        for course in course_list {
+            let mut member_list: Vec<u8> = course.guest_list.iter().map(|guest| guest.id).collect();
+            member_list.push(course.host.id);
+            for (index, first) in member_list.iter().enumerate() {
+                for second in member_list[index + 1..].iter() {
+                    assert!(
+                        met_set.insert((*first.min(second), *first.max(second))),
+                        "Teams {} and {} meet twice",
+                        first,
+                        second
+                    );
+                }
+            }
+        }
+    }
+    for course_list in plan.walking_path.values() {
+        assert_eq!(course_list.len(), number_of_courses);
+    }
+    let host_list: Vec<u8> = plan
+        .course_map
+        .values()
+        .flatten()
+        .map(|course| course.host.id)
+        .collect();
+    let host_set: HashSet<u8> = host_list.iter().cloned().collect();
+    assert_eq!(host_list.len(), host_set.len(), "Teams host only once");
+}
+
+#[test]
+fn test_exact_optimal_plan() {
+    let config = CalculatorConfig::new(get_course_name_list(3), get_contact_list(9), None);
+    let exact_plan = solve_exact(&config, Duration::from_secs(60), None).expect("Expected plan");
+    check_plan(&exact_plan.plan, 9, 3);
+    assert!(
+        exact_plan.optimal,
+        "Nine teams should be solved to optimality"
+    );
+    assert_eq!(exact_plan.gap, 0.0);
+    assert_eq!(exact_plan.lower_bound, exact_plan.plan.score);
+}
+
+#[test]
+fn test_exact_time_limit() {
+    let config = CalculatorConfig::new(get_course_name_list(3), get_contact_list(18), None);
+    let exact_plan = solve_exact(&config, Duration::from_secs(2), None).expect("Expected plan");
+    check_plan(&exact_plan.plan, 18, 3);
+    assert!(exact_plan.lower_bound <= exact_plan.plan.score);
+    assert!((0.0..=1.0).contains(&exact_plan.gap));
+}
+
+#[test]
+fn test_exact_cancel() {
+    let config = CalculatorConfig::new(get_course_name_list(3), get_contact_list(18), None);
+    let token = CancellationToken::new();
+    token.cancel();
+    assert!(
+        solve_exact(&config, Duration::from_secs(60), Some(&token)).is_err(),
+        "Cancelled solver should not find a plan"
+    );
+}
+
+#[test]
+fn test_exact_calculator() {
+    let mut config = CalculatorConfig::new(get_course_name_list(3), get_contact_list(9), None);
+    config.set_exact_solver(true);
+    config.set_time_budget(Some(Duration::from_secs(60)));
+    let mut calculator = Calculator::new(config);
+    calculator
+        .calculate()
+        .expect("Expected calculation to start");
+    assert_eq!(calculator.wait(), Some(JobState::Finished));
+    assert_eq!(
+        *calculator.gap.lock().expect("Failed to lock gap"),
+        Some(0.0)
+    );
+    assert!(calculator
+        .top_plan
+        .lock()
+        .expect("Failed to lock top_plan")
+        .is_some());
+}
+
+#[test]
+fn test_exact_too_many_teams() {
+    let contact_list: Vec<Contact> = (0..31)
+        .map(|index| {
+            Contact::new(
+                index,
+                &format!("Team {}", index + 1),
+                "",
+                50.0 + index as f64 / 100.0,
+                8.6,
+            )
+        })
+        .collect();
+    let mut config = CalculatorConfig::new(get_course_name_list(3), contact_list, None);
+    config.set_course_with_more_hosts_list(get_course_name_list(3)[..1].to_vec());
+    assert!(solve_exact(&config, Duration::from_secs(1), None).is_err());
+
+    config.set_exact_solver(true);
+    assert!(
+        config.check().is_err(),
+        "Exact solver should reject 31 teams"
+    );
+}
+
+// Teams of the brute force check with a start and goal point taken from the remaining contacts
+struct Scenario {
+    contact_list: Vec<Contact>,
+    course_name_list: Vec<String>,
+    start_point: Option<(i32, i32)>,
+    goal_point: Option<(i32, i32)>,
+}
+
+impl Scenario {
+    fn new(number_of_teams: usize, number_of_courses: usize, offset: usize) -> Self {
+        let point_list: Vec<(i32, i32)> = get_contact_list(number_of_teams + offset + 2)
+            [number_of_teams + offset..]
+            .iter()
+            .map(|contact| (contact.latitude, contact.longitude))
+            .collect();
+        Scenario {
+            contact_list: get_contact_list(number_of_teams),
+            course_name_list: get_course_name_list(number_of_courses),
+            start_point: Some(point_list[0]),
+            goal_point: Some(point_list[1]),
+        }
+    }
+
+    fn to_calculator_config(&self) -> CalculatorConfig {
+        let mut config = CalculatorConfig::new_with_start_and_goal(
+            self.start_point,
+            self.goal_point,
+            None,
+            self.course_name_list.clone(),
+            self.contact_list.clone(),
+        );
+        if !self
+            .contact_list
+            .len()
+            .is_multiple_of(self.course_name_list.len())
+        {
+            config.set_course_with_more_hosts_list(
+                self.course_name_list.last().into_iter().cloned().collect(),
+            );
+        }
+        config
+    }
+}
+
+// Longest walk of any team in the plan, courses in the order of the course name list
+fn get_score(scenario: &Scenario, plan: &Plan) -> f64 {
+    plan.walking_path
+        .values()
+        .map(|course_list| {
+            let mut course_list = course_list.clone();
+            course_list.sort_by_key(|course| {
+                scenario
+                    .course_name_list
+                    .iter()
+                    .position(|name| *name == course.name)
+            });
+            let mut stop_list: Vec<(i32, i32)> = course_list
+                .iter()
+                .map(|course| (course.host.latitude, course.host.longitude))
+                .collect();
+            stop_list.splice(0..0, scenario.start_point);
+            stop_list.extend(scenario.goal_point);
+            stop_list
+                .windows(2)
+                .map(|window| calc_distance(window[0].0, window[0].1, window[1].0, window[1].1))
+                .sum::<f64>()
+        })
+        .fold(0.0, f64::max)
+}
+
+// Tries every host and table assignment in which teams host at most once and meet
+// at most once, returns the smallest longest walk
+fn solve_brute_force(scenario: &Scenario, hostings_per_course: usize) -> f64 {
+    let number_of_teams = scenario.contact_list.len();
+    let table_size = number_of_teams / hostings_per_course;
+    let mut best_score = f64::MAX;
+    let mut course_list: Vec<Vec<Vec<usize>>> = Vec::new();
+    choose_hosts(
+        scenario,
+        hostings_per_course,
+        table_size,
+        &mut vec![false; number_of_teams],
+        &mut vec![0; number_of_teams],
+        &mut course_list,
+        &mut best_score,
+    );
+    best_score
+}
+
+fn choose_hosts(
+    scenario: &Scenario,
+    hostings_per_course: usize,
+    table_size: usize,
+    hosted: &mut Vec<bool>,
+    met_mask: &mut Vec<u32>,
+    course_list: &mut Vec<Vec<Vec<usize>>>,
+    best_score: &mut f64,
+) {
+    if course_list.len() == scenario.course_name_list.len() {
+        *best_score = best_score.min(get_brute_force_score(scenario, course_list));
+        return;
+    }
+    let unhosted: Vec<usize> = (0..hosted.len()).filter(|team| !hosted[*team]).collect();
+    for host_list in combinations(&unhosted, hostings_per_course) {
+        for host in &host_list {
+            hosted[*host] = true;
+        }
+        let mut table_list: Vec<Vec<usize>> = host_list.iter().map(|host| vec![*host]).collect();
+        let guest_list: Vec<usize> = (0..hosted.len())
+            .filter(|team| !host_list.contains(team))
+            .collect();
+        seat_guests(
+            scenario,
+            hostings_per_course,
+            table_size,
+            &guest_list,
+            &mut table_list,
+            hosted,
+            met_mask,
+            course_list,
+            best_score,
+        );
+        for host in &host_list {
+            hosted[*host] = false;
+        }
+    }
+}
+
+#[allow(clippy::too_many_arguments)]
+fn seat_guests(
+    scenario: &Scenario,
+    hostings_per_course: usize,
+    table_size: usize,
+    guest_list: &[usize],
+    table_list: &mut Vec<Vec<usize>>,
+    hosted: &mut Vec<bool>,
+    met_mask: &mut Vec<u32>,
+    course_list: &mut Vec<Vec<Vec<usize>>>,
+    best_score: &mut f64,
+) {
+    let Some((&guest, rest)) = guest_list.split_first() else {
+        let saved_mask = met_mask.clone();
+        for table in table_list.iter() {
+            for member in table {
+                met_mask[*member] |= table.iter().fold(0, |mask, other| mask | 1 << other);
+            }
+        }
+        course_list.push(table_list.clone());
+        choose_hosts(
+            scenario,
+            hostings_per_course,
+            table_size,
+            hosted,
+            met_mask,
+            course_list,
+            best_score,
+        );
+        course_list.pop();
+        *met_mask = saved_mask;
+        return;
+    };
+    for index in 0..table_list.len() {
+        let table = &table_list[index];
+        if table.len() == table_size
+            || table
+                .iter()
+                .any(|member| met_mask[guest] & (1 << member) != 0)
+        {
+            continue;
+        }
+        table_list[index].push(guest);
+        seat_guests(
+            scenario,
+            hostings_per_course,
+            table_size,
+            rest,
+            table_list,
+            hosted,
+            met_mask,
+            course_list,
+            best_score,
+        );
+        table_list[index].pop();
+    }
+}
+
+fn combinations(team_list: &[usize], size: usize) -> Vec<Vec<usize>> {
+    if size == 0 {
+        return vec![Vec::new()];
+    }
+    (0..team_list.len())
+        .flat_map(|index| {
+            combinations(&team_list[index + 1..], size - 1)
+                .into_iter()
+                .map(move |mut rest| {
+                    rest.insert(0, team_list[index]);
+                    rest
+                })
+        })
+        .collect()
+}
+
+// Longest walk from the start point over the hosts of every course to the goal point
+fn get_brute_force_score(scenario: &Scenario, course_list: &[Vec<Vec<usize>>]) -> f64 {
+    let point = |team: usize| {
+        let contact = &scenario.contact_list[team];
+        (contact.latitude, contact.longitude)
+    };
+    (0..scenario.contact_list.len())
+        .map(|team| {
+            let mut stop_list: Vec<(i32, i32)> = course_list
+                .iter()
+                .map(|table_list| {
+                    let table = table_list
+                        .iter()
+                        .find(|table| table.contains(&team))
+                        .expect("Expected team at a table");
+                    point(table[0])
+                })
+                .collect();
+            stop_list.splice(0..0, scenario.start_point);
+            stop_list.extend(scenario.goal_point);
+            stop_list
+                .windows(2)
+                .map(|window| calc_distance(window[0].0, window[0].1, window[1].0, window[1].1))
+                .sum::<f64>()
+        })
+        .fold(0.0, f64::max)
+}
+
+#[test]
+fn test_exact_matches_brute_force() {
+    // Every team hosts, and only some of the teams host
+    for (number_of_teams, guests_per_hosting, hostings_per_course, offset_list) in
+        [(6, None, 3, 0..3), (9, Some(2), 3, 0..1)]
+    {
+        for offset in offset_list {
+            let scenario = Scenario::new(number_of_teams, 2, offset);
+            let mut config = scenario.to_calculator_config();
+            config.set_guests_per_hosting(guests_per_hosting);
+
+            let exact_plan =
+                solve_exact(&config, Duration::from_secs(60), None).expect("Expected plan");
+            assert!(exact_plan.optimal);
+            let brute_force_score = solve_brute_force(&scenario, hostings_per_course);
+            assert!(
+                (get_score(&scenario, &exact_plan.plan) - brute_force_score).abs() < 1e-9,
+                "Offset {}, {} teams: exact {}, brute force {}",
+                offset,
+                number_of_teams,
+                exact_plan.plan.score,
+                brute_force_score
+            );
+        }
+    }
+}